@@ -2,7 +2,8 @@ use std::collections::HashMap;
 
 use criterion::{black_box, criterion_group, criterion_main, Criterion};
 use deckgym::card_ids::CardId;
-use deckgym::models::{Attack, Card, EnergyType, PokemonCard};
+use deckgym::database::{get_card_by_enum, get_card_ref};
+use deckgym::models::{Attack, EnergyType, PokemonCard};
 use lazy_static::lazy_static;
 
 fn num_match_approach(id: u16) -> Option<CardId> {
@@ -38,7 +39,7 @@ fn lazy_static_map_approach(id: &str) -> Option<CardId> {
 }
 
 lazy_static! {
-    static ref BULBASAUR: Card = Card::Pokemon(PokemonCard {
+    static ref BULBASAUR: PokemonCard = PokemonCard {
         id: "A1 001".to_string(),
         name: "Bulbasaur".to_string(),
         stage: 0,
@@ -56,19 +57,19 @@ lazy_static! {
         retreat_cost: vec![EnergyType::Colorless],
         rarity: "◇".to_string(),
         booster_pack: "Genetic Apex (A1) Mewtwo".to_string(),
-    });
+    };
 }
 
-pub fn get_card_lazy_map(id: CardId) -> &'static Card {
+pub fn get_card_lazy_map(id: CardId) -> &'static PokemonCard {
     match id {
         CardId::A1001Bulbasaur => &BULBASAUR,
         _ => panic!("Card not found"),
     }
 }
 
-pub fn get_card_create(id: CardId) -> Card {
+pub fn get_card_create(id: CardId) -> PokemonCard {
     match id {
-        CardId::A1001Bulbasaur => Card::Pokemon(PokemonCard {
+        CardId::A1001Bulbasaur => PokemonCard {
             id: "A1 001".to_string(),
             name: "Bulbasaur".to_string(),
             stage: 0,
@@ -86,7 +87,7 @@ pub fn get_card_create(id: CardId) -> Card {
             retreat_cost: vec![EnergyType::Colorless],
             rarity: "◇".to_string(),
             booster_pack: "Genetic Apex (A1) Mewtwo".to_string(),
-        }),
+        },
         _ => panic!("Card not found"),
     }
 }
@@ -108,6 +109,16 @@ fn criterion_benchmark(c: &mut Criterion) {
     c.bench_function("get_card_lazy_map", |b| {
         b.iter(|| get_card_lazy_map(black_box(CardId::A1001Bulbasaur)))
     });
+
+    c.bench_function("from_card_id", |b| {
+        b.iter(|| CardId::from_card_id(black_box("A1 001")))
+    });
+    c.bench_function("get_card_ref", |b| {
+        b.iter(|| get_card_ref(black_box(CardId::A1001Bulbasaur)))
+    });
+    c.bench_function("get_card_by_enum", |b| {
+        b.iter(|| get_card_by_enum(black_box(CardId::A1001Bulbasaur)))
+    });
 }
 
 criterion_group!(benches, criterion_benchmark);
@@ -1,14 +1,27 @@
 use criterion::{black_box, criterion_group, criterion_main, Criterion};
 use deckgym::{
-    players::{Player, RandomPlayer},
+    players::{MctsPlayer, Player, RandomPlayer},
     test_helpers::load_test_decks,
+    Game,
 };
 
-fn play_random_game(seed: u64) {
+fn random_game(seed: u64) -> Game<'static> {
     let (deck_a, deck_b) = load_test_decks();
     let player_a = Box::new(RandomPlayer { deck: deck_a });
     let player_b = Box::new(RandomPlayer { deck: deck_b });
     let players: Vec<Box<dyn Player + Send + 'static>> = vec![player_a, player_b];
+    deckgym::Game::new(players, seed)
+}
+
+fn play_random_game(seed: u64) {
+    random_game(seed).play();
+}
+
+fn play_mcts_game(seed: u64) {
+    let (deck_a, deck_b) = load_test_decks();
+    let player_a = Box::new(MctsPlayer::new(deck_a, 10));
+    let player_b = Box::new(RandomPlayer { deck: deck_b });
+    let players: Vec<Box<dyn Player + Send + 'static>> = vec![player_a, player_b];
     let mut game = deckgym::Game::new(players, seed);
     game.play();
 }
@@ -17,6 +30,22 @@ fn criterion_benchmark(c: &mut Criterion) {
     c.bench_function("play random game", |b| {
         b.iter(|| play_random_game(black_box(20)))
     });
+
+    // Search players clone the state for every node they expand
+    let mut game = random_game(20);
+    for _ in 0..30 {
+        game.play_tick();
+    }
+    c.bench_function("clone mid-game state", |b| {
+        b.iter(|| black_box(&game).get_state_clone())
+    });
+
+    let mut group = c.benchmark_group("search");
+    group.sample_size(10);
+    group.bench_function("play mcts game", |b| {
+        b.iter(|| play_mcts_game(black_box(20)))
+    });
+    group.finish();
 }
 
 criterion_group!(benches, criterion_benchmark);
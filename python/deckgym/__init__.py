@@ -11,10 +11,18 @@ from .deckgym import (
     PyGameOutcome as GameOutcome,
     PySimulationResults as SimulationResults,
     py_simulate as simulate,
+    PyCombinationResult as CombinationResult,
+    PyOptimizationReport as OptimizationReport,
+    py_optimize as optimize,
+    PyDeckOdds as DeckOdds,
+    py_deck_odds as deck_odds,
     get_player_types,
     PyBatchedSimulator,
     get_all_cards,
     get_card,
+    load_card_database,
+    load_custom_cards,
+    search_cards,
 )
 from gymnasium.envs.registration import register
 from .envs.ptcgp_env import PTCGPEnv
@@ -32,9 +40,17 @@ __all__ = [
     "GameOutcome",
     "SimulationResults",
     "simulate",
+    "CombinationResult",
+    "OptimizationReport",
+    "optimize",
+    "DeckOdds",
+    "deck_odds",
     "get_player_types",
     "get_all_cards",
     "get_card",
+    "load_card_database",
+    "load_custom_cards",
+    "search_cards",
     "PyBatchedSimulator",
     "PTCGPEnv",
 ]
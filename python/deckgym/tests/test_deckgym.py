@@ -55,6 +55,73 @@ def test_simulation():
     )
     assert results.total_games == 10
     assert results.player_a_wins + results.player_b_wins + results.ties == 10
+    assert sum(results.end_reasons.values()) == 10
+
+
+def test_end_reason():
+    deck_a = deck_dir / "venusaur-exeggutor.txt"
+    deck_b = deck_dir / "weezing-arbok.txt"
+    if not (deck_a.exists() and deck_b.exists()):
+        pytest.skip("Required deck files not found")
+    game = deckgym.Game(str(deck_a), str(deck_b), ["r", "r"], seed=42)
+    assert game.get_state().end_reason is None
+    game.play()
+    assert game.get_state().end_reason in (
+        "points_reached",
+        "no_pokemon_in_play",
+        "simultaneous_knockout_tie",
+        "turn_limit",
+    )
+
+
+def test_optimize():
+    incomplete = deck_dir / "incomplete-chari.txt"
+    enemies = [deck_dir / "weezing-arbok.txt", deck_dir / "mewtwoex.txt"]
+    if not (incomplete.exists() and all(e.exists() for e in enemies)):
+        pytest.skip("Required deck files not found")
+    report = deckgym.optimize(
+        str(incomplete),
+        ["A2 147", "A2 148"],
+        [str(e) for e in enemies],
+        num_games=2,
+        players=["r", "r"],
+        seed=3,
+    )
+    assert report.enemy_decks == ["weezing-arbok", "mewtwoex"]
+    best = report.combinations[0]
+    assert best.games == 4
+    assert set(best.per_enemy_win_rates) == {"weezing-arbok", "mewtwoex"}
+    assert '"combinations"' in report.to_json()
+
+
+def test_deck_validation():
+    deck_path = deck_dir / "venusaur-exeggutor.txt"
+    if not deck_path.exists():
+        pytest.skip("Required deck files not found")
+    deck = deckgym.Deck(str(deck_path))
+    assert deck.is_valid
+    assert deck.validate() == []
+
+
+def test_deck_code_round_trip():
+    deck_path = deck_dir / "venusaur-exeggutor.txt"
+    if not deck_path.exists():
+        pytest.skip("Required deck files not found")
+    deck = deckgym.Deck(str(deck_path))
+    decoded = deckgym.Deck.from_code(deck.to_code())
+    assert decoded.card_count == 20
+    assert decoded.to_deckgym_string() == deck.to_deckgym_string()
+
+
+def test_deck_odds():
+    deck = deck_dir / "venusaur-exeggutor.txt"
+    if not deck.exists():
+        pytest.skip("Required deck files not found")
+    odds = deckgym.deck_odds(str(deck), ["A1 001", "A1 002"], turns=4)
+    assert odds.basics_in_opening_hand[0] == 0.0
+    assert abs(sum(odds.basics_in_opening_hand) - 1.0) < 1e-9
+    assert len(odds.going_first) == len(odds.going_second) == 4
+    assert odds.going_first[0] >= odds.targets_in_opening_hand
 
 
 def test_performance():
@@ -247,3 +314,45 @@ def test_player_types_dict():
     for k, v in types.items():
         assert isinstance(k, str)
         assert isinstance(v, str)
+
+
+def test_load_card_database(tmp_path):
+    bulbasaur = deckgym.get_card("A1 001")
+    database = tmp_path / "database.json"
+    database.write_text(
+        '[{"Pokemon": {"id": "Z9 001", "name": "Bulbasaur", "stage": 0, '
+        '"evolves_from": null, "hp": 70, "energy_type": "Grass", "ability": null, '
+        '"attacks": [], "weakness": "Fire", "retreat_cost": ["Colorless"], '
+        '"rarity": "", "booster_pack": ""}}]'
+    )
+    assert deckgym.load_card_database(str(database)) == 1
+    card = deckgym.get_card("Z9 001")
+    assert card.name == bulbasaur.name
+    with pytest.raises(ValueError):
+        deckgym.load_card_database(str(tmp_path / "missing.json"))
+
+
+def test_load_custom_cards(tmp_path):
+    cards = tmp_path / "custom.json"
+    cards.write_text(
+        '[{"Pokemon": {"name": "Glass Cannon", "hp": 40, "energy_type": "Fire", '
+        '"attacks": [{"title": "Blast", "energy_required": ["Fire"], "damage": 90, '
+        '"mechanic": {"SelfDamage": {"amount": 20}}}]}}]'
+    )
+    [card_id] = deckgym.load_custom_cards(str(cards))
+    assert card_id.startswith("CUSTOM ")
+    assert deckgym.get_card(card_id).name == "Glass Cannon"
+    with pytest.raises(ValueError):
+        deckgym.load_custom_cards(str(tmp_path / "missing.json"))
+
+
+def test_search_cards():
+    cards = deckgym.search_cards(name="venusaur", attack="giant bloom")
+    assert "A1 004" in [card.id for card in cards]
+    assert all(card.is_pokemon for card in cards)
+
+    cards = deckgym.search_cards(energy_types=["grass"], stage=2, min_hp=150)
+    assert cards
+    assert all(card.stage == 2 and card.hp >= 150 for card in cards)
+    with pytest.raises(ValueError):
+        deckgym.search_cards(energy_types=["Wood"])
@@ -254,7 +254,7 @@ fn apply_attach_tool(state: &mut State, actor: usize, in_play_idx: usize, tool_c
     state.in_play_pokemon[actor][in_play_idx]
         .as_mut()
         .expect("Pokemon should be there if attaching tool to it")
-        .attached_tool = Some(tool_card.clone());
+        .attached_tool = Some(*tool_card);
     on_attach_tool(state, actor, in_play_idx, trainer_card);
 }
 
@@ -318,7 +318,7 @@ fn apply_return_pokemon_to_hand(acting_player: usize, state: &mut State, in_play
         .take()
         .expect("Pokemon should be there if returning to hand");
     let mut cards_to_collect = played_card.cards_behind.clone();
-    cards_to_collect.push(played_card.card.clone());
+    cards_to_collect.push(played_card.card);
     let count = cards_to_collect.len();
     state.hands[acting_player].extend(cards_to_collect);
     state.hands_visibility[acting_player].extend(std::iter::repeat_n(true, count));
@@ -467,9 +467,9 @@ pub(crate) fn apply_evolve(
         let damage_taken = from_pokemon.total_hp - from_pokemon.remaining_hp;
         played_card.remaining_hp -= damage_taken;
         played_card.attached_energy = from_pokemon.attached_energy.clone();
-        played_card.attached_tool = from_pokemon.attached_tool.clone();
+        played_card.attached_tool = from_pokemon.attached_tool;
         played_card.cards_behind = from_pokemon.cards_behind.clone();
-        played_card.cards_behind.push(from_pokemon.card.clone());
+        played_card.cards_behind.push(from_pokemon.card);
         state.in_play_pokemon[acting_player][position] = Some(played_card);
     } else {
         panic!("Only Pokemon cards can be evolved");
@@ -508,7 +508,7 @@ fn forecast_pokemon_communication(
     let probabilities = vec![1.0 / (num_deck_pokemon as f64); num_deck_pokemon];
     let mut outcomes: Mutations = vec![];
     for i in 0..num_deck_pokemon {
-        let hand_pokemon_clone = hand_pokemon.clone();
+        let hand_pokemon_clone = *hand_pokemon;
         outcomes.push(Box::new(move |rng, state, action| {
             // Get the i-th Pokemon from deck
             let deck_pokemon_card = state
@@ -545,7 +545,7 @@ fn forecast_shuffle_pokemon_into_deck(
     hand_pokemon: &Card,
     amount_left: usize,
 ) -> (Probabilities, Mutations) {
-    let pokemon = hand_pokemon.clone();
+    let pokemon = *hand_pokemon;
     (
         vec![1.0],
         vec![Box::new(move |_rng, state, _action| {
@@ -591,7 +591,7 @@ fn forecast_shuffle_opponent_supporter(
     acting_player: usize,
     supporter_card: &Card,
 ) -> (Probabilities, Mutations) {
-    let supporter_clone = supporter_card.clone();
+    let supporter_clone = *supporter_card;
     (
         vec![1.0],
         vec![Box::new(move |rng, state, _action| {
@@ -610,7 +610,7 @@ fn forecast_discard_opponent_supporter(
     acting_player: usize,
     supporter_card: &Card,
 ) -> (Probabilities, Mutations) {
-    let supporter_clone = supporter_card.clone();
+    let supporter_clone = *supporter_card;
     (
         vec![1.0],
         vec![Box::new(move |_rng, state, _action| {
@@ -629,7 +629,7 @@ fn forecast_discard_own_card(
     card: &Card,
     amount_left: usize,
 ) -> (Probabilities, Mutations) {
-    let card_clone = card.clone();
+    let card_clone = *card;
     (
         vec![1.0],
         vec![Box::new(move |_rng, state, _action| {
@@ -764,7 +764,7 @@ mod tests {
         healthy_bench.remaining_hp = 50;
         healthy_bench.attached_energy = vec![energy, energy, energy];
         state.in_play_pokemon[0][2] = Some(healthy_bench);
-        state.hands[0] = vec![primeape.clone(), primeape.clone()];
+        state.hands[0] = vec![primeape, primeape];
         state.hands_visibility[0] = vec![false, false];
 
         // Evolve Active
@@ -772,12 +772,12 @@ mod tests {
         assert_eq!(
             state.in_play_pokemon[0][0],
             Some(PlayedCard::new(
-                primeape.clone(),
+                primeape,
                 60, // 90 - 30 = 60
                 90,
                 vec![energy],
                 true,
-                vec![mankey.clone()]
+                vec![mankey]
             ))
         );
 
@@ -786,23 +786,23 @@ mod tests {
         assert_eq!(
             state.in_play_pokemon[0][0],
             Some(PlayedCard::new(
-                primeape.clone(),
+                primeape,
                 60, // 90 - 30 = 60
                 90,
                 vec![energy],
                 true,
-                vec![mankey.clone()]
+                vec![mankey]
             ))
         );
         assert_eq!(
             state.in_play_pokemon[0][2],
             Some(PlayedCard::new(
-                primeape.clone(),
+                primeape,
                 90, // 90 - 0 = 90
                 90,
                 vec![energy, energy, energy],
                 true,
-                vec![mankey.clone()]
+                vec![mankey]
             ))
         );
     }
@@ -13,7 +13,7 @@ use crate::{
         get_counterattack_damage, modify_damage, on_end_turn, on_knockout, should_poison_attacker,
     },
     models::Card,
-    state::GameOutcome,
+    state::{GameEndReason, GameOutcome},
     State,
 };
 
@@ -477,13 +477,13 @@ pub(crate) fn handle_damage(
     // Note even attacking player can lose by counterattack K.O.
     if state.points[0] >= 3 && state.points[1] >= 3 {
         debug!("Both players have 3 points, it's a tie");
-        state.winner = Some(GameOutcome::Tie);
+        state.declare_outcome(GameOutcome::Tie, GameEndReason::SimultaneousKnockoutTie);
         return;
     } else if state.points[0] >= 3 {
-        state.winner = Some(GameOutcome::Win(0));
+        state.declare_outcome(GameOutcome::Win(0), GameEndReason::PointsReached);
         return;
     } else if state.points[1] >= 3 {
-        state.winner = Some(GameOutcome::Win(1));
+        state.declare_outcome(GameOutcome::Win(1), GameEndReason::PointsReached);
         return;
     }
 
@@ -504,7 +504,7 @@ pub(crate) fn apply_common_mutation(state: &mut State, action: &Action) {
         state.move_generation_stack.pop();
     }
     if let SimpleAction::Play { trainer_card } = &action.action {
-        let card = Card::Trainer(trainer_card.clone());
+        let card = Card::Trainer(trainer_card);
         state.discard_card_from_hand(action.actor, &card);
         if card.is_support() {
             state.has_played_support = true;
@@ -620,7 +620,7 @@ mod tests {
         let played_meloetta = to_playable_card(&meloetta_card, false);
 
         let mut deck = Deck::default();
-        deck.cards.push(meloetta_card.clone()); // Only one Meloetta in deck
+        deck.cards.push(meloetta_card); // Only one Meloetta in deck
 
         let mut state = State::new(&Deck::default(), &deck);
         state.in_play_pokemon[1][0] = Some(played_meloetta);
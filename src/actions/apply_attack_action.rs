@@ -9,7 +9,7 @@ use crate::{
             collect_in_play_indices_by_type, energy_any_way_choices, generate_distributions,
         },
         attacks::{BenchSide, Mechanic},
-        effect_mechanic_map::EFFECT_MECHANIC_MAP,
+        effect_mechanic_map::attack_mechanic_from_effect,
         mutations::{doutcome, doutcome_from_mutation},
         Action,
     },
@@ -78,7 +78,7 @@ fn forecast_attack_inner(
     if let Some(attack_id) = AttackId::from_pokemon_index(&card_id[..], index) {
         forecast_effect_attack_by_attack_id(state, attack_id)
     } else {
-        let mechanic = EFFECT_MECHANIC_MAP.get(&effect_text[..]);
+        let mechanic = attack_mechanic_from_effect(&effect_text[..]);
         let Some(mechanic) = mechanic else {
             panic!(
                 "No implementation found for attack effect: {:?} on attack {:?} of Pokemon {}",
@@ -1374,19 +1374,17 @@ fn vaporeon_hyper_whirlpool(_state: &State, damage: u32) -> (Probabilities, Muta
             damage,
             move |_, state, action| {
                 let opponent = (action.actor + 1) % 2;
-                let mut to_discard = Vec::new();
-
-                // Collect energies to discard
-                for _ in 0..energies_to_remove {
-                    let active = state.get_active(opponent);
-                    if active.attached_energy.is_empty() {
-                        break; // No more energy to discard
-                    }
-                    // NOTE: Using last energy instead of random selection to avoid expanding the game tree.
-                    // This is a simplification - the card text says "random Energy" but we always
-                    // remove the last one for performance reasons.
-                    to_discard.push(*active.attached_energy.last().unwrap());
-                }
+                // NOTE: Using the last energies instead of random selection to avoid expanding the game tree.
+                // This is a simplification - the card text says "random Energy" but we always
+                // remove the last ones for performance reasons.
+                let to_discard: Vec<EnergyType> = state
+                    .get_active(opponent)
+                    .attached_energy
+                    .iter()
+                    .rev()
+                    .take(energies_to_remove)
+                    .copied()
+                    .collect();
 
                 // Discard collected energies properly (moves to discard pile)
                 if !to_discard.is_empty() {
@@ -1976,7 +1974,7 @@ fn ominous_claw_attack(acting_player: usize, _state: &State) -> (Probabilities,
             let possible_discards: Vec<SimpleAction> = state
                 .iter_hand_supporters(opponent)
                 .map(|card| SimpleAction::DiscardOpponentSupporter {
-                    supporter_card: card.clone(),
+                    supporter_card: *card,
                 })
                 .collect();
 
@@ -1999,7 +1997,7 @@ fn darkness_claw_attack(acting_player: usize, _state: &State) -> (Probabilities,
         let possible_discards: Vec<SimpleAction> = state
             .iter_hand_supporters(opponent)
             .map(|card| SimpleAction::DiscardOpponentSupporter {
-                supporter_card: card.clone(),
+                supporter_card: *card,
             })
             .collect();
 
@@ -2023,7 +2021,7 @@ fn dirty_throw_attack(acting_player: usize, state: &State) -> (Probabilities, Mu
             let possible_discards: Vec<SimpleAction> = state.hands[action.actor]
                 .iter()
                 .map(|card| SimpleAction::DiscardOwnCard {
-                    card: card.clone(),
+                    card: *card,
                     amount_left: 1,
                 })
                 .collect();
@@ -2049,7 +2047,7 @@ fn discard_hand_card_attack(
             let possible_discards: Vec<SimpleAction> = state.hands[action.actor]
                 .iter()
                 .map(|card| SimpleAction::DiscardOwnCard {
-                    card: card.clone(),
+                    card: *card,
                     amount_left: count,
                 })
                 .collect();
@@ -2112,7 +2110,7 @@ fn shuffle_opponent_active_into_deck() -> (Probabilities, Mutations) {
 
             // Put the card (and evolution chain) back into deck
             let mut cards_to_shuffle = active_pokemon.cards_behind.clone();
-            cards_to_shuffle.push(active_pokemon.card.clone());
+            cards_to_shuffle.push(active_pokemon.card);
 
             // Add cards to deck
             state.decks[opponent].cards.extend(cards_to_shuffle);
@@ -2433,6 +2431,22 @@ fn coin_flip_to_block_attack_next_turn(damage: u32) -> (Probabilities, Mutations
     })
 }
 
+fn damage_and_both_multiple_status_attack(
+    damage: u32,
+    statuses: Vec<StatusCondition>,
+) -> (Probabilities, Mutations) {
+    active_damage_effect_doutcome(damage, move |_, state, action| {
+        let active = state.get_active_mut(action.actor);
+        for status in &statuses {
+            active.apply_status_condition(*status);
+        }
+        let opponent_active = state.get_active_mut(1 - action.actor);
+        for status in &statuses {
+            opponent_active.apply_status_condition(*status);
+        }
+    })
+}
+
 #[cfg(test)]
 mod test {
     use rand::{rngs::StdRng, SeedableRng};
@@ -2683,19 +2697,3 @@ mod test {
         assert_eq!(state.get_active(1).remaining_hp, 70);
     }
 }
-
-fn damage_and_both_multiple_status_attack(
-    damage: u32,
-    statuses: Vec<StatusCondition>,
-) -> (Probabilities, Mutations) {
-    active_damage_effect_doutcome(damage, move |_, state, action| {
-        let active = state.get_active_mut(action.actor);
-        for status in &statuses {
-            active.apply_status_condition(*status);
-        }
-        let opponent_active = state.get_active_mut(1 - action.actor);
-        for status in &statuses {
-            opponent_active.apply_status_condition(*status);
-        }
-    })
-}
@@ -12,11 +12,13 @@ use crate::{
             pokemon_search_outcomes,
         },
     },
+    card_equivalence::canonical_card_id,
     card_ids::CardId,
     card_logic::{
         can_rare_candy_evolve, diantha_targets, ilima_targets, quick_grow_extract_candidates,
     },
     combinatorics::generate_combinations,
+    custom_cards::trainer_card_id,
     effects::TurnEffect,
     hooks::{get_stage, is_ultra_beast},
     models::{Card, EnergyType, TrainerCard, TrainerType},
@@ -59,122 +61,66 @@ fn forecast_trainer_action_inner(
         panic!("Unsupported Trainer Tool");
     }
 
-    let trainer_id = CardId::from_card_id(trainer_card.id.as_str()).unwrap_or_else(|| {
+    let trainer_id = trainer_card_id(&trainer_card.id).unwrap_or_else(|| {
         panic!(
             "CardId should be known for trainer: {} ({})",
             trainer_card.name, trainer_card.id
         )
     });
-    match trainer_id {
+    match canonical_card_id(trainer_id) {
         CardId::PA001Potion => doutcome(potion_effect),
         CardId::PA002XSpeed => doutcome(x_speed_effect),
-        CardId::PA005PokeBall | CardId::A2b111PokeBall => {
-            pokemon_search_outcomes(acting_player, state, true, "Poke Ball")
-        }
+        CardId::A2b111PokeBall => pokemon_search_outcomes(acting_player, state, true, "Poke Ball"),
         CardId::PA006RedCard => doutcome(red_card_effect),
-        CardId::PA007ProfessorsResearch | CardId::A4b373ProfessorsResearch => {
-            doutcome(professor_oak_effect)
-        }
-        CardId::A1219Erika | CardId::A1266Erika | CardId::A4b328Erika | CardId::A4b329Erika => {
-            doutcome(erika_effect)
-        }
-        CardId::A1220Misty | CardId::A1267Misty => misty_outcomes(),
-        CardId::A1221Blaine | CardId::A1268Blaine => doutcome(blaine_effect),
-        CardId::A1224Brock | CardId::A1271Brock => doutcome(brock_effect),
-        CardId::A2a072Irida | CardId::A2a087Irida | CardId::A4b330Irida | CardId::A4b331Irida => {
-            doutcome(irida_effect)
-        }
-        CardId::A2b070PokemonCenterLady | CardId::A2b089PokemonCenterLady => {
-            doutcome(pokemon_center_lady_effect)
-        }
-        CardId::A3155Lillie
-        | CardId::A3197Lillie
-        | CardId::A3209Lillie
-        | CardId::A4b348Lillie
-        | CardId::A4b349Lillie
-        | CardId::A4b374Lillie => doutcome(lillie_effect),
-        CardId::A1222Koga | CardId::A1269Koga => doutcome(koga_effect),
-        CardId::A1223Giovanni
-        | CardId::A1270Giovanni
-        | CardId::A4b334Giovanni
-        | CardId::A4b335Giovanni => doutcome(giovanni_effect),
-        CardId::A2b071Red | CardId::A2b090Red | CardId::A4b352Red | CardId::A4b353Red => {
-            doutcome(red_effect)
-        }
-        CardId::A1225Sabrina
-        | CardId::A1272Sabrina
-        | CardId::A4b338Sabrina
-        | CardId::A4b339Sabrina => doutcome(sabrina_effect),
+        CardId::A4b373ProfessorsResearch => doutcome(professor_oak_effect),
+        CardId::A1219Erika => doutcome(erika_effect),
+        CardId::A1220Misty => misty_outcomes(),
+        CardId::A1221Blaine => doutcome(blaine_effect),
+        CardId::A1224Brock => doutcome(brock_effect),
+        CardId::A2a072Irida => doutcome(irida_effect),
+        CardId::A2b070PokemonCenterLady => doutcome(pokemon_center_lady_effect),
+        CardId::A3155Lillie => doutcome(lillie_effect),
+        CardId::A1222Koga => doutcome(koga_effect),
+        CardId::A1223Giovanni => doutcome(giovanni_effect),
+        CardId::A2b071Red => doutcome(red_effect),
+        CardId::A1225Sabrina => doutcome(sabrina_effect),
         CardId::A1a065MythicalSlab => doutcome(mythical_slab_effect),
-        CardId::A1a068Leaf | CardId::A1a082Leaf | CardId::A4b346Leaf | CardId::A4b347Leaf => {
-            doutcome(leaf_effect)
-        }
-        CardId::A2150Cyrus | CardId::A2190Cyrus | CardId::A4b326Cyrus | CardId::A4b327Cyrus => {
-            doutcome(cyrus_effect)
-        }
-        CardId::A2155Mars | CardId::A2195Mars | CardId::A4b344Mars | CardId::A4b345Mars => {
-            doutcome(mars_effect)
-        }
-        CardId::A3144RareCandy
-        | CardId::A4b314RareCandy
-        | CardId::A4b315RareCandy
-        | CardId::A4b379RareCandy => doutcome(rare_candy_effect),
+        CardId::A1a068Leaf => doutcome(leaf_effect),
+        CardId::A2150Cyrus => doutcome(cyrus_effect),
+        CardId::A2155Mars => doutcome(mars_effect),
+        CardId::A3144RareCandy => doutcome(rare_candy_effect),
         CardId::A3a064Repel => doutcome(repel_effect),
-        CardId::A2146PokemonCommunication
-        | CardId::A4b316PokemonCommunication
-        | CardId::A4b317PokemonCommunication => doutcome(pokemon_communication_effect),
-        CardId::A4151ElementalSwitch
-        | CardId::A4b310ElementalSwitch
-        | CardId::A4b311ElementalSwitch => doutcome(elemental_switch_effect),
-        CardId::A3a067Gladion | CardId::A3a081Gladion => {
-            gladion_search_outcomes(acting_player, state)
-        }
-        CardId::A3a069Lusamine
-        | CardId::A3a083Lusamine
-        | CardId::A4b350Lusamine
-        | CardId::A4b351Lusamine
-        | CardId::A4b375Lusamine => doutcome(lusamine_effect),
-        CardId::A3149Ilima | CardId::A3191Ilima => doutcome(ilima_effect),
-        CardId::A4157Lyra | CardId::A4197Lyra | CardId::A4b332Lyra | CardId::A4b333Lyra => {
-            doutcome(lyra_effect)
-        }
-        CardId::A4158Silver | CardId::A4198Silver | CardId::A4b336Silver | CardId::A4b337Silver => {
-            doutcome(silver_effect)
-        }
-        CardId::A3b066EeveeBag | CardId::A4b308EeveeBag | CardId::A4b309EeveeBag => {
-            doutcome(eevee_bag_effect)
-        }
-
-        CardId::B1217FlamePatch | CardId::B1331FlamePatch => doutcome(flame_patch_effect),
-        CardId::B1225Copycat | CardId::B1270Copycat => doutcome(copycat_effect),
-        CardId::A2b069Iono | CardId::A2b088Iono | CardId::A4b340Iono | CardId::A4b341Iono => {
-            doutcome(iono_effect)
-        }
-        CardId::A2b072TeamRocketGrunt | CardId::A2b091TeamRocketGrunt => {
-            team_rocket_grunt_outcomes()
-        }
-        CardId::B1223May | CardId::B1268May => may_effect(acting_player, state),
-        CardId::B1224Fantina | CardId::B1269Fantina => doutcome(fantina_effect),
-        CardId::B1226Lisia | CardId::B1271Lisia => lisia_effect(acting_player, state),
-        CardId::A2a073CelesticTownElder | CardId::A2a088CelesticTownElder => {
-            celestic_town_elder_effect(acting_player, state)
-        }
-        CardId::A2a074Barry | CardId::A2a089Barry => doutcome(barry_effect),
-        CardId::A2a075Adaman | CardId::A2a090Adaman => doutcome(adaman_effect),
-        CardId::A1a067Blue | CardId::A1a081Blue => doutcome(blue_effect),
-        CardId::B2149Diantha | CardId::B2190Diantha => doutcome(diantha_effect),
-        CardId::B2150Sightseer | CardId::B2191Sightseer => doutcome(sightseer_effect),
-        CardId::B2151Juggler | CardId::B2192Juggler => doutcome(juggler_effect),
-        CardId::B2152Piers | CardId::B2193Piers => doutcome(piers_effect),
+        CardId::A2146PokemonCommunication => doutcome(pokemon_communication_effect),
+        CardId::A4151ElementalSwitch => doutcome(elemental_switch_effect),
+        CardId::A3a067Gladion => gladion_search_outcomes(acting_player, state),
+        CardId::A3a069Lusamine => doutcome(lusamine_effect),
+        CardId::A3149Ilima => doutcome(ilima_effect),
+        CardId::A4157Lyra => doutcome(lyra_effect),
+        CardId::A4158Silver => doutcome(silver_effect),
+        CardId::A3b066EeveeBag => doutcome(eevee_bag_effect),
+
+        CardId::B1217FlamePatch => doutcome(flame_patch_effect),
+        CardId::B1225Copycat => doutcome(copycat_effect),
+        CardId::A2b069Iono => doutcome(iono_effect),
+        CardId::A2b072TeamRocketGrunt => team_rocket_grunt_outcomes(),
+        CardId::B1223May => may_effect(acting_player, state),
+        CardId::B1224Fantina => doutcome(fantina_effect),
+        CardId::B1226Lisia => lisia_effect(acting_player, state),
+        CardId::A2a073CelesticTownElder => celestic_town_elder_effect(acting_player, state),
+        CardId::A2a074Barry => doutcome(barry_effect),
+        CardId::A2a075Adaman => doutcome(adaman_effect),
+        CardId::A1a067Blue => doutcome(blue_effect),
+        CardId::B2149Diantha => doutcome(diantha_effect),
+        CardId::B2150Sightseer => doutcome(sightseer_effect),
+        CardId::B2151Juggler => doutcome(juggler_effect),
+        CardId::B2152Piers => doutcome(piers_effect),
         CardId::B1a066ClemontsBackpack => doutcome(clemonts_backpack_effect),
-        CardId::B1a068Clemont | CardId::B1a081Clemont => clemont_effect(acting_player, state),
-        CardId::B1a067QuickGrowExtract | CardId::B1a103QuickGrowExtract => {
-            quick_grow_extract_effect(acting_player, state)
-        }
-        CardId::B1a069Serena | CardId::B1a082Serena => serena_effect(acting_player, state),
+        CardId::B1a068Clemont => clemont_effect(acting_player, state),
+        CardId::B1a067QuickGrowExtract => quick_grow_extract_effect(acting_player, state),
+        CardId::B1a069Serena => serena_effect(acting_player, state),
         CardId::B2145LuckyIcePop => doutcome(lucky_ice_pop_effect),
-        CardId::A4156Will | CardId::A4196Will => doutcome(will_effect),
-        CardId::A3151Guzma | CardId::A3193Guzma | CardId::A3208Guzma => doutcome(guzma_effect),
+        CardId::A4156Will => doutcome(will_effect),
+        CardId::A3151Guzma => doutcome(guzma_effect),
         // Stadium cards
         CardId::B2153TrainingArea | CardId::B2154StartingPlains | CardId::B2155PeculiarPlaza => {
             doutcome(stadium_effect)
@@ -186,9 +132,7 @@ fn forecast_trainer_action_inner(
                 doutcome(stadium_effect)
             }
         }
-        CardId::B2a086ElectricGenerator | CardId::B2a131ElectricGenerator => {
-            electric_generator_outcomes(acting_player, state)
-        }
+        CardId::B2a086ElectricGenerator => electric_generator_outcomes(acting_player, state),
         _ => panic!(
             "Unsupported Trainer Card: {} ({})",
             trainer_card.name, trainer_card.id
@@ -204,7 +148,7 @@ fn stadium_effect(_: &mut StdRng, state: &mut State, action: &Action) {
 
         // Remove HP bonus from old Stadium if it was Starting Plains
         if let Some(old_stadium) = state.get_stadium() {
-            if let Some(old_stadium_id) = CardId::from_card_id(&old_stadium.get_id()) {
+            if let Some(old_stadium_id) = trainer_card_id(&old_stadium.get_id()) {
                 if old_stadium_id == CardId::B2154StartingPlains {
                     // Remove +20 HP from all Basic Pokemon
                     for player in 0..2 {
@@ -222,8 +166,8 @@ fn stadium_effect(_: &mut StdRng, state: &mut State, action: &Action) {
             }
         }
 
-        let card = Card::Trainer(trainer_card.clone());
-        let card_id = CardId::from_card_id(&trainer_card.id);
+        let card = Card::Trainer(trainer_card);
+        let card_id = trainer_card_id(&trainer_card.id);
         state.set_stadium(card, action.actor);
         debug!("Stadium: {} is now in play", trainer_card.name);
 
@@ -743,7 +687,7 @@ fn koga_effect(_: &mut StdRng, state: &mut State, action: &Action) {
         .as_ref()
         .expect("Active Pokemon should be there if Koga is played");
     let mut cards_to_collect = active_pokemon.cards_behind.clone();
-    cards_to_collect.push(active_pokemon.card.clone());
+    cards_to_collect.push(active_pokemon.card);
     let count = cards_to_collect.len();
     state.hands[action.actor].extend(cards_to_collect);
     state.hands_visibility[action.actor].extend(std::iter::repeat_n(true, count));
@@ -795,7 +739,7 @@ fn mythical_slab_effect(_: &mut StdRng, state: &mut State, action: &Action) {
 
             // Re-implementing original logic (which checked is_basic()) but using draw result.
             if card.is_basic() {
-                state.hands[action.actor].push(card.clone());
+                state.hands[action.actor].push(card);
                 state.hands_visibility[action.actor].push(true); // Reveal? Assuming yes.
             } else {
                 state.decks[action.actor].cards.push(card);
@@ -826,12 +770,12 @@ fn red_card_effect(rng: &mut StdRng, state: &mut State, action: &Action) {
 // Give the choice to the player to attach a tool to one of their pokemon.
 fn attach_tool(_: &mut StdRng, state: &mut State, action: &Action) {
     if let SimpleAction::Play { trainer_card } = &action.action {
-        let tool_card = Card::Trainer(trainer_card.clone());
+        let tool_card = Card::Trainer(trainer_card);
         let choices = enumerate_tool_choices(trainer_card, state, action.actor)
             .into_iter()
             .map(|(in_play_idx, _)| SimpleAction::AttachTool {
                 in_play_idx,
-                tool_card: tool_card.clone(),
+                tool_card,
             })
             .collect::<Vec<_>>();
         state.move_generation_stack.push((action.actor, choices));
@@ -852,7 +796,7 @@ fn rare_candy_effect(_: &mut StdRng, state: &mut State, action: &Action) {
             hand.iter()
                 .filter(|card| can_rare_candy_evolve(card, in_play))
                 .map(move |card| SimpleAction::Evolve {
-                    evolution: card.clone(),
+                    evolution: *card,
                     in_play_idx,
                     from_deck: false, // Rare Candy uses evolution from hand
                 })
@@ -873,7 +817,7 @@ fn pokemon_communication_effect(_: &mut StdRng, state: &mut State, action: &Acti
         .iter()
         .filter(|card| matches!(card, Card::Pokemon(_)))
         .map(|card| SimpleAction::CommunicatePokemon {
-            hand_pokemon: card.clone(),
+            hand_pokemon: *card,
         })
         .collect();
 
@@ -921,7 +865,7 @@ fn silver_effect(_: &mut StdRng, state: &mut State, action: &Action) {
         .iter()
         .filter(|card| card.is_support())
         .map(|card| SimpleAction::ShuffleOpponentSupporter {
-            supporter_card: card.clone(),
+            supporter_card: *card,
         })
         .collect();
 
@@ -1177,7 +1121,7 @@ fn celestic_town_elder_effect(acting_player: usize, state: &State) -> (Probabili
                 .position(|card| card == &pokemon)
             {
                 state.discard_piles[action.actor].remove(idx);
-                state.hands[action.actor].push(pokemon.clone());
+                state.hands[action.actor].push(pokemon);
                 state.hands_visibility[action.actor].push(true);
             }
         }));
@@ -1306,7 +1250,7 @@ fn mesagoza_outcomes(acting_player: usize, state: &State) -> (Probabilities, Mut
     let (search_probs, search_mutations) =
         pokemon_search_outcomes(acting_player, state, false, "Mesagoza");
 
-    for (p, m) in search_probs.into_iter().zip(search_mutations.into_iter()) {
+    for (p, m) in search_probs.into_iter().zip(search_mutations) {
         probabilities.push(0.5 * p);
         outcomes.push(Box::new(move |rng, state, action| {
             debug!("Mesagoza: Heads! Finding a Pokémon.");
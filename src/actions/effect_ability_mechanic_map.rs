@@ -5,6 +5,7 @@ use std::collections::HashMap;
 use std::sync::LazyLock;
 
 use crate::actions::abilities::{AbilityMechanic, TargetScope};
+use crate::custom_cards::custom_ability_mechanic;
 use crate::models::{Card, EnergyType};
 
 /// Map from ability effect text to its AbilityMechanic.
@@ -184,8 +185,12 @@ pub static EFFECT_ABILITY_MECHANIC_MAP: LazyLock<HashMap<&'static str, AbilityMe
         map
     });
 
+/// The mechanic of an ability effect text, including the ones of custom cards
+/// (see `load_custom_cards`)
 pub fn ability_mechanic_from_effect(effect: &str) -> Option<&'static AbilityMechanic> {
-    EFFECT_ABILITY_MECHANIC_MAP.get(effect)
+    EFFECT_ABILITY_MECHANIC_MAP
+        .get(effect)
+        .or_else(|| custom_ability_mechanic(effect))
 }
 
 pub fn get_simulator_ability_mechanic(card: &Card) -> Option<&'static AbilityMechanic> {
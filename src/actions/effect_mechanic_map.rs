@@ -6,6 +6,7 @@ use std::sync::LazyLock;
 
 use crate::{
     actions::attacks::{BenchSide, Mechanic},
+    custom_cards::custom_attack_mechanic,
     effects::{CardEffect, TurnEffect},
     models::{EnergyType, StatusCondition},
 };
@@ -1534,3 +1535,11 @@ pub static EFFECT_MECHANIC_MAP: LazyLock<HashMap<&'static str, Mechanic>> = Lazy
 
     map
 });
+
+/// The mechanic of an attack effect text, including the ones of custom cards
+/// (see `load_custom_cards`)
+pub fn attack_mechanic_from_effect(effect: &str) -> Option<&'static Mechanic> {
+    EFFECT_MECHANIC_MAP
+        .get(effect)
+        .or_else(|| custom_attack_mechanic(effect))
+}
@@ -27,19 +27,19 @@ fn test_guzma_discards_multiple_tools() {
 
     // Player 0 has Guzma in hand
     let guzma_card_enum = get_card_by_enum(CardId::A3151Guzma);
-    let guzma_card = if let crate::models::Card::Trainer(tc) = &guzma_card_enum {
-        tc.clone()
+    let guzma_card = if let crate::models::Card::Trainer(tc) = guzma_card_enum {
+        tc
     } else {
         panic!("Guzma should be a trainer card")
     };
-    state.hands[0].push(guzma_card_enum.clone());
+    state.hands[0].push(guzma_card_enum);
     state.hands_visibility[0].push(true);
 
     // Act: Play Guzma
     let action = Action {
         actor: 0,
         action: SimpleAction::Play {
-            trainer_card: guzma_card.clone(),
+            trainer_card: guzma_card,
         },
         is_stack: false,
     };
@@ -72,14 +72,14 @@ fn test_guzma_cannot_be_played_without_tools() {
 
     // Player 0 has Guzma in hand
     let guzma_card_enum = get_card_by_enum(CardId::A3151Guzma);
-    let guzma_card = if let crate::models::Card::Trainer(tc) = &guzma_card_enum {
-        tc.clone()
+    let guzma_card = if let crate::models::Card::Trainer(tc) = guzma_card_enum {
+        tc
     } else {
         panic!("Guzma should be a trainer card")
     };
 
     // Check move generation
-    let actions = generate_possible_trainer_actions(&state, &guzma_card).unwrap();
+    let actions = generate_possible_trainer_actions(&state, guzma_card).unwrap();
 
     // Should not be able to play Guzma
     assert!(
@@ -1,4 +1,4 @@
-pub(crate) mod abilities;
+pub mod abilities;
 mod apply_abilities_action;
 mod apply_action;
 mod apply_action_helpers;
@@ -27,6 +27,6 @@ pub use effect_ability_mechanic_map::EFFECT_ABILITY_MECHANIC_MAP;
 pub use effect_ability_mechanic_map::{
     ability_mechanic_from_effect, get_simulator_ability_mechanic,
 };
-pub use effect_mechanic_map::EFFECT_MECHANIC_MAP;
+pub use effect_mechanic_map::{attack_mechanic_from_effect, EFFECT_MECHANIC_MAP};
 pub use types::Action;
 pub use types::SimpleAction;
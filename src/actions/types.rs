@@ -17,7 +17,8 @@ pub enum SimpleAction {
         amount: u8,
     },
     Play {
-        trainer_card: TrainerCard,
+        #[serde(deserialize_with = "TrainerCard::deserialize_static")]
+        trainer_card: &'static TrainerCard,
     },
 
     // Card because of the fossil Trainer Cards...
@@ -58,7 +58,7 @@ fn main() {
             enum_name = enum_name[..enum_name.len() - 2].to_string();
             enum_name.push_str("Ex");
         }
-        card_map.insert(enum_name.clone(), card.clone());
+        card_map.insert(enum_name.clone(), card);
         id_to_enum.insert(card.get_id().clone(), enum_name.clone());
     }
 
@@ -113,6 +113,10 @@ fn print_enums(card_map: &IndexMap<String, Card>, id_to_enum: &IndexMap<String,
 fn print_database(card_map: &IndexMap<String, Card>) {
     println!("// This is code generated from the database.json by card_enum_generator.rs. Do not edit manually.");
     println!();
+    println!("use std::sync::LazyLock;");
+    println!();
+    println!("use strum::IntoEnumIterator;");
+    println!();
     println!("use crate::{{");
     println!("    card_ids::CardId,");
     println!(
@@ -120,7 +124,20 @@ fn print_database(card_map: &IndexMap<String, Card>) {
     );
     println!("}};");
     println!();
+    println!("/// Every card, indexed by `CardId` discriminant, built on first use");
+    println!("static CARDS: LazyLock<Vec<Card>> = LazyLock::new(|| CardId::iter().map(build_card).collect());");
+    println!();
+    println!("/// The card's definition, without copying it");
+    println!("pub fn get_card_ref(id: CardId) -> &'static Card {{");
+    println!("    &CARDS[id as usize]");
+    println!("}}");
+    println!();
+    println!("/// The card's handle, see `get_card_ref`");
     println!("pub fn get_card_by_enum(id: CardId) -> Card {{");
+    println!("    *get_card_ref(id)");
+    println!("}}");
+    println!();
+    println!("fn build_card(id: CardId) -> Card {{");
     println!("    match id {{");
     for (enum_name, card) in card_map.iter() {
         print_card(enum_name, card);
@@ -132,7 +149,7 @@ fn print_database(card_map: &IndexMap<String, Card>) {
 fn print_card(enum_name: &str, card: &Card) {
     match card {
         Card::Pokemon(pokemon_card) => {
-            println!("        CardId::{enum_name} => Card::Pokemon(PokemonCard {{");
+            println!("        CardId::{enum_name} => Card::from(PokemonCard {{");
             println!("            id: \"{}\".to_string(),", pokemon_card.id);
             println!("            name: \"{}\".to_string(),", pokemon_card.name);
             println!("            stage: {},", pokemon_card.stage);
@@ -169,7 +186,7 @@ fn print_card(enum_name: &str, card: &Card) {
             println!("        }}),");
         }
         Card::Trainer(trainer_card) => {
-            println!("        CardId::{enum_name} => Card::Trainer(TrainerCard {{");
+            println!("        CardId::{enum_name} => Card::from(TrainerCard {{");
             println!("            id: \"{}\".to_string(),", trainer_card.id);
             println!("            name: \"{}\".to_string(),", trainer_card.name);
             println!(
@@ -1,11 +1,12 @@
 use clap::Parser;
 use colored::Colorize;
 use deckgym::database::get_card_by_enum;
+use deckgym::gauntlet::{cli_gauntlet, Meta};
+use deckgym::optimize::{ParallelConfig, SimulationConfig};
 use deckgym::simulate::initialize_logger;
 use deckgym::temp_deck::{find_card_id, generate_temp_deck};
-use deckgym::{simulate, Deck};
+use deckgym::Deck;
 use log::warn;
-use num_format::{Locale, ToFormattedString};
 use std::fs;
 use std::path::Path;
 
@@ -38,10 +39,8 @@ fn main() {
     // Validate the generated deck
     match Deck::from_string(&deck_string) {
         Ok(deck) => {
-            if !deck.is_valid() {
-                eprintln!("Error: Generated deck is invalid");
-                eprintln!("Deck must have exactly 20 cards, at least 1 basic Pokemon,");
-                eprintln!("and no card name can appear more than twice.");
+            if let Err(err) = deck.check_valid() {
+                eprintln!("Error: Generated deck is invalid: {err}");
                 eprintln!("\nGenerated deck:");
                 eprintln!("{deck_string}");
                 std::process::exit(1);
@@ -76,99 +75,27 @@ fn main() {
         std::process::exit(1);
     }
 
-    simulate_against_folder(
-        deck_path.to_str().expect("Temp deck path should be valid"),
+    let meta = match Meta::from_folder(
         example_decks
             .to_str()
             .expect("Example decks path should be valid"),
-        10_000,
-    );
-}
-
-/// Simulate games between one deck and multiple decks in a folder.
-fn simulate_against_folder(deck_a_path: &str, decks_folder: &str, total_num_simulations: u32) {
-    // Read all deck files from the folder
-    let deck_paths: Vec<String> = fs::read_dir(decks_folder)
-        .expect("Failed to read decks folder")
-        .filter_map(|entry| {
-            let entry = entry.ok()?;
-            if entry.path().is_file() {
-                Some(entry.path().to_str()?.to_string())
-            } else {
-                None
-            }
-        })
-        .collect();
-
-    // Load and validate decks
-    let valid_decks: Vec<(String, Deck)> = deck_paths
-        .iter()
-        .filter_map(|path| {
-            let deck = Deck::from_file(path).ok()?;
-            if deck.cards.len() == 20 {
-                Some((path.clone(), deck))
-            } else {
-                warn!("Skipping deck {} (invalid)", path);
-                None
-            }
-        })
-        .collect();
-
-    if valid_decks.is_empty() {
-        warn!("No valid decks found in folder: {}", decks_folder);
-        return;
-    }
-
-    warn!(
-        "Found {} valid deck files in folder",
-        valid_decks.len().to_formatted_string(&Locale::en)
-    );
-
-    // Calculate games per deck (distribute evenly)
-    let num_decks = valid_decks.len() as u32;
-    let games_per_deck = total_num_simulations / num_decks;
-    let remainder = total_num_simulations % num_decks;
-
-    warn!(
-        "Running {} total games ({} per deck)",
-        total_num_simulations.to_formatted_string(&Locale::en),
-        games_per_deck.to_formatted_string(&Locale::en)
-    );
-
-    // Run simulations against each deck
-    for (i, (deck_path, _)) in valid_decks.iter().enumerate() {
-        let deck_name = deck_path.split('/').next_back().unwrap_or(deck_path);
-        let games_for_this_deck = if i < remainder as usize {
-            games_per_deck + 1
-        } else {
-            games_per_deck
-        };
-
-        if games_for_this_deck == 0 {
-            continue;
+    ) {
+        Ok(meta) => meta,
+        Err(e) => {
+            eprintln!("Error: {e}");
+            std::process::exit(1);
         }
-
-        warn!("\n{}", "=".repeat(60));
-        warn!(
-            "Simulating against deck {}/{}: {}",
-            i + 1,
-            num_decks,
-            deck_name
-        );
-        warn!("{}", "=".repeat(60));
-
-        simulate(
-            deck_a_path,
-            deck_path,
-            None,
-            games_for_this_deck,
-            None,
-            false,
-            None,
-        );
-    }
-
-    warn!("\n{}", "=".repeat(60));
-    warn!("All simulations complete!");
-    warn!("{}", "=".repeat(60));
+    };
+    cli_gauntlet(
+        deck_path.to_str().expect("Temp deck path should be valid"),
+        &meta,
+        SimulationConfig {
+            num_games: 10_000,
+            players: None,
+            seed: None,
+        },
+        ParallelConfig::default(),
+        None,
+        None,
+    );
 }
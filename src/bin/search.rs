@@ -1,100 +1,121 @@
 use clap::Parser;
-use serde_json::Value;
-use std::fs;
+use deckgym::card_database::load_card_database;
+use deckgym::card_search::{search_cards, CardQuery};
+use deckgym::deck_builder::parse_energy_type;
+use deckgym::models::{Card, EnergyType};
 
 #[derive(Parser)]
 #[command(name = "search")]
-#[command(about = "Search for Pokémon cards by name and optionally by attack")]
+#[command(about = "Search for Pokémon and trainer cards")]
 struct Args {
-    /// The search query to match against card names (case-insensitive)
-    query: String,
+    /// Text to match against card names (case-insensitive)
+    query: Option<String>,
 
-    /// Optional attack name to filter Pokemon cards (case-insensitive)
+    /// Text to match against attack names (case-insensitive)
     #[arg(short, long)]
     attack: Option<String>,
 
+    /// Pokémon stage (0 for Basic)
+    #[arg(long)]
+    stage: Option<u8>,
+
+    /// Pokémon types, any of them (e.g. "Grass,Fire")
+    #[arg(long, value_delimiter = ',', value_parser = parse_energy_type)]
+    energy: Vec<EnergyType>,
+
+    #[arg(long)]
+    min_hp: Option<u32>,
+
+    #[arg(long)]
+    max_hp: Option<u32>,
+
+    /// Weaknesses, any of them (e.g. "Fire")
+    #[arg(long, value_delimiter = ',', value_parser = parse_energy_type)]
+    weakness: Vec<EnergyType>,
+
+    /// Maximum number of energies to retreat
+    #[arg(long)]
+    max_retreat_cost: Option<usize>,
+
+    /// Only Pokémon with (true) or without (false) an ability
+    #[arg(long)]
+    has_ability: Option<bool>,
+
+    /// Maximum number of energies of the matching attack
+    #[arg(long)]
+    max_attack_cost: Option<usize>,
+
+    /// Minimum base damage of the matching attack
+    #[arg(long)]
+    min_damage: Option<u32>,
+
+    /// Text to match against attack, ability and trainer effects (case-insensitive)
+    #[arg(short, long)]
+    effect: Option<String>,
+
+    /// Text to match against the booster pack (e.g. "Genetic Apex")
+    #[arg(long)]
+    booster_pack: Option<String>,
+
+    /// Exact rarity (e.g. "◊◊◊◊")
+    #[arg(long)]
+    rarity: Option<String>,
+
+    /// Only cards the engine fully implements (true) or doesn't (false)
+    #[arg(long)]
+    implemented: Option<bool>,
+
     /// Show only name and id instead of full card details
     #[arg(short, long)]
     simple: bool,
 
-    /// Path to the database file
-    #[arg(short, long, default_value = "database.json")]
-    database: String,
+    /// Also search the cards of a database file that this build doesn't have yet
+    #[arg(short, long)]
+    database: Option<String>,
 }
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
     let args = Args::parse();
+    if let Some(path) = &args.database {
+        load_card_database(path)?;
+    }
 
-    // Read the database file
-    let database_content = fs::read_to_string(&args.database)?;
-    let database: Vec<Value> = serde_json::from_str(&database_content)?;
-
-    // Search for matching cards
-    let mut matches = Vec::new();
-    let query_lower = args.query.to_lowercase();
-    let attack_query_lower = args.attack.as_ref().map(|a| a.to_lowercase());
-
-    for card in database {
-        // Check if it's a Pokemon card
-        if let Some(pokemon) = card.get("Pokemon") {
-            if let Some(name) = pokemon.get("name").and_then(|n| n.as_str()) {
-                if name.to_lowercase().contains(&query_lower) {
-                    // If attack filter is specified, check if Pokemon has matching attack
-                    let mut attack_match = true;
-                    if let Some(ref attack_query) = attack_query_lower {
-                        attack_match = false;
-                        if let Some(attacks) = pokemon.get("attacks").and_then(|a| a.as_array()) {
-                            for attack in attacks {
-                                if let Some(title) = attack.get("title").and_then(|t| t.as_str()) {
-                                    if title.to_lowercase().contains(attack_query) {
-                                        attack_match = true;
-                                        break;
-                                    }
-                                }
-                            }
-                        }
-                    }
-
-                    if attack_match {
-                        if args.simple {
-                            // Create a simplified version with just id and name
-                            let simplified = serde_json::json!({
-                                "Pokemon": {
-                                    "id": pokemon.get("id"),
-                                    "name": name
-                                }
-                            });
-                            matches.push(simplified);
-                        } else {
-                            matches.push(card);
-                        }
-                    }
-                }
+    let query = CardQuery {
+        name: args.query,
+        stage: args.stage,
+        energy_types: args.energy,
+        min_hp: args.min_hp,
+        max_hp: args.max_hp,
+        weaknesses: args.weakness,
+        max_retreat_cost: args.max_retreat_cost,
+        has_ability: args.has_ability,
+        attack: args.attack,
+        max_attack_cost: args.max_attack_cost,
+        min_attack_damage: args.min_damage,
+        effect: args.effect,
+        booster_pack: args.booster_pack,
+        rarity: args.rarity,
+        implemented: args.implemented,
+    };
+    let matches: Vec<serde_json::Value> = search_cards(&query)
+        .into_iter()
+        .map(|card| {
+            if !args.simple {
+                return serde_json::to_value(card);
             }
-        }
-        // Check if it's a Trainer card (only if no attack filter is specified)
-        else if let Some(trainer) = card.get("Trainer") {
-            // Only include Trainer cards if no attack filter is specified
-            if attack_query_lower.is_none() {
-                if let Some(name) = trainer.get("name").and_then(|n| n.as_str()) {
-                    if name.to_lowercase().contains(&query_lower) {
-                        if args.simple {
-                            // Create a simplified version with just id and name
-                            let simplified = serde_json::json!({
-                                "Trainer": {
-                                    "id": trainer.get("id"),
-                                    "name": name
-                                }
-                            });
-                            matches.push(simplified);
-                        } else {
-                            matches.push(card);
-                        }
-                    }
+            // Create a simplified version with just id and name
+            let kind = match card {
+                Card::Pokemon(_) => "Pokemon",
+                Card::Trainer(_) => "Trainer",
+            };
+            Ok(serde_json::json!({
+                kind: {
+                    "id": card.get_id(),
+                    "name": card.get_name()
                 }
-            }
-        }
-    }
+            }))
+        })
+        .collect::<Result<_, _>>()?;
 
     // Output the results as JSON
     let output = serde_json::to_string_pretty(&matches)?;
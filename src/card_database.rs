@@ -0,0 +1,255 @@
+use std::collections::HashMap;
+use std::fs;
+use std::sync::{LazyLock, RwLock};
+
+use strum::IntoEnumIterator;
+
+use crate::{
+    card_equivalence::plays_the_same,
+    card_ids::CardId,
+    card_validation::get_implementation_status,
+    custom_cards::play_trainer_as,
+    database::{get_card_by_enum, get_card_ref},
+    models::{Card, TrainerType},
+};
+
+/// Cards added with `load_card_database` or `load_custom_cards`, by id (e.g. "B3 001")
+static RUNTIME_CARDS: LazyLock<RwLock<HashMap<String, Card>>> = LazyLock::new(Default::default);
+
+/// Loads the cards of a file in the `database.json` format that this build doesn't have yet,
+/// so that decks can use a new set without regenerating `card_ids.rs` and `database.rs`.
+/// Returns how many cards were added.
+///
+/// Cards the build already has keep their compiled definition. Loaded cards have no `CardId`,
+/// so they play through the mechanics their effect texts map to (see
+/// `get_card_implementation_status`): vanilla Pokémon, Pokémon whose attacks and abilities reuse
+/// implemented texts, and tools. Other trainers are implemented per card, so they play as the
+/// implemented trainer they only differ from in their print (e.g. a reprint of Professor's
+/// Research); `Deck::check_valid` rejects those without one. Tools working on `CardId`s (like
+/// the optimizer) don't take loaded cards.
+pub fn load_card_database(path: &str) -> Result<usize, String> {
+    let contents = fs::read_to_string(path)
+        .map_err(|err| format!("Failed to read card database {path}: {err}"))?;
+    load_card_database_from_string(&contents)
+        .map_err(|err| format!("Invalid card database {path}: {err}"))
+}
+
+/// See `load_card_database`
+pub fn load_card_database_from_string(json: &str) -> Result<usize, String> {
+    let cards: Vec<Card> = serde_json::from_str(json).map_err(|err| err.to_string())?;
+    let mut runtime_cards = RUNTIME_CARDS.write().expect("Card database lock poisoned");
+    let mut added = 0;
+    for card in cards {
+        let id = card.get_id();
+        if CardId::from_card_id(&id).is_some() {
+            continue;
+        }
+        if let Some(card_id) = implemented_trainer_print(&card) {
+            play_trainer_as(id.clone(), card_id);
+        }
+        if runtime_cards.insert(id, card).is_none() {
+            added += 1;
+        }
+    }
+    Ok(added)
+}
+
+/// The implemented compiled trainer a loaded (non-tool) trainer plays the same as
+fn implemented_trainer_print(card: &Card) -> Option<CardId> {
+    let Card::Trainer(trainer_card) = card else {
+        return None;
+    };
+    if matches!(
+        trainer_card.trainer_card_type,
+        TrainerType::Tool | TrainerType::Fossil
+    ) {
+        return None;
+    }
+    CardId::iter().find(|&card_id| {
+        plays_the_same(get_card_ref(card_id), card)
+            && get_implementation_status(card_id).is_complete()
+    })
+}
+
+/// The card with the given id (e.g. "A1 001"), compiled in or loaded at runtime
+pub fn get_card_by_id(id: &str) -> Option<Card> {
+    if let Some(card_id) = CardId::from_card_id(id) {
+        return Some(get_card_by_enum(card_id));
+    }
+    RUNTIME_CARDS
+        .read()
+        .expect("Card database lock poisoned")
+        .get(id)
+        .cloned()
+}
+
+/// Adds a card under its id, e.g. a custom card (see `load_custom_cards`)
+pub(crate) fn add_runtime_card(card: Card) {
+    RUNTIME_CARDS
+        .write()
+        .expect("Card database lock poisoned")
+        .insert(card.get_id(), card);
+}
+
+/// Every card loaded with `load_card_database` or `load_custom_cards`, sorted by id
+pub fn runtime_cards() -> Vec<Card> {
+    let mut cards: Vec<Card> = RUNTIME_CARDS
+        .read()
+        .expect("Card database lock poisoned")
+        .values()
+        .cloned()
+        .collect();
+    cards.sort_by_key(|card| card.get_id());
+    cards
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        actions::SimpleAction,
+        card_validation::get_card_implementation_status,
+        deck::DeckIssue,
+        models::{PokemonCard, TrainerCard},
+        players::{Player, RandomPlayer},
+        Deck, Game,
+    };
+
+    /// A copy of an existing card under a new id, as a new set would reprint it
+    fn new_print(card_id: CardId, id: &str) -> Card {
+        let id = id.to_string();
+        match get_card_by_enum(card_id) {
+            Card::Pokemon(pokemon) => PokemonCard {
+                id,
+                ..pokemon.clone()
+            }
+            .into(),
+            Card::Trainer(trainer) => TrainerCard {
+                id,
+                ..trainer.clone()
+            }
+            .into(),
+        }
+    }
+
+    #[test]
+    fn test_loaded_cards_are_playable() {
+        let cards = vec![
+            new_print(CardId::A1001Bulbasaur, "Z9 001"),
+            new_print(CardId::A1002Ivysaur, "Z9 002"),
+            new_print(CardId::A1219Erika, "Z9 003"),
+            // Already compiled in, so ignored
+            get_card_by_enum(CardId::A1003Venusaur),
+        ];
+        let json = serde_json::to_string(&cards).unwrap();
+        assert_eq!(load_card_database_from_string(&json), Ok(3));
+        assert!(get_card_by_id("Z9 001").is_some());
+        assert!(get_card_by_id("Z9 999").is_none());
+        assert!(runtime_cards().iter().any(|card| card.get_id() == "Z9 002"));
+
+        // Reprinted trainers play as the compiled print
+        let erika = get_card_by_id("Z9 003").unwrap();
+        assert!(get_card_implementation_status(&erika).is_complete());
+        let ivysaur = get_card_by_id("Z9 002").unwrap();
+        assert!(get_card_implementation_status(&ivysaur).is_complete());
+
+        let deck = Deck::from_string(
+            "Energy: Grass\n2 Bulbasaur Z9 001\n2 Ivysaur Z9 002\n2 Exeggcute A1 021\n",
+        )
+        .unwrap();
+        assert_eq!(deck.cards[0].get_id(), "Z9 001");
+        assert!(!deck
+            .validate()
+            .iter()
+            .any(|issue| matches!(issue, DeckIssue::UnimplementedCard { .. })));
+        let players: Vec<Box<dyn Player + Send>> = vec![
+            Box::new(RandomPlayer { deck: deck.clone() }),
+            Box::new(RandomPlayer { deck }),
+        ];
+        let mut game = Game::new(players, 7);
+        game.play();
+    }
+
+    #[test]
+    fn test_deserializing_reuses_card_handles() {
+        let handle = |card: Card| match card {
+            Card::Pokemon(pokemon) => pokemon as *const PokemonCard as usize,
+            Card::Trainer(trainer) => trainer as *const TrainerCard as usize,
+        };
+        let bulbasaur = get_card_by_enum(CardId::A1001Bulbasaur);
+        let json = serde_json::to_string(&bulbasaur).unwrap();
+        let deserialized: Card = serde_json::from_str(&json).unwrap();
+        assert_eq!(handle(deserialized), handle(bulbasaur));
+
+        // A changed definition under a compiled id is its own card, built only once
+        let tougher = match bulbasaur {
+            Card::Pokemon(pokemon) => Card::from(PokemonCard {
+                hp: 990,
+                ..pokemon.clone()
+            }),
+            Card::Trainer(_) => unreachable!(),
+        };
+        let json = serde_json::to_string(&tougher).unwrap();
+        let first: Card = serde_json::from_str(&json).unwrap();
+        let second: Card = serde_json::from_str(&json).unwrap();
+        assert_ne!(handle(first), handle(bulbasaur));
+        assert_eq!(handle(first), handle(second));
+        assert!(matches!(first, Card::Pokemon(pokemon) if pokemon.hp == 990));
+
+        // Trainers played in (e.g. replayed) actions share the handles too
+        let potion = get_card_by_enum(CardId::PA001Potion);
+        let Card::Trainer(trainer_card) = potion else {
+            unreachable!()
+        };
+        let json = serde_json::to_string(&SimpleAction::Play { trainer_card }).unwrap();
+        let SimpleAction::Play { trainer_card } = serde_json::from_str(&json).unwrap() else {
+            unreachable!()
+        };
+        assert_eq!(handle(Card::Trainer(trainer_card)), handle(potion));
+    }
+
+    #[test]
+    fn test_loaded_trainers_play_as_their_compiled_print() {
+        let mystery = match get_card_by_enum(CardId::A4b373ProfessorsResearch) {
+            Card::Trainer(trainer) => TrainerCard {
+                id: "Z8 002".to_string(),
+                name: "Mystery Research".to_string(),
+                effect: "Does something no trainer does.".to_string(),
+                ..trainer.clone()
+            },
+            Card::Pokemon(_) => unreachable!(),
+        };
+        let cards = vec![
+            new_print(CardId::A4b373ProfessorsResearch, "Z8 001"),
+            Card::from(mystery),
+        ];
+        let json = serde_json::to_string(&cards).unwrap();
+        assert_eq!(load_card_database_from_string(&json), Ok(2));
+
+        let deck_text = fs::read_to_string("example_decks/venusaur-exeggutor.txt").unwrap();
+        // The deck with its two Professor's Research swapped for a loaded trainer
+        let with = |line: &str| {
+            Deck::from_string(&deck_text.replace("2 Professor's Research P-A 007", line)).unwrap()
+        };
+
+        let unplayable = with("2 Mystery Research Z8 002");
+        assert!(unplayable.check_valid().is_err());
+        assert!(unplayable
+            .validate()
+            .contains(&DeckIssue::UnplayableTrainer {
+                id: "Z8 002".to_string(),
+                name: "Mystery Research".to_string(),
+            }));
+
+        let deck = with("2 Professor's Research Z8 001");
+        assert_eq!(deck.check_valid(), Ok(()));
+        for seed in 0..10 {
+            let players: Vec<Box<dyn Player + Send>> = vec![
+                Box::new(RandomPlayer { deck: deck.clone() }),
+                Box::new(RandomPlayer { deck: deck.clone() }),
+            ];
+            let mut game = Game::new(players, seed);
+            game.play();
+        }
+    }
+}
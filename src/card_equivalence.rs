@@ -0,0 +1,110 @@
+use std::collections::HashMap;
+use std::sync::LazyLock;
+
+use strum::IntoEnumIterator;
+
+use crate::{card_ids::CardId, database::get_card_ref, models::Card};
+
+/// Canonical print of every card, indexed by `CardId` discriminant
+static CANONICAL_IDS: LazyLock<Vec<CardId>> = LazyLock::new(|| {
+    let mut canonical_ids = Vec::new();
+    let mut groups_by_name: HashMap<String, Vec<&Card>> = HashMap::new();
+    for id in CardId::iter() {
+        let card = get_card_ref(id);
+        let groups = groups_by_name.entry(card.get_name()).or_default();
+        let canonical = match groups.iter().find(|other| plays_the_same(other, card)) {
+            Some(other) => other.get_card_id(),
+            None => {
+                groups.push(card);
+                id
+            }
+        };
+        canonical_ids.push(canonical);
+    }
+    canonical_ids
+});
+
+/// The canonical print among the functional reprints of `id` (the first one in `CardId` order).
+///
+/// Code that gives cards behavior should match on this instead of listing every print, so
+/// that a new reprint works without changes.
+pub fn canonical_card_id(id: CardId) -> CardId {
+    CANONICAL_IDS[id as usize]
+}
+
+/// Whether two cards are prints of the same card, see `plays_the_same`
+pub fn are_reprints(a: CardId, b: CardId) -> bool {
+    canonical_card_id(a) == canonical_card_id(b)
+}
+
+/// Every print that plays the same as `id` (including itself), in `CardId` order
+pub fn equivalent_prints(id: CardId) -> Vec<CardId> {
+    let canonical = canonical_card_id(id);
+    CardId::iter()
+        .filter(|&other| canonical_card_id(other) == canonical)
+        .collect()
+}
+
+/// Whether two cards only differ in their print (id, rarity, booster pack)
+pub fn plays_the_same(a: &Card, b: &Card) -> bool {
+    match (a, b) {
+        (Card::Pokemon(a), Card::Pokemon(b)) => {
+            a.name == b.name
+                && a.stage == b.stage
+                && a.evolves_from == b.evolves_from
+                && a.hp == b.hp
+                && a.energy_type == b.energy_type
+                && a.ability == b.ability
+                && a.attacks == b.attacks
+                && a.weakness == b.weakness
+                && a.retreat_cost == b.retreat_cost
+        }
+        (Card::Trainer(a), Card::Trainer(b)) => {
+            a.name == b.name && a.trainer_card_type == b.trainer_card_type && a.effect == b.effect
+        }
+        _ => false,
+    }
+}
+
+impl Card {
+    /// See `canonical_card_id`. `None` for cards loaded at runtime, which have no `CardId`.
+    pub fn get_canonical_card_id(&self) -> Option<CardId> {
+        CardId::from_card_id(&self.get_id()).map(canonical_card_id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reprints_share_a_canonical_print() {
+        assert_eq!(
+            canonical_card_id(CardId::B1297Poliwrath),
+            CardId::A1061Poliwrath
+        );
+        assert_eq!(canonical_card_id(CardId::PA054Pawmot), CardId::A2b028Pawmot);
+        assert_eq!(
+            canonical_card_id(CardId::A1061Poliwrath),
+            CardId::A1061Poliwrath
+        );
+        assert!(are_reprints(CardId::PA005PokeBall, CardId::A2b111PokeBall));
+        assert_eq!(
+            equivalent_prints(CardId::PA007ProfessorsResearch),
+            vec![
+                CardId::A4b373ProfessorsResearch,
+                CardId::PA007ProfessorsResearch
+            ]
+        );
+    }
+
+    #[test]
+    fn test_cards_that_play_differently_are_not_reprints() {
+        // Both named "Pikachu ex", but with different attacks
+        assert!(!are_reprints(
+            CardId::A1096PikachuEx,
+            CardId::A2b022PikachuEx
+        ));
+        assert!(!are_reprints(CardId::A1001Bulbasaur, CardId::A1002Ivysaur));
+    }
+}
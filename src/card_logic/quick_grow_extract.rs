@@ -37,7 +37,7 @@ pub fn quick_grow_extract_candidates(state: &State, player: usize) -> Vec<(usize
                 if deck_pokemon.energy_type == EnergyType::Grass
                     && pokemon.card.can_evolve_into(deck_card)
                 {
-                    evolution_choices.push((in_play_idx, deck_card.clone()));
+                    evolution_choices.push((in_play_idx, *deck_card));
                 }
             }
         }
@@ -5,7 +5,7 @@ use strum::IntoEnumIterator;
 
 use crate::{
     card_ids::CardId,
-    database::get_card_by_enum,
+    database::get_card_ref,
     hooks::get_stage,
     models::{Card, PlayedCard},
 };
@@ -15,8 +15,7 @@ static STAGE1_LOOKUP: LazyLock<HashMap<String, Vec<String>>> = LazyLock::new(||
     let mut lookup: HashMap<String, Vec<String>> = HashMap::new();
 
     for id in CardId::iter() {
-        let card = get_card_by_enum(id);
-        if let Card::Pokemon(pokemon_card) = card {
+        if let Card::Pokemon(pokemon_card) = get_card_ref(id) {
             if pokemon_card.stage == 1 {
                 if let Some(evolves_from) = &pokemon_card.evolves_from {
                     lookup
@@ -36,8 +35,7 @@ static STAGE2_LOOKUP: LazyLock<HashMap<String, Vec<String>>> = LazyLock::new(||
     let mut lookup: HashMap<String, Vec<String>> = HashMap::new();
 
     for id in CardId::iter() {
-        let card = get_card_by_enum(id);
-        if let Card::Pokemon(pokemon_card) = card {
+        if let Card::Pokemon(pokemon_card) = get_card_ref(id) {
             if pokemon_card.stage == 2 {
                 if let Some(evolves_from) = &pokemon_card.evolves_from {
                     lookup
@@ -61,11 +59,10 @@ static RARE_CANDY_LOOKUP: LazyLock<HashMap<(String, String), bool>> = LazyLock::
     let mut stage1_map: HashMap<String, String> = HashMap::new();
 
     for id in CardId::iter() {
-        let card = get_card_by_enum(id);
-        if let Card::Pokemon(pokemon_card) = card {
+        if let Card::Pokemon(pokemon_card) = get_card_ref(id) {
             if pokemon_card.stage == 1 {
-                if let Some(evolves_from) = pokemon_card.evolves_from {
-                    stage1_map.insert(pokemon_card.name.clone(), evolves_from);
+                if let Some(evolves_from) = &pokemon_card.evolves_from {
+                    stage1_map.insert(pokemon_card.name.clone(), evolves_from.clone());
                 }
             }
         }
@@ -73,8 +70,7 @@ static RARE_CANDY_LOOKUP: LazyLock<HashMap<(String, String), bool>> = LazyLock::
 
     // Now iterate through all Stage 2 Pokemon and build the lookup table
     for id in CardId::iter() {
-        let card = get_card_by_enum(id);
-        if let Card::Pokemon(stage2_pokemon) = card {
+        if let Card::Pokemon(stage2_pokemon) = get_card_ref(id) {
             if stage2_pokemon.stage == 2 {
                 if let Some(stage1_name) = &stage2_pokemon.evolves_from {
                     // Check if this Stage 1 exists and what it evolves from
@@ -128,7 +124,7 @@ pub fn get_highest_evolutions(card: &Card, available_cards: &[Card]) -> Vec<Card
 
     // If already Stage 2, return the card itself
     if current_stage == 2 {
-        return vec![card.clone()];
+        return vec![*card];
     }
 
     let mut highest_evolutions = Vec::new();
@@ -143,7 +139,7 @@ pub fn get_highest_evolutions(card: &Card, available_cards: &[Card]) -> Vec<Card
                         for available_card in available_cards {
                             if let Card::Pokemon(p) = available_card {
                                 if p.stage == 2 && stage2_names.contains(&p.name) {
-                                    highest_evolutions.push(available_card.clone());
+                                    highest_evolutions.push(*available_card);
                                 }
                             }
                         }
@@ -155,7 +151,7 @@ pub fn get_highest_evolutions(card: &Card, available_cards: &[Card]) -> Vec<Card
                     for available_card in available_cards {
                         if let Card::Pokemon(p) = available_card {
                             if p.stage == 1 && stage1_names.contains(&p.name) {
-                                highest_evolutions.push(available_card.clone());
+                                highest_evolutions.push(*available_card);
                             }
                         }
                     }
@@ -168,7 +164,7 @@ pub fn get_highest_evolutions(card: &Card, available_cards: &[Card]) -> Vec<Card
                 for available_card in available_cards {
                     if let Card::Pokemon(p) = available_card {
                         if p.stage == 2 && stage2_names.contains(&p.name) {
-                            highest_evolutions.push(available_card.clone());
+                            highest_evolutions.push(*available_card);
                         }
                     }
                 }
@@ -176,7 +172,7 @@ pub fn get_highest_evolutions(card: &Card, available_cards: &[Card]) -> Vec<Card
 
             // If no Stage 2 found, return the card itself
             if highest_evolutions.is_empty() {
-                highest_evolutions.push(card.clone());
+                highest_evolutions.push(*card);
             }
         }
         _ => {}
@@ -188,6 +184,7 @@ pub fn get_highest_evolutions(card: &Card, available_cards: &[Card]) -> Vec<Card
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::database::get_card_by_enum;
     use crate::hooks::to_playable_card;
 
     #[test]
@@ -237,7 +234,7 @@ mod tests {
         let venusaur = get_card_by_enum(CardId::A1003Venusaur);
 
         // When both Stage 1 and Stage 2 are available, should return only Stage 2
-        let available = vec![ivysaur.clone(), venusaur.clone()];
+        let available = vec![ivysaur, venusaur];
         let result = get_highest_evolutions(&bulbasaur, &available);
 
         // Should return at least one card, and all should be Stage 2
@@ -257,7 +254,7 @@ mod tests {
         let ivysaur = get_card_by_enum(CardId::A1002Ivysaur);
 
         // When only Stage 1 is available, should return Stage 1
-        let available = vec![ivysaur.clone()];
+        let available = vec![ivysaur];
         let result = get_highest_evolutions(&bulbasaur, &available);
 
         assert_eq!(result.len(), 1);
@@ -0,0 +1,187 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::LazyLock;
+
+use strum::IntoEnumIterator;
+
+use crate::{card_equivalence::are_reprints, card_ids::CardId, database::get_card_ref};
+
+/// Every print of each card name, keyed by `normalize_name`, in `CardId` order
+static CARDS_BY_NAME: LazyLock<HashMap<String, Vec<CardId>>> = LazyLock::new(|| {
+    let mut map: HashMap<String, Vec<CardId>> = HashMap::new();
+    for id in CardId::iter() {
+        let name = normalize_name(&get_card_ref(id).get_name());
+        map.entry(name).or_default().push(id);
+    }
+    map
+});
+
+/// Why a card name couldn't be resolved
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CardNameError {
+    NotFound {
+        name: String,
+        /// Closest card names, best first
+        suggestions: Vec<String>,
+    },
+    /// Prints of the name that play differently, so the set has to be given
+    Ambiguous { name: String, prints: Vec<String> },
+}
+
+impl fmt::Display for CardNameError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CardNameError::NotFound { name, suggestions } => {
+                write!(f, "no card named '{name}'")?;
+                if !suggestions.is_empty() {
+                    write!(f, " (did you mean {}?)", suggestions.join(", "))?;
+                }
+                Ok(())
+            }
+            CardNameError::Ambiguous { name, prints } => write!(
+                f,
+                "'{name}' matches cards that play differently ({}); add the set, e.g. '{name} ({})'",
+                prints.join(", "),
+                prints[0].split(' ').next().unwrap_or_default()
+            ),
+        }
+    }
+}
+
+/// Resolves a card by name, as written in tournament lists (e.g. "Mewtwo ex" or
+/// "Mewtwo ex (A1)" to only consider prints of set A1). Case, accents and spacing don't matter.
+///
+/// When several prints match, they must be reprints of one card (see `are_reprints`), and
+/// the canonical one is returned.
+pub fn resolve_card_name(name: &str) -> Result<CardId, CardNameError> {
+    let (name, set) = split_set(name);
+    let not_found = || CardNameError::NotFound {
+        name: name.to_string(),
+        suggestions: suggest_names(name),
+    };
+    let prints: Vec<CardId> = CARDS_BY_NAME
+        .get(&normalize_name(name))
+        .ok_or_else(not_found)?
+        .iter()
+        .copied()
+        .filter(|&id| match set {
+            Some(set) => get_card_ref(id)
+                .get_id()
+                .split(' ')
+                .next()
+                .is_some_and(|card_set| card_set.eq_ignore_ascii_case(set)),
+            None => true,
+        })
+        .collect();
+    let Some(&canonical) = prints.first() else {
+        return Err(not_found());
+    };
+
+    if prints.iter().all(|&id| are_reprints(id, canonical)) {
+        return Ok(canonical);
+    }
+    Err(CardNameError::Ambiguous {
+        name: name.to_string(),
+        prints: prints.iter().map(|&id| get_card_ref(id).get_id()).collect(),
+    })
+}
+
+/// Splits a trailing set in parentheses off a name: "Mewtwo ex (A1)" -> ("Mewtwo ex", Some("A1"))
+fn split_set(name: &str) -> (&str, Option<&str>) {
+    let name = name.trim();
+    if let Some(rest) = name.strip_suffix(')') {
+        if let Some((name, set)) = rest.rsplit_once('(') {
+            return (name.trim(), Some(set.trim()));
+        }
+    }
+    (name, None)
+}
+
+/// Lowercase ASCII without accents or repeated spaces, so that "poke  ball" finds "Poké Ball"
+pub(crate) fn normalize_name(name: &str) -> String {
+    let name: String = name
+        .chars()
+        .map(|c| match c {
+            'é' | 'É' => 'e',
+            '’' => '\'',
+            c => c.to_ascii_lowercase(),
+        })
+        .collect();
+    name.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Up to 3 card names within a few typos of `name`
+fn suggest_names(name: &str) -> Vec<String> {
+    let normalized = normalize_name(name);
+    let max_distance = (normalized.chars().count() / 4).max(2);
+    let mut candidates: Vec<(usize, &CardId)> = CARDS_BY_NAME
+        .iter()
+        .map(|(other, prints)| (edit_distance(&normalized, other), &prints[0]))
+        .filter(|(distance, _)| *distance <= max_distance)
+        .collect();
+    candidates.sort_by_key(|&(distance, &id)| (distance, id as usize));
+    candidates
+        .into_iter()
+        .take(3)
+        .map(|(_, &id)| get_card_ref(id).get_name())
+        .collect()
+}
+
+/// Levenshtein distance between two strings, by characters
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut current = vec![i + 1; b.len() + 1];
+        for (j, &cb) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(ca != cb);
+            current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+        }
+        previous = current;
+    }
+    previous[b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolves_reprints_to_first_print() {
+        assert_eq!(
+            resolve_card_name("Professor's Research"),
+            Ok(CardId::A4b373ProfessorsResearch)
+        );
+        assert_eq!(resolve_card_name("poke  ball"), Ok(CardId::A2b111PokeBall));
+        assert_eq!(
+            resolve_card_name("Poké Ball (P-A)"),
+            Ok(CardId::PA005PokeBall)
+        );
+        assert_eq!(resolve_card_name("mewtwo ex"), Ok(CardId::A1129MewtwoEx));
+    }
+
+    #[test]
+    fn test_ambiguous_names_need_a_set() {
+        let err = resolve_card_name("Pikachu ex").unwrap_err();
+        assert!(matches!(err, CardNameError::Ambiguous { .. }), "{err}");
+        assert_eq!(
+            resolve_card_name("Pikachu ex (A1)"),
+            Ok(CardId::A1096PikachuEx)
+        );
+        assert_eq!(
+            resolve_card_name("Pikachu ex (A2b)"),
+            Ok(CardId::A2b022PikachuEx)
+        );
+    }
+
+    #[test]
+    fn test_suggests_names_for_typos() {
+        match resolve_card_name("Bulbsaur") {
+            Err(CardNameError::NotFound { suggestions, .. }) => {
+                assert_eq!(suggestions.first().map(String::as_str), Some("Bulbasaur"));
+            }
+            other => panic!("unexpected {other:?}"),
+        }
+        assert_eq!(edit_distance("kitten", "sitting"), 3);
+    }
+}
@@ -0,0 +1,226 @@
+use strum::IntoEnumIterator;
+
+use crate::{
+    card_database::runtime_cards,
+    card_ids::CardId,
+    card_lookup::normalize_name,
+    card_validation::get_card_implementation_status,
+    database::get_card_by_enum,
+    models::{Attack, Card, EnergyType, PokemonCard},
+};
+
+/// Filters for `search_cards`. Unset filters match every card, and text filters match
+/// substrings ignoring case and accents. Pokémon-only filters (stage, HP, attacks...) leave
+/// out trainers when set.
+#[derive(Debug, Clone, Default)]
+pub struct CardQuery {
+    pub name: Option<String>,
+    pub stage: Option<u8>,
+    /// Any of these types
+    pub energy_types: Vec<EnergyType>,
+    pub min_hp: Option<u32>,
+    pub max_hp: Option<u32>,
+    /// Any of these weaknesses
+    pub weaknesses: Vec<EnergyType>,
+    pub max_retreat_cost: Option<usize>,
+    pub has_ability: Option<bool>,
+    /// Title of an attack. The attack filters must all hold for the same attack.
+    pub attack: Option<String>,
+    pub max_attack_cost: Option<usize>,
+    pub min_attack_damage: Option<u32>,
+    /// Text of an attack's, ability's or trainer's effect
+    pub effect: Option<String>,
+    pub booster_pack: Option<String>,
+    /// Exact rarity, e.g. "◊◊◊◊" or "☆"
+    pub rarity: Option<String>,
+    /// Whether the engine fully implements the card, see `get_card_implementation_status`
+    pub implemented: Option<bool>,
+}
+
+impl CardQuery {
+    pub fn matches(&self, card: &Card) -> bool {
+        let (booster_pack, rarity) = match card {
+            Card::Pokemon(pokemon) => (&pokemon.booster_pack, &pokemon.rarity),
+            Card::Trainer(trainer) => (&trainer.booster_pack, &trainer.rarity),
+        };
+        let matches_kind = match card {
+            Card::Pokemon(pokemon) => self.matches_pokemon(pokemon),
+            Card::Trainer(_) => !self.has_pokemon_filters(),
+        };
+        matches_kind
+            && text_matches(&self.name, &card.get_name())
+            && text_matches(&self.booster_pack, booster_pack)
+            && self.rarity.as_ref().is_none_or(|wanted| wanted == rarity)
+            && self
+                .effect
+                .as_ref()
+                .is_none_or(|_| effect_texts(card).any(|text| text_matches(&self.effect, text)))
+            && self.implemented.is_none_or(|implemented| {
+                get_card_implementation_status(card).is_complete() == implemented
+            })
+    }
+
+    fn has_pokemon_filters(&self) -> bool {
+        self.stage.is_some()
+            || !self.energy_types.is_empty()
+            || self.min_hp.is_some()
+            || self.max_hp.is_some()
+            || !self.weaknesses.is_empty()
+            || self.max_retreat_cost.is_some()
+            || self.has_ability.is_some()
+            || self.has_attack_filters()
+    }
+
+    fn has_attack_filters(&self) -> bool {
+        self.attack.is_some() || self.max_attack_cost.is_some() || self.min_attack_damage.is_some()
+    }
+
+    fn matches_pokemon(&self, pokemon: &PokemonCard) -> bool {
+        self.stage.is_none_or(|stage| pokemon.stage == stage)
+            && (self.energy_types.is_empty() || self.energy_types.contains(&pokemon.energy_type))
+            && self.min_hp.is_none_or(|min_hp| pokemon.hp >= min_hp)
+            && self.max_hp.is_none_or(|max_hp| pokemon.hp <= max_hp)
+            && (self.weaknesses.is_empty()
+                || pokemon
+                    .weakness
+                    .is_some_and(|weakness| self.weaknesses.contains(&weakness)))
+            && self
+                .max_retreat_cost
+                .is_none_or(|max_cost| pokemon.retreat_cost.len() <= max_cost)
+            && self
+                .has_ability
+                .is_none_or(|has_ability| pokemon.ability.is_some() == has_ability)
+            && (!self.has_attack_filters()
+                || pokemon
+                    .attacks
+                    .iter()
+                    .any(|attack| self.matches_attack(attack)))
+    }
+
+    fn matches_attack(&self, attack: &Attack) -> bool {
+        text_matches(&self.attack, &attack.title)
+            && self
+                .max_attack_cost
+                .is_none_or(|max_cost| attack.energy_required.len() <= max_cost)
+            && self
+                .min_attack_damage
+                .is_none_or(|min_damage| attack.fixed_damage >= min_damage)
+    }
+}
+
+/// Every card matching `query`: the compiled ones in `CardId` order, then the ones loaded with
+/// `load_card_database`
+pub fn search_cards(query: &CardQuery) -> Vec<Card> {
+    CardId::iter()
+        .map(get_card_by_enum)
+        .chain(runtime_cards())
+        .filter(|card| query.matches(card))
+        .collect()
+}
+
+fn text_matches(wanted: &Option<String>, text: &str) -> bool {
+    wanted
+        .as_ref()
+        .is_none_or(|wanted| normalize_name(text).contains(&normalize_name(wanted)))
+}
+
+fn effect_texts(card: &Card) -> Box<dyn Iterator<Item = &str> + '_> {
+    match card {
+        Card::Pokemon(pokemon) => Box::new(
+            pokemon
+                .attacks
+                .iter()
+                .filter_map(|attack| attack.effect.as_deref())
+                .chain(
+                    pokemon
+                        .ability
+                        .iter()
+                        .map(|ability| ability.effect.as_str()),
+                ),
+        ),
+        Card::Trainer(trainer) => Box::new(std::iter::once(trainer.effect.as_str())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ids(query: &CardQuery) -> Vec<String> {
+        search_cards(query).iter().map(Card::get_id).collect()
+    }
+
+    #[test]
+    fn test_search_by_name_and_attack() {
+        let query = CardQuery {
+            name: Some("venusaur".to_string()),
+            attack: Some("giant bloom".to_string()),
+            ..Default::default()
+        };
+        let results = ids(&query);
+        assert!(results.contains(&"A1 004".to_string()));
+        assert!(results.iter().all(|id| id != "A1 003"));
+
+        let query = CardQuery {
+            name: Some("poke ball".to_string()),
+            ..Default::default()
+        };
+        assert!(ids(&query).contains(&"P-A 005".to_string()));
+    }
+
+    #[test]
+    fn test_pokemon_filters_leave_out_trainers() {
+        let query = CardQuery {
+            energy_types: vec![EnergyType::Grass],
+            stage: Some(2),
+            min_hp: Some(150),
+            weaknesses: vec![EnergyType::Fire],
+            has_ability: Some(false),
+            ..Default::default()
+        };
+        let results = search_cards(&query);
+        assert!(!results.is_empty());
+        for card in &results {
+            let Card::Pokemon(pokemon) = card else {
+                panic!("{card} is not a Pokémon");
+            };
+            assert_eq!(pokemon.energy_type, EnergyType::Grass);
+            assert_eq!(pokemon.stage, 2);
+            assert!(pokemon.hp >= 150);
+            assert_eq!(pokemon.weakness, Some(EnergyType::Fire));
+            assert!(pokemon.ability.is_none());
+        }
+    }
+
+    #[test]
+    fn test_attack_filters_hold_for_the_same_attack() {
+        let query = CardQuery {
+            max_attack_cost: Some(1),
+            min_attack_damage: Some(60),
+            ..Default::default()
+        };
+        for card in search_cards(&query) {
+            let Card::Pokemon(pokemon) = card else {
+                panic!("{card} is not a Pokémon");
+            };
+            assert!(pokemon
+                .attacks
+                .iter()
+                .any(|attack| attack.energy_required.len() <= 1 && attack.fixed_damage >= 60));
+        }
+    }
+
+    #[test]
+    fn test_search_by_effect_and_implementation() {
+        let query = CardQuery {
+            effect: Some("heal 20 damage from each of your pokemon".to_string()),
+            implemented: Some(true),
+            ..Default::default()
+        };
+        let results = search_cards(&query);
+        assert!(!results.is_empty());
+        assert!(results
+            .iter()
+            .all(|card| get_card_implementation_status(card).is_complete()));
+    }
+}
@@ -1,6 +1,7 @@
 use crate::{
-    actions::{ability_mechanic_from_effect, EFFECT_MECHANIC_MAP},
+    actions::{ability_mechanic_from_effect, attack_mechanic_from_effect},
     card_ids::CardId,
+    custom_cards::trainer_card_id,
     database::get_card_by_enum,
     models::{Card, TrainerType},
     move_generation::trainer_move_generation_implementation,
@@ -38,7 +39,12 @@ impl ImplementationStatus {
 }
 
 pub fn get_implementation_status(card_id: CardId) -> ImplementationStatus {
-    let card = get_card_by_enum(card_id);
+    get_card_implementation_status(&get_card_by_enum(card_id))
+}
+
+/// Like `get_implementation_status`, but also works for cards loaded at runtime (see
+/// `load_card_database`), which the engine can only play through effect-text mechanics.
+pub fn get_card_implementation_status(card: &Card) -> ImplementationStatus {
     let card_id_string = card.get_id();
 
     match card {
@@ -47,7 +53,7 @@ pub fn get_implementation_status(card_id: CardId) -> ImplementationStatus {
             for (index, attack) in pokemon.attacks.iter().enumerate() {
                 if let Some(effect_text) = &attack.effect {
                     if AttackId::from_pokemon_index(&card_id_string, index).is_none()
-                        && EFFECT_MECHANIC_MAP.get(&effect_text[..]).is_none()
+                        && attack_mechanic_from_effect(&effect_text[..]).is_none()
                     {
                         return ImplementationStatus::MissingAttack;
                     }
@@ -64,14 +70,17 @@ pub fn get_implementation_status(card_id: CardId) -> ImplementationStatus {
             }
         }
         Card::Trainer(trainer_card) => {
-            if trainer_card.trainer_card_type == TrainerType::Tool
-                && !is_tool_effect_implemented(&trainer_card)
-            {
-                return ImplementationStatus::MissingTool;
+            if trainer_card.trainer_card_type == TrainerType::Tool {
+                if !is_tool_effect_implemented(trainer_card) {
+                    return ImplementationStatus::MissingTool;
+                }
+            } else if trainer_card_id(&card_id_string).is_none() {
+                // Other trainers are implemented per card
+                return ImplementationStatus::MissingTrainer;
             }
 
             // Verify it can generate moves
-            let moves = trainer_move_generation_implementation(&State::default(), &trainer_card);
+            let moves = trainer_move_generation_implementation(&State::default(), trainer_card);
             if moves.is_none() {
                 return ImplementationStatus::MissingTrainer;
             };
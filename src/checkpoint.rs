@@ -0,0 +1,244 @@
+use std::{
+    fs,
+    path::Path,
+    time::{Duration, Instant},
+};
+
+use log::warn;
+use rayon::prelude::*;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+use crate::{
+    players::{fill_code_array, PlayerCode},
+    Deck,
+};
+
+/// Where and how often a long run saves its partial results
+#[derive(Debug, Clone)]
+pub struct CheckpointConfig {
+    pub path: String,
+    /// Minimum time between two saves (the final results are always saved)
+    pub interval: Duration,
+    /// Continue from the checkpoint at `path` instead of refusing to overwrite it
+    pub resume: bool,
+}
+
+impl CheckpointConfig {
+    pub fn new(path: &str) -> Self {
+        CheckpointConfig {
+            path: path.to_string(),
+            interval: Duration::from_secs(60),
+            resume: false,
+        }
+    }
+}
+
+/// Contents of a checkpoint file
+#[derive(Serialize, Deserialize)]
+struct CheckpointFile<T> {
+    /// Which runner wrote the file (e.g. "gauntlet")
+    kind: String,
+    /// Parameters of the run, so that a different run can't resume from it
+    run: serde_json::Value,
+    /// Base seed of the games, drawn once when the run didn't get one
+    seed: u64,
+    progress: T,
+}
+
+/// Periodically saves the progress of a run so that it can be resumed after a crash or Ctrl-C.
+///
+/// Games are seeded with `game_seed(Some(checkpoint.seed()), index)`, and runners save their
+/// progress only between batches of games, so a resumed run plays exactly the games that
+/// were left and ends with the same results as an uninterrupted one.
+pub struct Checkpoint {
+    config: CheckpointConfig,
+    kind: String,
+    run: serde_json::Value,
+    seed: u64,
+    last_save: Instant,
+}
+
+impl Checkpoint {
+    /// Starts checkpointing a run, returning the saved progress when resuming one.
+    ///
+    /// Fails if the file belongs to another kind of run or to different parameters, and if it
+    /// already exists but `config.resume` is not set.
+    pub fn open<T: DeserializeOwned>(
+        config: &CheckpointConfig,
+        kind: &str,
+        run: serde_json::Value,
+        seed: Option<u64>,
+    ) -> Result<(Self, Option<T>), String> {
+        let path = &config.path;
+        let mut checkpoint = Checkpoint {
+            config: config.clone(),
+            kind: kind.to_string(),
+            run,
+            seed: seed.unwrap_or_else(rand::random::<u64>),
+            last_save: Instant::now(),
+        };
+        if !Path::new(path).exists() {
+            if config.resume {
+                warn!("No checkpoint at {path} yet, starting from scratch");
+            }
+            return Ok((checkpoint, None));
+        }
+        if !config.resume {
+            return Err(format!(
+                "Checkpoint {path} already exists; pass --resume to continue it or remove it"
+            ));
+        }
+
+        let contents = fs::read_to_string(path)
+            .map_err(|err| format!("Failed to read checkpoint {path}: {err}"))?;
+        let file: CheckpointFile<T> = serde_json::from_str(&contents)
+            .map_err(|err| format!("Failed to parse checkpoint {path}: {err}"))?;
+        if file.kind != checkpoint.kind {
+            return Err(format!(
+                "Checkpoint {path} was written by {}, not {kind}",
+                file.kind
+            ));
+        }
+        if file.run != checkpoint.run {
+            return Err(format!(
+                "Checkpoint {path} belongs to a run with different decks, players, seed or number of games"
+            ));
+        }
+        checkpoint.seed = file.seed;
+        warn!("Resuming from checkpoint {path}");
+        Ok((checkpoint, Some(file.progress)))
+    }
+
+    /// Base seed of the run's games
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// Saves `progress` if the interval has passed since the last save
+    pub fn save_if_due<T: Serialize>(&mut self, progress: &T) {
+        if self.last_save.elapsed() >= self.config.interval {
+            self.save(progress);
+        }
+    }
+
+    /// Saves `progress`, replacing the previous checkpoint only once the new one is written.
+    /// Failures are logged rather than returned so they don't end a long run.
+    pub fn save<T: Serialize>(&mut self, progress: &T) {
+        let file = CheckpointFile {
+            kind: self.kind.clone(),
+            run: self.run.clone(),
+            seed: self.seed,
+            progress,
+        };
+        let path = &self.config.path;
+        let tmp_path = format!("{path}.tmp");
+        let result = serde_json::to_string(&file)
+            .map_err(|err| err.to_string())
+            .and_then(|json| fs::write(&tmp_path, json).map_err(|err| err.to_string()))
+            .and_then(|()| fs::rename(&tmp_path, path).map_err(|err| err.to_string()));
+        match result {
+            Ok(()) => self.last_save = Instant::now(),
+            Err(err) => warn!("Failed to save checkpoint {path}: {err}"),
+        }
+    }
+}
+
+/// Identifies a deck in a run's parameters by its card ids
+pub fn deck_card_ids(deck: &Deck) -> Vec<String> {
+    deck.cards.iter().map(|card| card.get_id()).collect()
+}
+
+/// Identifies the players of a run in its parameters (e.g. `["e2", "r"]`)
+pub fn player_names(players: &Option<Vec<PlayerCode>>) -> Vec<String> {
+    fill_code_array(players.clone())
+        .iter()
+        .map(|code| code.to_string())
+        .collect()
+}
+
+/// Plays games `start..num_games` (by index) and hands their results to `on_batch`
+/// together with the number of games done so far.
+///
+/// Without a checkpoint everything is one batch; with one, batches are small enough to
+/// save often while still keeping every thread busy.
+pub fn play_in_batches<R, P, B>(
+    start: usize,
+    num_games: usize,
+    checkpointing: bool,
+    parallel: bool,
+    play: P,
+    mut on_batch: B,
+) where
+    R: Send,
+    P: Fn(usize) -> R + Sync,
+    B: FnMut(usize, Vec<R>),
+{
+    let batch_size = if checkpointing {
+        rayon::current_num_threads() * 16
+    } else {
+        num_games.max(1)
+    };
+    let mut done = start;
+    while done < num_games {
+        let end = (done + batch_size).min(num_games);
+        let results: Vec<R> = if parallel {
+            (done..end).into_par_iter().map(&play).collect()
+        } else {
+            (done..end).map(&play).collect()
+        };
+        done = end;
+        on_batch(done, results);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resume_requires_same_run() {
+        let path = std::env::temp_dir().join("deckgym_checkpoint_test.json");
+        let path = path.to_str().unwrap();
+        fs::remove_file(path).ok();
+        let mut config = CheckpointConfig::new(path);
+        let run = serde_json::json!({ "num_games": 10 });
+
+        let (mut checkpoint, progress) =
+            Checkpoint::open::<Vec<u32>>(&config, "test", run.clone(), None).unwrap();
+        assert!(progress.is_none());
+        checkpoint.save(&vec![1, 2, 3]);
+
+        // An existing checkpoint is only picked up when resuming
+        assert!(Checkpoint::open::<Vec<u32>>(&config, "test", run.clone(), None).is_err());
+        config.resume = true;
+        let (resumed, progress) =
+            Checkpoint::open::<Vec<u32>>(&config, "test", run.clone(), Some(1)).unwrap();
+        assert_eq!(progress, Some(vec![1, 2, 3]));
+        assert_eq!(resumed.seed(), checkpoint.seed());
+
+        let other_run = serde_json::json!({ "num_games": 20 });
+        assert!(Checkpoint::open::<Vec<u32>>(&config, "test", other_run, None).is_err());
+        assert!(Checkpoint::open::<Vec<u32>>(&config, "other", run, None).is_err());
+        fs::remove_file(path).ok();
+    }
+
+    #[test]
+    fn test_play_in_batches_covers_remaining_games() {
+        let mut played = Vec::new();
+        let mut last_done = 0;
+        play_in_batches(
+            3,
+            200,
+            true,
+            false,
+            |index| index,
+            |done, batch| {
+                assert!(done > last_done);
+                last_done = done;
+                played.extend(batch);
+            },
+        );
+        assert_eq!(played, (3..200).collect::<Vec<_>>());
+        assert_eq!(last_done, 200);
+    }
+}
@@ -0,0 +1,485 @@
+use std::collections::HashMap;
+use std::fs;
+use std::mem::discriminant;
+use std::sync::{LazyLock, Mutex, RwLock};
+
+use serde::Deserialize;
+use strum::IntoEnumIterator;
+
+use crate::{
+    actions::{
+        abilities::AbilityMechanic, attacks::Mechanic, trainer_mechanic::TrainerMechanic,
+        EFFECT_ABILITY_MECHANIC_MAP, EFFECT_MECHANIC_MAP,
+    },
+    card_database::{add_runtime_card, runtime_cards},
+    card_ids::CardId,
+    card_validation::get_implementation_status,
+    database::get_card_ref,
+    models::{Ability, Attack, Card, EnergyType, PokemonCard, TrainerCard, TrainerType},
+};
+
+/// Set of the ids given to custom cards, e.g. "CUSTOM 001"
+pub const CUSTOM_SET: &str = "CUSTOM";
+
+/// Mechanics of custom attacks and abilities, by effect text
+static CUSTOM_ATTACK_MECHANICS: LazyLock<RwLock<HashMap<String, &'static Mechanic>>> =
+    LazyLock::new(Default::default);
+static CUSTOM_ABILITY_MECHANICS: LazyLock<RwLock<HashMap<String, &'static AbilityMechanic>>> =
+    LazyLock::new(Default::default);
+/// Held while loading, so that concurrent loads give distinct ids
+static LOADING: Mutex<()> = Mutex::new(());
+/// Compiled trainer each custom or database-loaded trainer plays as, by its id
+static CUSTOM_TRAINERS: LazyLock<RwLock<HashMap<String, CardId>>> = LazyLock::new(Default::default);
+
+/// A hypothetical card, as written in a custom cards file
+#[derive(Debug, Clone, Deserialize)]
+pub enum CustomCard {
+    Pokemon(CustomPokemon),
+    Trainer(CustomTrainer),
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct CustomPokemon {
+    pub name: String,
+    #[serde(default)]
+    pub stage: u8,
+    #[serde(default)]
+    pub evolves_from: Option<String>,
+    pub hp: u32,
+    pub energy_type: EnergyType,
+    #[serde(default)]
+    pub ability: Option<CustomAbility>,
+    #[serde(default)]
+    pub attacks: Vec<CustomAttack>,
+    #[serde(default)]
+    pub weakness: Option<EnergyType>,
+    /// Number of Colorless energy
+    #[serde(default)]
+    pub retreat_cost: usize,
+}
+
+/// An attack doing `damage`, plus what its `mechanic` does. Without a mechanic, an `effect`
+/// text of an existing attack plays like that attack.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct CustomAttack {
+    pub title: String,
+    #[serde(default)]
+    pub energy_required: Vec<EnergyType>,
+    #[serde(default)]
+    pub damage: u32,
+    #[serde(default)]
+    pub effect: Option<String>,
+    #[serde(default)]
+    pub mechanic: Option<Mechanic>,
+}
+
+/// Like `CustomAttack`, an ability needs a `mechanic` or the `effect` text of an existing one
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct CustomAbility {
+    pub title: String,
+    #[serde(default)]
+    pub effect: Option<String>,
+    #[serde(default)]
+    pub mechanic: Option<AbilityMechanic>,
+}
+
+/// Trainers are implemented per card, so a custom trainer plays as the first implemented
+/// trainer of its type with the same `mechanic` and/or `effect` text.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct CustomTrainer {
+    pub name: String,
+    pub trainer_card_type: TrainerType,
+    #[serde(default)]
+    pub effect: Option<String>,
+    #[serde(default)]
+    pub mechanic: Option<TrainerMechanic>,
+}
+
+/// A custom card ready to be added, with the mechanics it needs registered
+struct PreparedCard {
+    card: Card,
+    attack_mechanics: Vec<(String, Mechanic)>,
+    ability_mechanic: Option<(String, AbilityMechanic)>,
+    plays_as: Option<CardId>,
+}
+
+/// Loads a JSON list of custom cards (`{"Pokemon": {...}}` or `{"Trainer": {...}}`) composed
+/// from the existing attack, ability and trainer mechanics, so they can be played without
+/// writing Rust. Each card gets the next free id of the `CUSTOM` set ("CUSTOM 001", ...),
+/// which deck files use like any other id (e.g. `2 Shadow Mewtwo CUSTOM 1`).
+///
+/// Returns the ids given to the cards, in file order. Nothing is added if a card is invalid.
+pub fn load_custom_cards(path: &str) -> Result<Vec<String>, String> {
+    let contents = fs::read_to_string(path)
+        .map_err(|err| format!("Failed to read custom cards {path}: {err}"))?;
+    load_custom_cards_from_string(&contents)
+        .map_err(|err| format!("Invalid custom cards {path}: {err}"))
+}
+
+/// See `load_custom_cards`
+pub fn load_custom_cards_from_string(json: &str) -> Result<Vec<String>, String> {
+    let definitions: Vec<CustomCard> = serde_json::from_str(json).map_err(|err| err.to_string())?;
+    let _loading = LOADING.lock().expect("Custom cards lock poisoned");
+    let first_number = runtime_cards()
+        .iter()
+        .filter(|card| card.get_id().starts_with(CUSTOM_SET))
+        .count()
+        + 1;
+    let prepared = definitions
+        .into_iter()
+        .enumerate()
+        .map(|(index, definition)| {
+            let id = format!("{CUSTOM_SET} {:03}", first_number + index);
+            definition.prepare(id)
+        })
+        .collect::<Result<Vec<PreparedCard>, _>>()?;
+    check_consistent(prepared.iter().flat_map(|card| &card.attack_mechanics))?;
+    check_consistent(
+        prepared
+            .iter()
+            .filter_map(|card| card.ability_mechanic.as_ref()),
+    )?;
+
+    let mut ids = Vec::new();
+    for prepared in prepared {
+        let id = prepared.card.get_id();
+        for (effect, mechanic) in prepared.attack_mechanics {
+            register(&CUSTOM_ATTACK_MECHANICS, effect, mechanic);
+        }
+        if let Some((effect, mechanic)) = prepared.ability_mechanic {
+            register(&CUSTOM_ABILITY_MECHANICS, effect, mechanic);
+        }
+        if let Some(card_id) = prepared.plays_as {
+            play_trainer_as(id.clone(), card_id);
+        }
+        add_runtime_card(prepared.card);
+        ids.push(id);
+    }
+    Ok(ids)
+}
+
+/// The mechanic a custom attack effect text was loaded with
+pub(crate) fn custom_attack_mechanic(effect: &str) -> Option<&'static Mechanic> {
+    lookup(&CUSTOM_ATTACK_MECHANICS, effect)
+}
+
+/// The mechanic a custom ability effect text was loaded with
+pub(crate) fn custom_ability_mechanic(effect: &str) -> Option<&'static AbilityMechanic> {
+    lookup(&CUSTOM_ABILITY_MECHANICS, effect)
+}
+
+/// Makes the runtime trainer `id` play as the compiled trainer `card_id`
+pub(crate) fn play_trainer_as(id: String, card_id: CardId) {
+    CUSTOM_TRAINERS
+        .write()
+        .expect("Custom cards lock poisoned")
+        .insert(id, card_id);
+}
+
+/// The card whose implementation plays a trainer: the trainer itself, or the one a custom or
+/// database-loaded trainer plays as
+pub fn trainer_card_id(id: &str) -> Option<CardId> {
+    CardId::from_card_id(id).or_else(|| {
+        CUSTOM_TRAINERS
+            .read()
+            .expect("Custom cards lock poisoned")
+            .get(id)
+            .copied()
+    })
+}
+
+impl CustomCard {
+    fn prepare(self, id: String) -> Result<PreparedCard, String> {
+        match self {
+            CustomCard::Pokemon(pokemon) => pokemon.prepare(id),
+            CustomCard::Trainer(trainer) => trainer.prepare(id),
+        }
+    }
+}
+
+impl CustomPokemon {
+    fn prepare(self, id: String) -> Result<PreparedCard, String> {
+        let mut attack_mechanics = Vec::new();
+        let mut attacks = Vec::new();
+        for attack in self.attacks {
+            let effect = match attack.mechanic {
+                Some(mechanic) => {
+                    if !is_simulated(&mechanic, &EFFECT_MECHANIC_MAP) {
+                        return Err(format!(
+                            "No implemented attack plays like {mechanic:?} ({} of {})",
+                            attack.title, self.name
+                        ));
+                    }
+                    let effect = attack.effect.unwrap_or_else(|| format!("{mechanic:?}"));
+                    check_effect(
+                        &effect,
+                        &mechanic,
+                        EFFECT_MECHANIC_MAP.get(effect.as_str()),
+                        &CUSTOM_ATTACK_MECHANICS,
+                    )?;
+                    attack_mechanics.push((effect.clone(), mechanic));
+                    Some(effect)
+                }
+                None => attack.effect,
+            };
+            attacks.push(Attack {
+                energy_required: attack.energy_required,
+                title: attack.title,
+                fixed_damage: attack.damage,
+                effect,
+            });
+        }
+
+        let mut ability_mechanic = None;
+        let ability = match self.ability {
+            Some(CustomAbility {
+                title,
+                effect,
+                mechanic: Some(mechanic),
+            }) => {
+                if !is_simulated(&mechanic, &EFFECT_ABILITY_MECHANIC_MAP) {
+                    return Err(format!(
+                        "No implemented ability plays like {mechanic:?} ({title} of {})",
+                        self.name
+                    ));
+                }
+                let effect = effect.unwrap_or_else(|| format!("{mechanic:?}"));
+                check_effect(
+                    &effect,
+                    &mechanic,
+                    EFFECT_ABILITY_MECHANIC_MAP.get(effect.as_str()),
+                    &CUSTOM_ABILITY_MECHANICS,
+                )?;
+                ability_mechanic = Some((effect.clone(), mechanic));
+                Some(Ability { title, effect })
+            }
+            Some(CustomAbility {
+                title,
+                effect: Some(effect),
+                mechanic: None,
+            }) => Some(Ability { title, effect }),
+            Some(CustomAbility { title, .. }) => {
+                return Err(format!(
+                    "Ability {title} of {} needs a mechanic or an effect",
+                    self.name
+                ))
+            }
+            None => None,
+        };
+
+        let card = Card::from(PokemonCard {
+            id,
+            name: self.name,
+            stage: self.stage,
+            evolves_from: self.evolves_from,
+            hp: self.hp,
+            energy_type: self.energy_type,
+            ability,
+            attacks,
+            weakness: self.weakness,
+            retreat_cost: vec![EnergyType::Colorless; self.retreat_cost],
+            rarity: String::new(),
+            booster_pack: CUSTOM_SET.to_string(),
+        });
+        Ok(PreparedCard {
+            card,
+            attack_mechanics,
+            ability_mechanic,
+            plays_as: None,
+        })
+    }
+}
+
+impl CustomTrainer {
+    fn prepare(self, id: String) -> Result<PreparedCard, String> {
+        if self.mechanic.is_none() && self.effect.is_none() {
+            return Err(format!("{} needs a mechanic or an effect", self.name));
+        }
+        let mechanic = self
+            .mechanic
+            .as_ref()
+            .map(|mechanic| format!("{mechanic:?}"));
+        let plays_as = CardId::iter()
+            .find(|&card_id| {
+                let Card::Trainer(trainer) = get_card_ref(card_id) else {
+                    return false;
+                };
+                trainer.trainer_card_type == self.trainer_card_type
+                    && self
+                        .effect
+                        .as_ref()
+                        .is_none_or(|effect| *effect == trainer.effect)
+                    && mechanic.as_ref().is_none_or(|mechanic| {
+                        card_id
+                            .get_trainer_mechanic()
+                            .is_some_and(|other| format!("{other:?}") == *mechanic)
+                    })
+                    && get_implementation_status(card_id).is_complete()
+            })
+            .ok_or_else(|| {
+                format!(
+                    "No implemented {:?} plays like {}",
+                    self.trainer_card_type, self.name
+                )
+            })?;
+        let Card::Trainer(original) = get_card_ref(plays_as) else {
+            unreachable!("Custom trainers play as trainers");
+        };
+        let card = Card::from(TrainerCard {
+            id,
+            trainer_card_type: self.trainer_card_type,
+            name: self.name,
+            effect: self.effect.unwrap_or_else(|| original.effect.clone()),
+            rarity: String::new(),
+            booster_pack: CUSTOM_SET.to_string(),
+        });
+        Ok(PreparedCard {
+            card,
+            attack_mechanics: Vec::new(),
+            ability_mechanic: None,
+            plays_as: Some(plays_as),
+        })
+    }
+}
+
+/// Whether an implemented effect text plays a mechanic of the same kind, i.e. the engine
+/// simulates it (some kinds only describe cards, e.g. for vectorization)
+fn is_simulated<T>(mechanic: &T, implemented: &HashMap<&'static str, T>) -> bool {
+    let kind = discriminant(mechanic);
+    implemented
+        .values()
+        .any(|other| discriminant(other) == kind)
+}
+
+/// Fails if the effect text already plays as a different mechanic
+fn check_effect<T: PartialEq + 'static>(
+    effect: &str,
+    mechanic: &T,
+    compiled: Option<&T>,
+    custom: &RwLock<HashMap<String, &'static T>>,
+) -> Result<(), String> {
+    match compiled.or_else(|| lookup(custom, effect)) {
+        Some(existing) if existing != mechanic => {
+            Err(format!("Effect \"{effect}\" already has another mechanic"))
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Fails if the same effect text is given different mechanics
+fn check_consistent<'a, T: PartialEq + 'a>(
+    mechanics: impl Iterator<Item = &'a (String, T)>,
+) -> Result<(), String> {
+    let mut seen: HashMap<&str, &T> = HashMap::new();
+    for (effect, mechanic) in mechanics {
+        if *seen.entry(effect).or_insert(mechanic) != mechanic {
+            return Err(format!("Effect \"{effect}\" is given different mechanics"));
+        }
+    }
+    Ok(())
+}
+
+fn register<T>(mechanics: &RwLock<HashMap<String, &'static T>>, effect: String, mechanic: T) {
+    let mut mechanics = mechanics.write().expect("Custom cards lock poisoned");
+    mechanics
+        .entry(effect)
+        .or_insert_with(|| Box::leak(Box::new(mechanic)));
+}
+
+fn lookup<T>(mechanics: &RwLock<HashMap<String, &'static T>>, effect: &str) -> Option<&'static T> {
+    mechanics
+        .read()
+        .expect("Custom cards lock poisoned")
+        .get(effect)
+        .copied()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        card_database::get_card_by_id,
+        card_validation::get_card_implementation_status,
+        players::{Player, RandomPlayer},
+        Deck, Game,
+    };
+
+    const CUSTOM_CARDS: &str = r#"[
+        {"Pokemon": {
+            "name": "Shadow Mewtwo",
+            "hp": 120,
+            "energy_type": "Psychic",
+            "weakness": "Darkness",
+            "retreat_cost": 1,
+            "ability": {"title": "Dark Mending", "mechanic": {"HealAllYourPokemon": {"amount": 10}}},
+            "attacks": [
+                {"title": "Reckless Blast", "energy_required": ["Psychic"], "damage": 70,
+                 "mechanic": {"SelfDamage": {"amount": 30}}},
+                {"title": "Psy Strike", "energy_required": ["Psychic", "Colorless"], "damage": 60}
+            ]
+        }},
+        {"Trainer": {
+            "name": "Tiny Potion",
+            "trainer_card_type": "Item",
+            "mechanic": {"type": "Heal", "params": {"amount": 20, "cure_status": false, "target_scope": "SelfBoard"}}
+        }}
+    ]"#;
+
+    #[test]
+    fn test_custom_cards_are_playable() {
+        let ids = load_custom_cards_from_string(CUSTOM_CARDS).unwrap();
+        assert_eq!(ids.len(), 2);
+        assert!(ids.iter().all(|id| id.starts_with("CUSTOM ")));
+
+        let mewtwo = get_card_by_id(&ids[0]).unwrap();
+        assert_eq!(mewtwo.get_name(), "Shadow Mewtwo");
+        assert!(get_card_implementation_status(&mewtwo).is_complete());
+        let effect = mewtwo.get_attacks()[0].effect.clone().unwrap();
+        assert_eq!(
+            custom_attack_mechanic(&effect),
+            Some(&Mechanic::SelfDamage { amount: 30 })
+        );
+        let potion = get_card_by_id(&ids[1]).unwrap();
+        assert!(get_card_implementation_status(&potion).is_complete());
+        assert_eq!(trainer_card_id(&ids[1]), Some(CardId::PA001Potion));
+
+        let deck = Deck::from_string(&format!(
+            "Energy: Psychic\n2 {}\n2 {}\n2 Ralts A1 130\n2 Kirlia A1 131\n2 Gardevoir A1 132\n2 Poké Ball P-A 5\n",
+            ids[0], ids[1]
+        ))
+        .unwrap();
+        for seed in 0..5 {
+            let players: Vec<Box<dyn Player + Send>> = vec![
+                Box::new(RandomPlayer { deck: deck.clone() }),
+                Box::new(RandomPlayer { deck: deck.clone() }),
+            ];
+            Game::new(players, seed).play();
+        }
+    }
+
+    #[test]
+    fn test_invalid_custom_cards_are_not_added() {
+        let unknown_trainer = r#"[
+            {"Pokemon": {"name": "Fine Mon", "hp": 60, "energy_type": "Grass"}},
+            {"Trainer": {"name": "Mega Potion", "trainer_card_type": "Item",
+                         "mechanic": {"type": "Draw", "params": {"amount": 9, "shuffle_hand_first": false}}}}
+        ]"#;
+        assert!(load_custom_cards_from_string(unknown_trainer).is_err());
+        // Only describes cards, no implemented effect plays it
+        let unsimulated = r#"[{"Pokemon": {"name": "Odd Mon", "hp": 60, "energy_type": "Grass",
+            "attacks": [{"title": "Bump", "mechanic": {"DiscardOpponentDeck": {"count": 1}}}]}}]"#;
+        assert!(load_custom_cards_from_string(unsimulated).is_err());
+        let conflicting = r#"[{"Pokemon": {"name": "Odd Mon", "hp": 60, "energy_type": "Grass",
+            "attacks": [{"title": "Bump", "effect": "Heal 30 damage from this Pokémon.",
+                         "mechanic": {"SelfDamage": {"amount": 30}}}]}}]"#;
+        assert!(load_custom_cards_from_string(conflicting).is_err());
+        assert!(load_custom_cards_from_string(r#"[{"Pokemon": {"name": "No HP"}}]"#).is_err());
+        assert!(!runtime_cards()
+            .iter()
+            .any(|card| card.get_name() == "Fine Mon"));
+    }
+}
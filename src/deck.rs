@@ -2,12 +2,18 @@ use rand::seq::SliceRandom;
 use rand::Rng;
 use serde::{Deserialize, Serialize};
 use std::collections::HashSet;
+use std::fmt;
 use std::fs;
 use std::hash::{Hash, Hasher};
 
+use crate::card_database::get_card_by_id;
 use crate::card_ids::CardId;
+use crate::card_lookup::{resolve_card_name, CardNameError};
+use crate::card_validation::{get_card_implementation_status, ImplementationStatus};
+use crate::custom_cards::trainer_card_id;
 use crate::database::get_card_by_enum;
-use crate::models::{Card, EnergyType};
+use crate::format::Format;
+use crate::models::{Card, EnergyType, TrainerType};
 
 /// Represents a deck of cards.
 #[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
@@ -38,9 +44,22 @@ impl Deck {
     }
 
     pub fn from_string(contents: &str) -> Result<Self, String> {
+        Self::parse(contents).map_err(|issues| {
+            issues
+                .iter()
+                .map(|issue| issue.to_string())
+                .collect::<Vec<_>>()
+                .join("; ")
+        })
+    }
+
+    /// Parses a deck, reporting every unknown card and invalid line instead of stopping at the first
+    pub fn parse(contents: &str) -> Result<Self, Vec<DeckIssue>> {
         let mut energy_types = HashSet::new();
         let mut cards = Vec::new();
-        for line in contents.lines() {
+        let mut issues = Vec::new();
+        for (index, line) in contents.lines().enumerate() {
+            let line_number = index + 1;
             // if line is empty or starts with "Pokemon:" or "Trainer:, skip it
             let trimmed = line.trim();
             if trimmed.is_empty()
@@ -49,25 +68,51 @@ impl Deck {
             {
                 continue;
             }
-            if trimmed.starts_with("Energy:") {
-                let energy_type: &str = trimmed
-                    .split_whitespace()
-                    .last()
-                    .expect("Energy: line should have an energy type");
-                energy_types
-                    .insert(EnergyType::from_str(energy_type).expect("Invalid energy type"));
+            if let Some(energy) = trimmed.strip_prefix("Energy:") {
+                let energy = energy.split_whitespace().last().unwrap_or_default();
+                match EnergyType::from_str(energy) {
+                    Some(energy_type) => {
+                        energy_types.insert(energy_type);
+                    }
+                    None => issues.push(DeckIssue::InvalidEnergy {
+                        line: line_number,
+                        energy: energy.to_string(),
+                    }),
+                }
                 continue;
             }
 
-            let (count, card) = Card::from_str_with_count(trimmed)?;
-            cards.extend(vec![card; count as usize]);
+            match parse_card_line(trimmed) {
+                Ok((count, card)) => cards.extend(vec![card; count as usize]),
+                Err(CardLineError::Invalid(_)) => issues.push(DeckIssue::InvalidLine {
+                    line: line_number,
+                    text: trimmed.to_string(),
+                }),
+                Err(CardLineError::UnknownId(id)) => issues.push(DeckIssue::UnknownCard {
+                    line: line_number,
+                    id,
+                }),
+                Err(CardLineError::UnknownName(error)) => issues.push(DeckIssue::UnresolvedName {
+                    line: line_number,
+                    error,
+                }),
+            }
+        }
+        if !issues.is_empty() {
+            return Err(issues);
         }
 
-        // If empty energy types set, populate it with the energy types from the cards
+        // If empty energy types set, populate it with the energy types from the cards. Colorless
+        // and Dragon Pokémon don't tell us anything, as the Energy Zone never generates those.
         if energy_types.is_empty() {
             cards.iter().for_each(|x| {
                 if let Card::Pokemon(pokemon_card) = x {
-                    energy_types.insert(pokemon_card.energy_type);
+                    if !matches!(
+                        pokemon_card.energy_type,
+                        EnergyType::Colorless | EnergyType::Dragon
+                    ) {
+                        energy_types.insert(pokemon_card.energy_type);
+                    }
                 }
             });
         }
@@ -81,25 +126,146 @@ impl Deck {
         })
     }
 
+    /// The deck in the DeckGym "Copy as Text" format that `from_string` parses: the `Energy:`
+    /// lines, then Pokémon and trainers with their counts, in the order they first appear.
+    pub fn to_deckgym_string(&self) -> String {
+        let mut energy_types = self.energy_types.clone();
+        energy_types.sort();
+        let mut lines: Vec<String> = energy_types
+            .iter()
+            .map(|energy| format!("Energy: {energy}"))
+            .collect();
+
+        let mut counts: Vec<(&Card, u32)> = Vec::new();
+        for card in &self.cards {
+            match counts.iter_mut().find(|(other, _)| *other == card) {
+                Some((_, count)) => *count += 1,
+                None => counts.push((card, 1)),
+            }
+        }
+        let (pokemon, trainers): (Vec<_>, Vec<_>) = counts
+            .into_iter()
+            .partition(|(card, _)| matches!(card, Card::Pokemon(_)));
+        for (header, section) in [("Pokémon", pokemon), ("Trainer", trainers)] {
+            if section.is_empty() {
+                continue;
+            }
+            if !lines.is_empty() {
+                lines.push(String::new());
+            }
+            let total: u32 = section.iter().map(|(_, count)| count).sum();
+            lines.push(format!("{header}: {total}"));
+            for (card, count) in section {
+                lines.push(format!("{count} {} {}", card.get_name(), card.get_id()));
+            }
+        }
+        lines.join("\n") + "\n"
+    }
+
+    /// Whether the deck can be played: 20 cards, at least 1 basic, at most 2 copies of a
+    /// name, only energy types the game generates and only trainers the engine can play. See `validate` for the reasons.
     pub fn is_valid(&self) -> bool {
-        let basic = self.cards.iter().filter(|x| x.is_basic()).count();
-        let has_correct_size = self.cards.len() == 20 && basic >= 1;
+        self.rule_issues().is_empty()
+    }
 
-        if !has_correct_size {
-            return false;
+    /// Everything wrong with the deck: broken deck-building rules (see `DeckIssue::is_error`)
+    /// and cards the engine doesn't fully implement.
+    pub fn validate(&self) -> Vec<DeckIssue> {
+        let mut issues = self.rule_issues();
+        let mut checked = HashSet::new();
+        for card in &self.cards {
+            if !checked.insert(card.get_id()) || is_unplayable_trainer(card) {
+                continue;
+            }
+            let status = get_card_implementation_status(card);
+            if !status.is_complete() {
+                issues.push(DeckIssue::UnimplementedCard {
+                    id: card.get_id(),
+                    name: card.get_name(),
+                    status,
+                });
+            }
         }
+        issues
+    }
+
+    /// `validate`, plus what the format doesn't allow
+    pub fn validate_in_format(&self, format: &Format) -> Vec<DeckIssue> {
+        let mut issues = self.validate();
+        issues.extend(format.deck_issues(&self.cards));
+        issues
+    }
 
-        // Check that no card name appears more than twice
-        let mut card_counts = std::collections::HashMap::new();
+    /// The `CardId` of every card, or an error if the deck has cards loaded at runtime
+    pub fn card_ids(&self) -> Result<Vec<CardId>, String> {
+        self.cards.iter().map(Card::try_get_card_id).collect()
+    }
+
+    /// Errors of `validate`, joined into one message, if the deck can't be played
+    pub fn check_valid(&self) -> Result<(), String> {
+        let errors: Vec<String> = self
+            .rule_issues()
+            .iter()
+            .map(|issue| issue.to_string())
+            .collect();
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors.join("; "))
+        }
+    }
+
+    fn rule_issues(&self) -> Vec<DeckIssue> {
+        let mut issues = Vec::new();
+        if self.cards.len() != 20 {
+            issues.push(DeckIssue::WrongSize {
+                cards: self.cards.len(),
+            });
+        }
+        if !self.cards.iter().any(|x| x.is_basic()) {
+            issues.push(DeckIssue::NoBasic);
+        }
+
+        // Check that no card name appears more than twice (reprints count together, and so do
+        // different cards sharing a name)
+        let mut card_counts: Vec<(String, usize)> = Vec::new();
         for card in &self.cards {
-            let count = card_counts.entry(card.get_name()).or_insert(0);
-            *count += 1;
-            if *count > 2 {
-                return false;
+            let name = card.get_name();
+            match card_counts
+                .iter_mut()
+                .find(|(existing, _)| *existing == name)
+            {
+                Some((_, count)) => *count += 1,
+                None => card_counts.push((name, 1)),
+            }
+        }
+        for (name, count) in card_counts {
+            if count > 2 {
+                issues.push(DeckIssue::TooManyCopies { name, count });
             }
         }
 
-        true
+        let mut reported = HashSet::new();
+        for card in &self.cards {
+            if is_unplayable_trainer(card) && reported.insert(card.get_id()) {
+                issues.push(DeckIssue::UnplayableTrainer {
+                    id: card.get_id(),
+                    name: card.get_name(),
+                });
+            }
+        }
+
+        if self.energy_types.is_empty() {
+            issues.push(DeckIssue::NoEnergy);
+        }
+        let mut energy_types = self.energy_types.clone();
+        energy_types.sort();
+        for energy in energy_types {
+            if matches!(energy, EnergyType::Colorless | EnergyType::Dragon) {
+                issues.push(DeckIssue::EnergyNotInGame(energy));
+            }
+        }
+        issues
     }
 
     /// Draws a card from the deck.
@@ -168,27 +334,156 @@ impl Deck {
 }
 
 impl Card {
-    /// Parses a line and returns a tuple of count and a `Card`.
+    /// Parses a line and returns a tuple of count and a `Card`. The card is given either by
+    /// its trailing `<set> <number>` (e.g. "2 Bulbasaur A1 001") or by name (e.g. "2 Mewtwo ex"
+    /// or "2 Mewtwo ex (A1)"), see `resolve_card_name`.
     pub fn from_str_with_count(line: &str) -> Result<(u32, Card), String> {
-        let parts: Vec<&str> = line.split_whitespace().collect();
-        if parts.len() < 3 {
-            return Err(format!("Invalid card format: {line}"));
-        }
+        parse_card_line(line).map_err(|err| match err {
+            CardLineError::Invalid(message) => message,
+            CardLineError::UnknownId(id) => format!("Card ID not found for id: {id}"),
+            CardLineError::UnknownName(err) => format!("Invalid card line '{line}': {err}"),
+        })
+    }
+}
 
-        let count = parts[0]
-            .parse::<u32>()
-            .map_err(|_| format!("Invalid count: {}", parts[0]))?;
-        let set = parts[parts.len() - 2];
-        // maybe pad number with 0 on the left if missing 0s
-        let number = parts[parts.len() - 1];
-        let padded_number = format!("{number:0>3}");
-        let id = format!("{set} {padded_number}");
+/// Why `parse_card_line` failed
+enum CardLineError {
+    Invalid(String),
+    UnknownId(String),
+    UnknownName(CardNameError),
+}
+
+fn parse_card_line(line: &str) -> Result<(u32, Card), CardLineError> {
+    let parts: Vec<&str> = line.split_whitespace().collect();
+    if parts.len() < 2 {
+        return Err(CardLineError::Invalid(format!(
+            "Invalid card format: {line}"
+        )));
+    }
 
+    let count = parts[0]
+        .parse::<u32>()
+        .map_err(|_| CardLineError::Invalid(format!("Invalid count: {}", parts[0])))?;
+    let number = parts[parts.len() - 1];
+    if parts.len() < 3 || !number.chars().all(|c| c.is_ascii_digit()) {
         let card_id =
-            CardId::from_card_id(&id).ok_or_else(|| format!("Card ID not found for id: {id}"))?;
-        let card = get_card_by_enum(card_id);
+            resolve_card_name(&parts[1..].join(" ")).map_err(CardLineError::UnknownName)?;
+        return Ok((count, get_card_by_enum(card_id)));
+    }
+
+    let set = parts[parts.len() - 2];
+    // maybe pad number with 0 on the left if missing 0s
+    let padded_number = format!("{number:0>3}");
+    let id = format!("{set} {padded_number}");
+
+    let card = get_card_by_id(&id).ok_or(CardLineError::UnknownId(id))?;
+    Ok((count, card))
+}
 
-        Ok((count, card.clone()))
+/// Something wrong with a deck, see `Deck::parse` and `Deck::validate`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DeckIssue {
+    /// A line that is neither a header, an `Energy:` line nor `<count> <card>`
+    InvalidLine {
+        line: usize,
+        text: String,
+    },
+    UnknownCard {
+        line: usize,
+        id: String,
+    },
+    /// A card given by a name that matches no card, or prints that play differently
+    UnresolvedName {
+        line: usize,
+        error: CardNameError,
+    },
+    InvalidEnergy {
+        line: usize,
+        energy: String,
+    },
+    WrongSize {
+        cards: usize,
+    },
+    NoBasic,
+    TooManyCopies {
+        name: String,
+        count: usize,
+    },
+    /// No `Energy:` line and no Pokémon to infer the energy types from, so the Energy Zone
+    /// would have nothing to generate
+    NoEnergy,
+    /// An energy type the Energy Zone never generates (Colorless or Dragon)
+    EnergyNotInGame(EnergyType),
+    UnimplementedCard {
+        id: String,
+        name: String,
+        status: ImplementationStatus,
+    },
+    /// A trainer loaded at runtime that plays like no implemented trainer, so it can't be played
+    UnplayableTrainer {
+        id: String,
+        name: String,
+    },
+    /// A card from a set or booster pack the format doesn't allow, see `Format`
+    NotInFormat {
+        id: String,
+        name: String,
+    },
+    Banned {
+        id: String,
+        name: String,
+    },
+    OverFormatLimit {
+        name: String,
+        count: usize,
+        limit: usize,
+    },
+}
+
+impl DeckIssue {
+    /// Whether the deck can't be played at all (unimplemented cards play, just inaccurately)
+    pub fn is_error(&self) -> bool {
+        !matches!(self, DeckIssue::UnimplementedCard { .. })
+    }
+}
+
+impl fmt::Display for DeckIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DeckIssue::InvalidLine { line, text } => {
+                write!(f, "Line {line}: invalid card line '{text}'")
+            }
+            DeckIssue::UnknownCard { line, id } => write!(f, "Line {line}: unknown card {id}"),
+            DeckIssue::UnresolvedName { line, error } => write!(f, "Line {line}: {error}"),
+            DeckIssue::InvalidEnergy { line, energy } => {
+                write!(f, "Line {line}: invalid energy type '{energy}'")
+            }
+            DeckIssue::WrongSize { cards } => write!(f, "Deck has {cards} cards instead of 20"),
+            DeckIssue::NoBasic => write!(f, "Deck has no basic Pokémon"),
+            DeckIssue::TooManyCopies { name, count } => {
+                write!(f, "Deck has {count} copies of {name} (at most 2 allowed)")
+            }
+            DeckIssue::NoEnergy => write!(f, "Deck has no energy types (add an Energy: line)"),
+            DeckIssue::EnergyNotInGame(energy) => {
+                write!(f, "{energy} energy is never generated in the game")
+            }
+            DeckIssue::UnimplementedCard { id, name, status } => {
+                write!(f, "{name} ({id}): {}", status.description())
+            }
+            DeckIssue::UnplayableTrainer { id, name } => {
+                write!(f, "{name} ({id}) plays like no implemented trainer")
+            }
+            DeckIssue::NotInFormat { id, name } => {
+                write!(f, "{name} ({id}) is not legal in the format")
+            }
+            DeckIssue::Banned { id, name } => write!(f, "{name} ({id}) is banned in the format"),
+            DeckIssue::OverFormatLimit { name, count, limit } => {
+                write!(
+                    f,
+                    "Deck has {count} copies of {name} (the format allows {limit})"
+                )
+            }
+        }
     }
 }
 
@@ -196,6 +491,20 @@ pub fn is_basic(card: &Card) -> bool {
     card.is_basic()
 }
 
+/// Whether the card is a trainer loaded at runtime that the engine has no implementation to play
+/// with (tools and fossils don't need one)
+fn is_unplayable_trainer(card: &Card) -> bool {
+    match card {
+        Card::Trainer(trainer_card) => {
+            !matches!(
+                trainer_card.trainer_card_type,
+                TrainerType::Tool | TrainerType::Fossil
+            ) && trainer_card_id(&trainer_card.id).is_none()
+        }
+        Card::Pokemon(_) => false,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use rand::thread_rng;
@@ -296,6 +605,38 @@ Trainer: 12
         assert_eq!(deck.energy_types[0], EnergyType::Grass);
     }
 
+    #[test]
+    fn test_inferred_energy_skips_colorless_and_dragon() {
+        let string = "2 Swablu A4a 64\n2 Mega Altaria ex B1 102\n2 Chingling B1 109\n";
+        let deck = Deck::from_string(string).unwrap();
+        assert_eq!(deck.energy_types, vec![EnergyType::Psychic]);
+        assert!(!deck
+            .validate()
+            .iter()
+            .any(|issue| matches!(issue, DeckIssue::EnergyNotInGame(_))));
+
+        let altaria = fs::read_to_string("example_decks/altaria.txt").unwrap();
+        let without_energy: String = altaria
+            .lines()
+            .filter(|line| !line.starts_with("Energy:"))
+            .map(|line| format!("{line}\n"))
+            .collect();
+        let deck = Deck::from_string(&without_energy).unwrap();
+        assert!(deck.check_valid().is_ok());
+    }
+
+    #[test]
+    fn test_deck_without_energy_types_is_invalid() {
+        let string = "2 Swablu A4a 64\n2 Altaria B1 197\n";
+        let deck = Deck::from_string(string).unwrap();
+        assert!(deck.energy_types.is_empty());
+        assert!(deck.validate().contains(&DeckIssue::NoEnergy));
+        assert!(!deck.is_valid());
+
+        let deck = Deck::from_string(&format!("Energy: Psychic\n{string}")).unwrap();
+        assert!(!deck.validate().contains(&DeckIssue::NoEnergy));
+    }
+
     #[test]
     fn test_from_string_without_leading_zeros() {
         let string = r#"Energy: Grass
@@ -313,4 +654,80 @@ Trainer: 12
         let deck = Deck::from_string(string).expect("Failed to parse deck from string");
         assert_eq!(deck.cards.len(), 20);
     }
+
+    #[test]
+    fn test_parse_reports_every_bad_line() {
+        let string =
+            "Energy: Plasma\n2 Bulbasaur A1 001\n2 Missingno Z9 999\nnot a card\n2 Bulbsaur";
+        assert_eq!(
+            Deck::parse(string).unwrap_err(),
+            vec![
+                DeckIssue::InvalidEnergy {
+                    line: 1,
+                    energy: "Plasma".to_string(),
+                },
+                DeckIssue::UnknownCard {
+                    line: 3,
+                    id: "Z9 999".to_string(),
+                },
+                DeckIssue::InvalidLine {
+                    line: 4,
+                    text: "not a card".to_string(),
+                },
+                DeckIssue::UnresolvedName {
+                    line: 5,
+                    error: CardNameError::NotFound {
+                        name: "Bulbsaur".to_string(),
+                        suggestions: vec!["Bulbasaur".to_string()],
+                    },
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_validate() {
+        let deck = Deck::from_file("example_decks/venusaur-exeggutor.txt").unwrap();
+        assert!(deck.validate().is_empty());
+        assert!(deck.is_valid());
+
+        let deck = Deck::from_string("Energy: Colorless\n3 Ivysaur A1 002\n").unwrap();
+        assert_eq!(
+            deck.validate(),
+            vec![
+                DeckIssue::WrongSize { cards: 3 },
+                DeckIssue::NoBasic,
+                DeckIssue::TooManyCopies {
+                    name: "Ivysaur".to_string(),
+                    count: 3,
+                },
+                DeckIssue::EnergyNotInGame(EnergyType::Colorless),
+            ]
+        );
+        assert!(!deck.is_valid());
+        assert!(deck.check_valid().is_err());
+    }
+
+    #[test]
+    fn test_deckgym_string_round_trips() {
+        let deck = Deck::from_file("example_decks/venusaur-exeggutor.txt").unwrap();
+        let text = deck.to_deckgym_string();
+        assert!(text.starts_with("Energy: Grass\n\nPokémon: 10\n2 Bulbasaur A1 001\n"));
+        assert!(text.contains("\nTrainer: 10\n2 Professor's Research P-A 007\n"));
+        assert_eq!(Deck::from_string(&text).unwrap(), deck);
+    }
+
+    #[test]
+    fn test_from_string_by_name() {
+        let deck = Deck::from_string(
+            "2 Bulbasaur (A1)\n2 ivysaur (a1)\n2 Poke Ball (P-A)\n1 Pikachu ex (A1)",
+        )
+        .unwrap();
+        let ids: Vec<String> = deck.cards.iter().map(|card| card.get_id()).collect();
+        assert_eq!(
+            &ids[..6],
+            ["A1 001", "A1 001", "A1 002", "A1 002", "P-A 005", "P-A 005"]
+        );
+        assert_eq!(deck.cards[6].get_id(), "A1 096");
+    }
 }
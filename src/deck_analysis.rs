@@ -0,0 +1,316 @@
+use std::collections::BTreeMap;
+
+use log::warn;
+use strum::IntoEnumIterator;
+
+use crate::{
+    card_ids::CardId,
+    database::get_card_ref,
+    deck::DeckIssue,
+    models::{Card, EnergyType, PokemonCard, TrainerType},
+    Deck,
+};
+
+/// Static report of a deck's composition and likely problems, see `Deck::analyze`
+#[derive(Debug, Clone, Default)]
+pub struct DeckAnalysis {
+    pub num_cards: usize,
+    pub basics: u32,
+    pub stage1: u32,
+    pub stage2: u32,
+    pub supporters: u32,
+    pub items: u32,
+    pub tools: u32,
+    pub fossils: u32,
+    pub stadiums: u32,
+    /// Pokémon cards by retreat cost
+    pub retreat_costs: BTreeMap<usize, u32>,
+    /// Pokémon cards by the points the opponent gets for knocking them out (ex 2, Mega 3)
+    pub knockout_points: BTreeMap<u8, u32>,
+    /// Energy types the deck generates
+    pub energy_types: Vec<EnergyType>,
+    /// Attacks needing an energy type the deck doesn't generate
+    pub uncovered_attacks: Vec<UncoveredAttack>,
+    /// Generated energy types that no attack of the deck needs
+    pub unused_energy_types: Vec<EnergyType>,
+    /// Evolutions that can't be reached with the deck's cards
+    pub broken_evolutions: Vec<BrokenEvolution>,
+    /// Broken deck-building rules and cards the engine doesn't implement yet, see `Deck::validate`
+    pub issues: Vec<DeckIssue>,
+}
+
+/// An attack whose cost includes energy the deck doesn't generate
+#[derive(Debug, Clone, PartialEq)]
+pub struct UncoveredAttack {
+    pub pokemon: String,
+    pub attack: String,
+    pub missing: Vec<EnergyType>,
+}
+
+/// An evolution card whose previous stage (or, for Stage 2 with Rare Candy, basic) is missing
+#[derive(Debug, Clone, PartialEq)]
+pub struct BrokenEvolution {
+    pub card: String,
+    /// Name of the missing card it evolves from
+    pub missing: String,
+}
+
+impl DeckAnalysis {
+    /// Whether the report found anything that will likely distort simulations
+    pub fn has_problems(&self) -> bool {
+        !self.uncovered_attacks.is_empty()
+            || !self.broken_evolutions.is_empty()
+            || !self.issues.is_empty()
+    }
+}
+
+impl Deck {
+    /// Analyzes the deck's composition without playing any game: stage and trainer counts,
+    /// retreat costs, point liability, energy coverage of attack costs, evolution lines that
+    /// can't be completed and cards the engine doesn't fully implement.
+    pub fn analyze(&self) -> DeckAnalysis {
+        let mut analysis = DeckAnalysis {
+            num_cards: self.cards.len(),
+            energy_types: self.energy_types.clone(),
+            ..Default::default()
+        };
+        analysis.energy_types.sort();
+
+        for card in &self.cards {
+            match card {
+                Card::Pokemon(pokemon) => {
+                    match pokemon.stage {
+                        0 => analysis.basics += 1,
+                        1 => analysis.stage1 += 1,
+                        _ => analysis.stage2 += 1,
+                    }
+                    *analysis
+                        .retreat_costs
+                        .entry(pokemon.retreat_cost.len())
+                        .or_insert(0) += 1;
+                    *analysis
+                        .knockout_points
+                        .entry(card.get_knockout_points())
+                        .or_insert(0) += 1;
+                }
+                Card::Trainer(trainer) => match trainer.trainer_card_type {
+                    TrainerType::Supporter => analysis.supporters += 1,
+                    TrainerType::Item => analysis.items += 1,
+                    TrainerType::Tool => analysis.tools += 1,
+                    TrainerType::Fossil => analysis.fossils += 1,
+                    TrainerType::Stadium => analysis.stadiums += 1,
+                },
+            }
+        }
+
+        let mut unique: Vec<&Card> = Vec::new();
+        for card in &self.cards {
+            if !unique.contains(&card) {
+                unique.push(card);
+            }
+        }
+
+        let mut needed_types = Vec::new();
+        for card in unique {
+            let Card::Pokemon(pokemon) = card else {
+                continue;
+            };
+            for attack in &pokemon.attacks {
+                let mut missing: Vec<EnergyType> = attack
+                    .energy_required
+                    .iter()
+                    .copied()
+                    .filter(|energy| *energy != EnergyType::Colorless)
+                    .collect();
+                missing.sort();
+                missing.dedup();
+                needed_types.extend(missing.iter().copied());
+                missing.retain(|energy| !analysis.energy_types.contains(energy));
+                if !missing.is_empty() {
+                    analysis.uncovered_attacks.push(UncoveredAttack {
+                        pokemon: pokemon.name.clone(),
+                        attack: attack.title.clone(),
+                        missing,
+                    });
+                }
+            }
+            if let Some(broken) = self.broken_evolution(pokemon) {
+                if !analysis.broken_evolutions.contains(&broken) {
+                    analysis.broken_evolutions.push(broken);
+                }
+            }
+        }
+        analysis.unused_energy_types = analysis
+            .energy_types
+            .iter()
+            .copied()
+            .filter(|energy| !needed_types.contains(energy))
+            .collect();
+
+        analysis.issues = self.validate();
+        analysis
+    }
+
+    /// Checks that the deck has what `pokemon` evolves from. A Stage 2 may instead be
+    /// reached from its basic with Rare Candy.
+    fn broken_evolution(&self, pokemon: &'static PokemonCard) -> Option<BrokenEvolution> {
+        let evolves_from = pokemon.evolves_from.as_ref()?;
+        let evolution = Card::Pokemon(pokemon);
+        if self.has_previous_stage(&evolution) {
+            return None;
+        }
+        if pokemon.stage == 2 && self.has_rare_candy() {
+            // Rare Candy skips the Stage 1, but the basic still has to be in the deck
+            let basic = CardId::iter()
+                .map(get_card_ref)
+                .find_map(|card| match card {
+                    Card::Pokemon(stage1) if stage1.name == *evolves_from => {
+                        stage1.evolves_from.clone()
+                    }
+                    _ => None,
+                });
+            return match basic {
+                Some(basic) if !self.cards.iter().any(|card| card.get_name() == basic) => {
+                    Some(BrokenEvolution {
+                        card: pokemon.name.clone(),
+                        missing: basic,
+                    })
+                }
+                _ => None,
+            };
+        }
+        Some(BrokenEvolution {
+            card: pokemon.name.clone(),
+            missing: evolves_from.clone(),
+        })
+    }
+
+    fn has_previous_stage(&self, evolution: &Card) -> bool {
+        let Card::Pokemon(pokemon) = evolution else {
+            return true;
+        };
+        self.cards.iter().any(|card| match card {
+            Card::Pokemon(_) => card.can_evolve_into(evolution),
+            // Fossils are played as the basic their evolution comes from
+            Card::Trainer(trainer) => pokemon.evolves_from.as_ref() == Some(&trainer.name),
+        })
+    }
+
+    fn has_rare_candy(&self) -> bool {
+        self.cards
+            .iter()
+            .any(|card| card.get_canonical_card_id() == Some(CardId::A3144RareCandy))
+    }
+}
+
+/// Prints the result of `Deck::analyze`, as the CLI `deck inspect` command does
+pub fn print_deck_analysis(analysis: &DeckAnalysis) {
+    warn!(
+        "{} cards: {} basic, {} Stage 1, {} Stage 2 Pokémon",
+        analysis.num_cards, analysis.basics, analysis.stage1, analysis.stage2
+    );
+    warn!(
+        "Trainers: {} supporters, {} items, {} tools, {} fossils, {} stadiums",
+        analysis.supporters, analysis.items, analysis.tools, analysis.fossils, analysis.stadiums
+    );
+    let retreat_costs: Vec<String> = analysis
+        .retreat_costs
+        .iter()
+        .map(|(cost, count)| format!("{cost}: {count}"))
+        .collect();
+    warn!("Retreat costs: {}", retreat_costs.join(", "));
+    let knockout_points: Vec<String> = analysis
+        .knockout_points
+        .iter()
+        .map(|(points, count)| format!("{count} worth {points}"))
+        .collect();
+    warn!("Knockout points given up: {}", knockout_points.join(", "));
+    let energy_types: Vec<&str> = analysis
+        .energy_types
+        .iter()
+        .map(|energy| energy.as_str())
+        .collect();
+    warn!("Energy types: {}", energy_types.join(", "));
+
+    if !analysis.has_problems() && analysis.unused_energy_types.is_empty() {
+        warn!("No problems found");
+        return;
+    }
+    warn!("Problems:");
+    for uncovered in &analysis.uncovered_attacks {
+        let missing: Vec<&str> = uncovered.missing.iter().map(|e| e.as_str()).collect();
+        warn!(
+            "\t{}'s {} needs {} energy, which the deck doesn't generate",
+            uncovered.pokemon,
+            uncovered.attack,
+            missing.join(", ")
+        );
+    }
+    for energy in &analysis.unused_energy_types {
+        warn!("\tNo attack needs the deck's {energy} energy");
+    }
+    for broken in &analysis.broken_evolutions {
+        warn!(
+            "\t{} evolves from {}, which is not in the deck",
+            broken.card, broken.missing
+        );
+    }
+    for issue in &analysis.issues {
+        warn!("\t{issue}");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_analyze_complete_deck() {
+        let deck = Deck::from_file("example_decks/venusaur-exeggutor.txt").unwrap();
+        let analysis = deck.analyze();
+        assert_eq!(analysis.num_cards, 20);
+        assert_eq!(
+            (analysis.basics, analysis.stage1, analysis.stage2),
+            (4, 4, 2)
+        );
+        assert_eq!(analysis.supporters + analysis.items, 10);
+        assert_eq!(analysis.retreat_costs.values().sum::<u32>(), 10);
+        assert_eq!(analysis.knockout_points.get(&2), Some(&4));
+        assert_eq!(analysis.energy_types, vec![EnergyType::Grass]);
+        assert!(analysis.uncovered_attacks.is_empty());
+        assert!(analysis.broken_evolutions.is_empty());
+    }
+
+    #[test]
+    fn test_analyze_finds_broken_lines_and_missing_energy() {
+        let deck = Deck::from_string(
+            "Energy: Fire\n2 Venusaur ex A1 004\n2 Ivysaur A1 002\n2 Exeggcute A1 021\n",
+        )
+        .unwrap();
+        let analysis = deck.analyze();
+        assert_eq!(
+            analysis.broken_evolutions,
+            vec![BrokenEvolution {
+                card: "Ivysaur".to_string(),
+                missing: "Bulbasaur".to_string(),
+            }]
+        );
+        assert!(analysis
+            .uncovered_attacks
+            .iter()
+            .all(|attack| attack.missing == vec![EnergyType::Grass]));
+        assert!(!analysis.uncovered_attacks.is_empty());
+        assert_eq!(analysis.unused_energy_types, vec![EnergyType::Fire]);
+        assert!(analysis.has_problems());
+
+        // With Rare Candy the Stage 2 only needs its basic
+        let deck = Deck::from_string("2 Venusaur ex A1 004\n1 Rare Candy A3 144\n").unwrap();
+        assert_eq!(
+            deck.analyze().broken_evolutions,
+            vec![BrokenEvolution {
+                card: "Venusaur ex".to_string(),
+                missing: "Bulbasaur".to_string(),
+            }]
+        );
+    }
+}
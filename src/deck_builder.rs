@@ -0,0 +1,682 @@
+use std::collections::{HashMap, HashSet};
+use std::fs;
+
+use log::warn;
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::{Rng, SeedableRng};
+use strum::IntoEnumIterator;
+
+use crate::{
+    card_equivalence::canonical_card_id,
+    card_ids::CardId,
+    card_validation::get_implementation_status,
+    database::{get_card_by_enum, get_card_ref},
+    format::Format,
+    gauntlet::{run_gauntlet, Meta},
+    inventory::Inventory,
+    models::{Card, EnergyType},
+    optimize::{try_parse_card_id_string, ParallelConfig, SimulationConfig},
+    statistics::{weighted_win_rate, MatchTally},
+    Deck,
+};
+
+const DECK_SIZE: usize = 20;
+const MAX_COPIES: usize = 2;
+/// Attempts at drawing a random card that fits before giving up on filling a deck
+const MAX_FILL_ATTEMPTS: usize = 500;
+
+/// Cards the deck builder may use, and the energy its decks run
+#[derive(Clone, Debug, Default)]
+pub struct CardPool {
+    pub cards: Vec<CardId>,
+    /// Energy of the built decks; when empty it's inferred from each deck's Pokémon
+    pub energy_types: Vec<EnergyType>,
+    /// When set, built decks only use owned cards and what the craft budget can buy
+    pub inventory: Option<Inventory>,
+    /// When set, built decks respect the format's legality, bans and copy limits
+    pub format: Option<Format>,
+}
+
+impl CardPool {
+    /// Every implemented Pokémon of the given types (plus Colorless ones) and every
+    /// implemented trainer. Reprints of the same card are only included once.
+    pub fn from_energy_types(energy_types: &[EnergyType]) -> Self {
+        let mut seen = HashSet::new();
+        let cards = CardId::iter()
+            .filter(|&id| {
+                let allowed = match get_card_ref(id) {
+                    Card::Pokemon(pokemon) => {
+                        pokemon.energy_type == EnergyType::Colorless
+                            || energy_types.contains(&pokemon.energy_type)
+                    }
+                    Card::Trainer(_) => true,
+                };
+                allowed
+                    && get_implementation_status(id).is_complete()
+                    && seen.insert(canonical_card_id(id))
+            })
+            .collect();
+        CardPool {
+            cards,
+            energy_types: energy_types.to_vec(),
+            inventory: None,
+            format: None,
+        }
+    }
+
+    /// Card ids separated by commas or newlines (e.g. "A1 1, A1 2"); `#` starts a comment
+    pub fn from_string(contents: &str) -> Result<Self, String> {
+        let mut cards = Vec::new();
+        for line in contents.lines() {
+            let line = line.split('#').next().unwrap_or_default();
+            for id in line.split(',').map(str::trim).filter(|id| !id.is_empty()) {
+                let card_id = try_parse_card_id_string(id)?;
+                if !cards.contains(&card_id) {
+                    cards.push(card_id);
+                }
+            }
+        }
+        if cards.is_empty() {
+            return Err("Card pool is empty".to_string());
+        }
+        Ok(CardPool {
+            cards,
+            energy_types: Vec::new(),
+            inventory: None,
+            format: None,
+        })
+    }
+
+    pub fn from_file(path: &str) -> Result<Self, String> {
+        let contents = fs::read_to_string(path)
+            .map_err(|err| format!("Failed to read card pool {path}: {err}"))?;
+        Self::from_string(&contents)
+    }
+
+    /// Restricts the pool to what the inventory can provide
+    pub fn with_inventory(mut self, inventory: Inventory) -> Self {
+        self.cards.retain(|&id| inventory.allows(&[id]));
+        self.inventory = Some(inventory);
+        self
+    }
+
+    /// Restricts the pool to the cards the format allows
+    pub fn with_format(mut self, format: Format) -> Self {
+        self.cards.retain(|&id| format.is_legal(get_card_ref(id)));
+        self.format = Some(format);
+        self
+    }
+}
+
+/// Parses a comma-separated list of energy types (e.g. "Grass,Fire", case-insensitive)
+pub fn parse_energy_types(s: &str) -> Result<Vec<EnergyType>, String> {
+    s.split(',')
+        .map(str::trim)
+        .filter(|name| !name.is_empty())
+        .map(parse_energy_type)
+        .collect()
+}
+
+/// Parses an energy type (e.g. "grass", case-insensitive)
+pub fn parse_energy_type(name: &str) -> Result<EnergyType, String> {
+    let mut chars = name.chars();
+    let capitalized: String = chars
+        .next()
+        .map(|first| {
+            first
+                .to_uppercase()
+                .chain(chars.flat_map(char::to_lowercase))
+        })
+        .into_iter()
+        .flatten()
+        .collect();
+    EnergyType::from_str(&capitalized).ok_or_else(|| format!("Invalid energy type: {name}"))
+}
+
+/// Whether every evolution (and fossil-based Pokémon) in `cards` has something in `cards`
+/// it can evolve from. `Deck::is_valid` doesn't check this, but decks that can't evolve
+/// their Pokémon are never worth simulating.
+pub fn has_coherent_evolutions(cards: &[Card]) -> bool {
+    cards.iter().all(|card| match card {
+        Card::Pokemon(pokemon) if pokemon.evolves_from.is_some() => {
+            cards.iter().any(|other| other.can_evolve_into(card))
+        }
+        _ => true,
+    })
+}
+
+/// Builds a playable deck out of card ids, using the pool's energy (or the Pokémon types)
+pub fn deck_from_cards(cards: &[CardId], energy_types: &[EnergyType]) -> Deck {
+    let cards: Vec<Card> = cards.iter().map(|&id| get_card_by_enum(id)).collect();
+    let mut energy_types = energy_types.to_vec();
+    if energy_types.is_empty() {
+        for card in &cards {
+            if let Card::Pokemon(pokemon) = card {
+                if pokemon.energy_type != EnergyType::Colorless
+                    && !energy_types.contains(&pokemon.energy_type)
+                {
+                    energy_types.push(pokemon.energy_type);
+                }
+            }
+        }
+    }
+    if energy_types.is_empty() {
+        energy_types.push(EnergyType::Colorless);
+    }
+    Deck {
+        visibility: vec![false; cards.len()],
+        cards,
+        energy_types,
+    }
+}
+
+/// Settings of the evolutionary search
+#[derive(Clone, Debug)]
+pub struct EvolutionConfig {
+    /// Decks alive in every generation
+    pub population_size: usize,
+    pub generations: usize,
+}
+
+impl Default for EvolutionConfig {
+    fn default() -> Self {
+        EvolutionConfig {
+            population_size: 16,
+            generations: 10,
+        }
+    }
+}
+
+/// A deck found by the builder with its results against the meta
+#[derive(Clone, Debug)]
+pub struct BuiltDeck {
+    pub cards: Vec<CardId>,
+    pub deck: Deck,
+    /// Results against every meta entry, accumulated over all generations the deck survived
+    pub tallies: Vec<MatchTally>,
+    pub weighted_win_rate: f64,
+    /// Win rate of the worst matchup with a non-zero share
+    pub worst_matchup_win_rate: f64,
+}
+
+impl BuiltDeck {
+    pub fn games(&self) -> u32 {
+        self.tallies.iter().map(MatchTally::games).sum()
+    }
+
+    fn dominates(&self, other: &BuiltDeck) -> bool {
+        self.weighted_win_rate >= other.weighted_win_rate
+            && self.worst_matchup_win_rate >= other.worst_matchup_win_rate
+            && (self.weighted_win_rate > other.weighted_win_rate
+                || self.worst_matchup_win_rate > other.worst_matchup_win_rate)
+    }
+}
+
+/// Decks that no other deck beats on both weighted win rate and worst matchup,
+/// sorted by weighted win rate
+pub fn pareto_front(decks: &[BuiltDeck]) -> Vec<BuiltDeck> {
+    let mut front: Vec<BuiltDeck> = decks
+        .iter()
+        .filter(|deck| !decks.iter().any(|other| other.dominates(deck)))
+        .cloned()
+        .collect();
+    front.sort_by(|a, b| b.weighted_win_rate.total_cmp(&a.weighted_win_rate));
+    front
+}
+
+/// Builds complete decks out of `pool` with a genetic search whose fitness is the simulated
+/// win rate against `meta`.
+///
+/// Each generation every deck plays `sim_config.num_games` games against the meta (added to
+/// what it already played), the Pareto-best decks and the best half by weighted win rate
+/// survive, and the rest of the population is refilled with crossovers and mutations of the
+/// survivors. Mutations swap cards while keeping decks valid and their evolution lines
+/// complete. Returns the final Pareto front (weighted win rate vs worst matchup).
+pub fn build_decks(
+    pool: &CardPool,
+    meta: &Meta,
+    sim_config: &SimulationConfig,
+    evolution: &EvolutionConfig,
+    parallel_config: &ParallelConfig,
+) -> Result<Vec<BuiltDeck>, String> {
+    let mut rng = StdRng::seed_from_u64(sim_config.seed.unwrap_or_else(rand::random));
+    let mut builder = Builder {
+        pool,
+        rng: &mut rng,
+    };
+    let shares = meta.shares();
+
+    let mut population: Vec<Vec<CardId>> = Vec::new();
+    for _ in 0..evolution.population_size * 10 {
+        if population.len() >= evolution.population_size {
+            break;
+        }
+        if let Some(cards) = builder.random_deck() {
+            if !population.contains(&cards) {
+                population.push(cards);
+            }
+        }
+    }
+    if population.is_empty() {
+        return Err("Could not build any valid deck out of the card pool".to_string());
+    }
+
+    let mut results: HashMap<Vec<CardId>, Vec<MatchTally>> = HashMap::new();
+    for generation in 0..evolution.generations.max(1) {
+        // A different seed every generation, shared by every deck of the generation
+        let generation_config = SimulationConfig {
+            num_games: sim_config.num_games,
+            players: sim_config.players.clone(),
+            seed: sim_config
+                .seed
+                .map(|seed| seed.wrapping_add(generation as u64 * 1_000_003)),
+        };
+        for cards in &population {
+            let deck = deck_from_cards(cards, &pool.energy_types);
+            let report = run_gauntlet(
+                &deck,
+                meta,
+                &generation_config,
+                parallel_config,
+                None::<fn()>,
+            );
+            let tallies = results
+                .entry(cards.clone())
+                .or_insert_with(|| vec![MatchTally::default(); meta.entries.len()]);
+            for (tally, matchup) in tallies.iter_mut().zip(&report.matchups) {
+                tally.merge(&matchup.tally);
+            }
+        }
+
+        let mut evaluated: Vec<BuiltDeck> = population
+            .iter()
+            .map(|cards| built_deck(cards, &results[cards], &shares, pool))
+            .collect();
+        evaluated.sort_by(|a, b| b.weighted_win_rate.total_cmp(&a.weighted_win_rate));
+        let front = pareto_front(&evaluated);
+        warn!(
+            "Generation {}: best {:.2}% weighted win rate, {} deck(s) on the Pareto front",
+            generation + 1,
+            evaluated[0].weighted_win_rate * 100.0,
+            front.len()
+        );
+        if generation + 1 == evolution.generations.max(1) {
+            return Ok(front);
+        }
+
+        let survivor_count = (evolution.population_size / 2).max(1);
+        let mut survivors: Vec<Vec<CardId>> = front
+            .iter()
+            .take(survivor_count)
+            .map(|deck| deck.cards.clone())
+            .collect();
+        for deck in &evaluated {
+            if survivors.len() >= survivor_count {
+                break;
+            }
+            if !survivors.contains(&deck.cards) {
+                survivors.push(deck.cards.clone());
+            }
+        }
+
+        population = survivors.clone();
+        for _ in 0..evolution.population_size * 10 {
+            if population.len() >= evolution.population_size {
+                break;
+            }
+            let parent_a = builder.pick(&survivors).clone();
+            let child = if builder.rng.gen_bool(0.5) {
+                let parent_b = builder.pick(&survivors).clone();
+                builder.crossover(&parent_a, &parent_b)
+            } else {
+                Some(parent_a)
+            }
+            .and_then(|child| builder.mutate(&child));
+            if let Some(child) = child {
+                if !population.contains(&child) {
+                    population.push(child);
+                }
+            }
+        }
+    }
+    unreachable!("the last generation returns the Pareto front")
+}
+
+fn built_deck(
+    cards: &[CardId],
+    tallies: &[MatchTally],
+    shares: &[f64],
+    pool: &CardPool,
+) -> BuiltDeck {
+    let items: Vec<(f64, MatchTally)> = shares
+        .iter()
+        .copied()
+        .zip(tallies.iter().copied())
+        .collect();
+    let (weighted, _) = weighted_win_rate(&items);
+    let worst = items
+        .iter()
+        .filter(|(share, tally)| *share > 0.0 && tally.games() > 0)
+        .map(|(_, tally)| tally.win_rate())
+        .fold(f64::INFINITY, f64::min);
+    BuiltDeck {
+        cards: cards.to_vec(),
+        deck: deck_from_cards(cards, &pool.energy_types),
+        tallies: tallies.to_vec(),
+        weighted_win_rate: weighted,
+        worst_matchup_win_rate: if worst.is_finite() { worst } else { 0.0 },
+    }
+}
+
+/// Random deck generation and variation operators over a card pool
+struct Builder<'a> {
+    pool: &'a CardPool,
+    rng: &'a mut StdRng,
+}
+
+impl Builder<'_> {
+    fn pick<'b, T>(&mut self, items: &'b [T]) -> &'b T {
+        items.choose(self.rng).expect("items should not be empty")
+    }
+
+    /// A random legal deck: a basic to start with, then random pool cards (with whatever
+    /// they need to evolve from) until the deck is full
+    fn random_deck(&mut self) -> Option<Vec<CardId>> {
+        let basics: Vec<CardId> = self
+            .pool
+            .cards
+            .iter()
+            .copied()
+            .filter(|&id| get_card_ref(id).is_basic())
+            .collect();
+        let first = *basics.choose(self.rng)?;
+        let mut cards = vec![first];
+        self.fill(&mut cards);
+        finish(cards)
+    }
+
+    /// Removes one to three random cards (and evolutions left without a pre-evolution), then refills
+    fn mutate(&mut self, parent: &[CardId]) -> Option<Vec<CardId>> {
+        let mut cards = parent.to_vec();
+        for _ in 0..self.rng.gen_range(1..=3) {
+            let index = self.rng.gen_range(0..cards.len());
+            cards.remove(index);
+        }
+        drop_orphaned_evolutions(&mut cards);
+        if !cards.iter().any(|&id| get_card_ref(id).is_basic()) {
+            return None;
+        }
+        self.fill(&mut cards);
+        finish(cards)
+    }
+
+    /// A child made of cards from both parents, refilled from the pool if needed
+    fn crossover(&mut self, parent_a: &[CardId], parent_b: &[CardId]) -> Option<Vec<CardId>> {
+        let mut genes: Vec<CardId> = parent_a.iter().chain(parent_b).copied().collect();
+        genes.shuffle(self.rng);
+        let mut cards: Vec<CardId> = Vec::new();
+        if let Some(&basic) = genes.iter().find(|&&id| get_card_ref(id).is_basic()) {
+            cards.push(basic);
+        }
+        for gene in genes {
+            if cards.len() >= DECK_SIZE {
+                break;
+            }
+            self.add_with_prerequisites(&mut cards, gene);
+        }
+        self.fill(&mut cards);
+        finish(cards)
+    }
+
+    fn fill(&mut self, cards: &mut Vec<CardId>) {
+        for _ in 0..MAX_FILL_ATTEMPTS {
+            if cards.len() >= DECK_SIZE {
+                break;
+            }
+            let pool = self.pool;
+            let candidate = *self.pick(&pool.cards);
+            self.add_with_prerequisites(cards, candidate);
+        }
+    }
+
+    /// Adds `card` together with any missing pre-evolutions (taken from the pool),
+    /// if they all fit in the deck and respect the copy limit
+    fn add_with_prerequisites(&mut self, cards: &mut Vec<CardId>, card: CardId) -> bool {
+        let mut chain = vec![card];
+        loop {
+            let current = get_card_ref(*chain.last().unwrap());
+            let Card::Pokemon(pokemon) = current else {
+                break;
+            };
+            if pokemon.evolves_from.is_none()
+                || cards
+                    .iter()
+                    .chain(&chain)
+                    .any(|&id| get_card_ref(id).can_evolve_into(current))
+            {
+                break;
+            }
+            let options: Vec<CardId> = self
+                .pool
+                .cards
+                .iter()
+                .copied()
+                .filter(|&id| get_card_ref(id).can_evolve_into(current))
+                .collect();
+            match options.choose(self.rng) {
+                Some(&pre_evolution) => chain.push(pre_evolution),
+                None => return false,
+            }
+        }
+
+        if cards.len() + chain.len() > DECK_SIZE {
+            return false;
+        }
+        let mut name_counts: HashMap<String, usize> = HashMap::new();
+        for &id in cards.iter().chain(&chain) {
+            let count = name_counts.entry(get_card_ref(id).get_name()).or_default();
+            *count += 1;
+            if *count > MAX_COPIES {
+                return false;
+            }
+        }
+        let deck: Vec<CardId> = cards.iter().chain(&chain).copied().collect();
+        if let Some(inventory) = &self.pool.inventory {
+            if !inventory.allows(&deck) {
+                return false;
+            }
+        }
+        if let Some(format) = &self.pool.format {
+            if !format.allows(&deck) {
+                return false;
+            }
+        }
+        cards.extend(chain);
+        true
+    }
+}
+
+fn drop_orphaned_evolutions(cards: &mut Vec<CardId>) {
+    loop {
+        let deck_cards: Vec<Card> = cards.iter().map(|&id| get_card_by_enum(id)).collect();
+        let orphan = deck_cards.iter().position(|card| {
+            matches!(card, Card::Pokemon(pokemon) if pokemon.evolves_from.is_some())
+                && !deck_cards.iter().any(|other| other.can_evolve_into(card))
+        });
+        match orphan {
+            Some(index) => {
+                cards.remove(index);
+            }
+            None => break,
+        }
+    }
+}
+
+/// Sorts a finished deck into its canonical order, or rejects it if it isn't legal
+fn finish(mut cards: Vec<CardId>) -> Option<Vec<CardId>> {
+    cards.sort_by_key(|&id| id as usize);
+    let deck_cards: Vec<Card> = cards.iter().map(|&id| get_card_by_enum(id)).collect();
+    let deck = deck_from_cards(&cards, &[]);
+    (deck.is_valid() && has_coherent_evolutions(&deck_cards)).then_some(cards)
+}
+
+/// Runs the deck builder and writes the Pareto-best decks to `output_folder`, as the CLI does
+pub fn cli_build_decks(
+    pool: &CardPool,
+    meta: &Meta,
+    sim_config: SimulationConfig,
+    evolution: EvolutionConfig,
+    parallel_config: ParallelConfig,
+    output_folder: &str,
+) {
+    warn!(
+        "Building decks from {} cards against {} meta decks ({} generations of {} decks, {} games each)",
+        pool.cards.len(),
+        meta.entries.len(),
+        evolution.generations,
+        evolution.population_size,
+        sim_config.num_games
+    );
+    let front = match build_decks(pool, meta, &sim_config, &evolution, &parallel_config) {
+        Ok(front) => front,
+        Err(err) => {
+            warn!("{err}");
+            return;
+        }
+    };
+    if let Err(err) = fs::create_dir_all(output_folder) {
+        warn!("Failed to create {output_folder}: {err}");
+        return;
+    }
+    for (i, built) in front.iter().enumerate() {
+        let path = format!("{output_folder}/deck_{}.txt", i + 1);
+        match fs::write(&path, built.deck.to_deckgym_string()) {
+            Ok(()) => warn!(
+                "{path}: {:.2}% weighted win rate, {:.2}% worst matchup ({} games)",
+                built.weighted_win_rate * 100.0,
+                built.worst_matchup_win_rate * 100.0,
+                built.games()
+            ),
+            Err(err) => warn!("Failed to write {path}: {err}"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::players::PlayerCode;
+
+    #[test]
+    fn test_has_coherent_evolutions() {
+        let deck = Deck::from_string("Energy: Grass\n2 Bulbasaur A1 1\n2 Ivysaur A1 2").unwrap();
+        assert!(has_coherent_evolutions(&deck.cards));
+        let deck = Deck::from_string("Energy: Grass\n2 Bulbasaur A1 1\n2 Venusaur A1 3").unwrap();
+        assert!(!has_coherent_evolutions(&deck.cards));
+    }
+
+    #[test]
+    fn test_deck_to_text_round_trips() {
+        let deck = Deck::from_file("example_decks/venusaur-exeggutor.txt").unwrap();
+        let parsed = Deck::from_string(&deck.to_deckgym_string()).unwrap();
+        assert_eq!(parsed.cards, deck.cards);
+        assert_eq!(parsed.energy_types, deck.energy_types);
+    }
+
+    #[test]
+    fn test_card_pool_from_string() {
+        let pool = CardPool::from_string("A1 1, A1 2\n# comment\nA1 1\nP-A 5").unwrap();
+        assert_eq!(pool.cards.len(), 3);
+        assert!(CardPool::from_string("# nothing").is_err());
+        assert_eq!(
+            parse_energy_types("grass, FIRE").unwrap(),
+            vec![EnergyType::Grass, EnergyType::Fire]
+        );
+        assert!(parse_energy_types("Grss").is_err());
+    }
+
+    #[test]
+    fn test_build_decks_returns_valid_decks() {
+        let pool = CardPool::from_energy_types(&[EnergyType::Grass]);
+        let meta = Meta {
+            entries: vec![crate::gauntlet::MetaEntry {
+                name: "weezing-arbok".to_string(),
+                deck: Deck::from_file("example_decks/weezing-arbok.txt").unwrap(),
+                weight: 1.0,
+            }],
+        };
+        let sim_config = SimulationConfig {
+            num_games: 2,
+            players: Some(vec![PlayerCode::R, PlayerCode::R]),
+            seed: Some(1),
+        };
+        let evolution = EvolutionConfig {
+            population_size: 4,
+            generations: 2,
+        };
+
+        let front = build_decks(
+            &pool,
+            &meta,
+            &sim_config,
+            &evolution,
+            &ParallelConfig::default(),
+        )
+        .unwrap();
+        assert!(!front.is_empty());
+        for built in &front {
+            assert!(built.deck.is_valid());
+            assert!(has_coherent_evolutions(&built.deck.cards));
+            assert_eq!(built.deck.energy_types, vec![EnergyType::Grass]);
+            assert!(built.games() >= 2);
+        }
+    }
+
+    #[test]
+    fn test_build_decks_only_uses_owned_cards() {
+        // Owning exactly one deck's cards, with nothing to craft, leaves one buildable deck
+        let inventory = Inventory::from_file("example_decks/venusaur-exeggutor.txt").unwrap();
+        let pool = CardPool::from_energy_types(&[EnergyType::Grass]).with_inventory(inventory);
+        let meta = Meta {
+            entries: vec![crate::gauntlet::MetaEntry {
+                name: "weezing-arbok".to_string(),
+                deck: Deck::from_file("example_decks/weezing-arbok.txt").unwrap(),
+                weight: 1.0,
+            }],
+        };
+        let sim_config = SimulationConfig {
+            num_games: 2,
+            players: Some(vec![PlayerCode::R, PlayerCode::R]),
+            seed: Some(3),
+        };
+        let evolution = EvolutionConfig {
+            population_size: 2,
+            generations: 1,
+        };
+
+        let front = build_decks(
+            &pool,
+            &meta,
+            &sim_config,
+            &evolution,
+            &ParallelConfig::default(),
+        )
+        .unwrap();
+        let inventory = pool.inventory.as_ref().unwrap();
+        for built in &front {
+            assert!(built.deck.is_valid());
+            assert!(inventory.allows(&built.cards));
+        }
+    }
+
+    #[test]
+    fn test_card_pool_with_format() {
+        let format = Format::from_string("Sets: A1\nBanned: A1 001\nLimit: A1 225, 1").unwrap();
+        let pool = CardPool::from_energy_types(&[EnergyType::Grass]).with_format(format);
+        assert!(!pool.cards.is_empty());
+        assert!(!pool.cards.contains(&CardId::A1001Bulbasaur));
+        assert!(pool.cards.contains(&CardId::A1005Caterpie));
+        assert!(!pool.cards.contains(&CardId::B1001Pinsir));
+    }
+}
@@ -0,0 +1,200 @@
+use strum::IntoEnumIterator;
+
+use crate::{card_ids::CardId, database::get_card_by_enum, models::EnergyType, Deck};
+
+/// Bumped whenever the layout or the `CardId` order changes, so old codes fail loudly
+const VERSION: u8 = 1;
+
+/// Bit of each energy type in the code's energy mask
+const ENERGY_TYPES: [EnergyType; 10] = [
+    EnergyType::Grass,
+    EnergyType::Fire,
+    EnergyType::Water,
+    EnergyType::Lightning,
+    EnergyType::Psychic,
+    EnergyType::Fighting,
+    EnergyType::Darkness,
+    EnergyType::Metal,
+    EnergyType::Dragon,
+    EnergyType::Colorless,
+];
+
+/// Cards are stored as 14-bit `CardId` indices with the count (1-4) in the top 2 bits
+const INDEX_BITS: u16 = 14;
+const MAX_COPIES: u16 = 4;
+
+const BASE64_URL: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+
+impl Deck {
+    /// A short, URL-safe code for sharing the deck (e.g. in chat), decoded by `from_code`.
+    ///
+    /// Layout before base64url: version byte, 2-byte energy mask, then one little-endian
+    /// `u16` per distinct card holding its `CardId` index and count, so decks with cards loaded
+    /// at runtime can't be encoded.
+    pub fn to_code(&self) -> Result<String, String> {
+        let card_ids = self
+            .card_ids()
+            .map_err(|err| format!("Can't encode the deck: {err}"))?;
+        let mut bytes = vec![VERSION];
+        let mask = ENERGY_TYPES
+            .iter()
+            .enumerate()
+            .filter(|(_, energy)| self.energy_types.contains(energy))
+            .fold(0u16, |mask, (bit, _)| mask | 1 << bit);
+        bytes.extend(mask.to_le_bytes());
+
+        let mut counts: Vec<(CardId, u16)> = Vec::new();
+        for id in card_ids {
+            match counts
+                .iter_mut()
+                .find(|(other, count)| *other == id && *count < MAX_COPIES)
+            {
+                Some((_, count)) => *count += 1,
+                None => counts.push((id, 1)),
+            }
+        }
+        for (id, count) in counts {
+            let value = (count - 1) << INDEX_BITS | id as u16;
+            bytes.extend(value.to_le_bytes());
+        }
+        Ok(encode_base64_url(&bytes))
+    }
+
+    /// Decodes a code made by `to_code`
+    pub fn from_code(code: &str) -> Result<Self, String> {
+        let invalid = |reason: &str| format!("Invalid deck code: {reason}");
+        let bytes = decode_base64_url(code.trim()).ok_or_else(|| invalid("not base64url"))?;
+        if bytes.len() < 3 || bytes.len() % 2 == 0 {
+            return Err(invalid("wrong length"));
+        }
+        if bytes[0] != VERSION {
+            return Err(invalid(&format!("unsupported version {}", bytes[0])));
+        }
+
+        let mask = u16::from_le_bytes([bytes[1], bytes[2]]);
+        let energy_types = ENERGY_TYPES
+            .iter()
+            .enumerate()
+            .filter(|(bit, _)| mask & 1 << bit != 0)
+            .map(|(_, &energy)| energy)
+            .collect();
+
+        let mut cards = Vec::new();
+        for pair in bytes[3..].chunks(2) {
+            let value = u16::from_le_bytes([pair[0], pair[1]]);
+            let index = value & ((1 << INDEX_BITS) - 1);
+            let count = (value >> INDEX_BITS) + 1;
+            let id = CardId::iter()
+                .nth(index as usize)
+                .ok_or_else(|| invalid(&format!("unknown card index {index}")))?;
+            let card = get_card_by_enum(id);
+            cards.extend(std::iter::repeat_n(card, count as usize));
+        }
+
+        Ok(Deck {
+            visibility: vec![false; cards.len()],
+            cards,
+            energy_types,
+        })
+    }
+}
+
+fn encode_base64_url(bytes: &[u8]) -> String {
+    let mut encoded = String::new();
+    for chunk in bytes.chunks(3) {
+        let mut buffer = [0u8; 3];
+        buffer[..chunk.len()].copy_from_slice(chunk);
+        let value = u32::from_be_bytes([0, buffer[0], buffer[1], buffer[2]]);
+        for i in 0..=chunk.len() {
+            let sextet = (value >> (18 - 6 * i)) & 0x3f;
+            encoded.push(BASE64_URL[sextet as usize] as char);
+        }
+    }
+    encoded
+}
+
+fn decode_base64_url(code: &str) -> Option<Vec<u8>> {
+    let mut bytes = Vec::new();
+    for chunk in code.as_bytes().chunks(4) {
+        if chunk.len() == 1 {
+            return None;
+        }
+        let mut value = 0u32;
+        for (i, &c) in chunk.iter().enumerate() {
+            let sextet = BASE64_URL.iter().position(|&b| b == c)? as u32;
+            value |= sextet << (18 - 6 * i);
+        }
+        bytes.extend(&value.to_be_bytes()[1..chunk.len()]);
+    }
+    Some(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{Card, PokemonCard};
+
+    #[test]
+    fn test_base64_url_round_trips() {
+        for len in 0..8 {
+            let bytes: Vec<u8> = (0..len).map(|i| (i * 97 + 200) as u8).collect();
+            let encoded = encode_base64_url(&bytes);
+            assert!(!encoded.contains(['+', '/', '=']));
+            assert_eq!(decode_base64_url(&encoded), Some(bytes));
+        }
+        assert_eq!(decode_base64_url("a"), None);
+        assert_eq!(decode_base64_url("ab!c"), None);
+    }
+
+    #[test]
+    fn test_deck_code_round_trips() {
+        for path in [
+            "example_decks/venusaur-exeggutor.txt",
+            "example_decks/weezing-arbok.txt",
+        ] {
+            let deck = Deck::from_file(path).unwrap();
+            let code = deck.to_code().unwrap();
+            assert!(code.len() < 40, "{code}");
+            let decoded = Deck::from_code(&code).unwrap();
+            assert_eq!(decoded.cards, deck.cards);
+            assert_eq!(decoded.energy_types, deck.energy_types);
+        }
+        assert!(Deck::from_code("not a code").is_err());
+    }
+
+    #[test]
+    fn test_deck_codes_keep_their_meaning() {
+        // Codes store `CardId` indices, so these break if the enum order changes. Bump `VERSION`
+        // (and update the codes) rather than letting shared codes decode to other cards.
+        for (path, code) in [
+            (
+                "example_decks/venusaur-exeggutor.txt",
+                "AQEAAEAUQBZAAUADQM1Ky0raQOAAyErMCg",
+            ),
+            (
+                "example_decks/altaria.txt",
+                "ARAAA0YNSBEIFEggCGwI4AAHQgkCDgIRA6FHiAiJSA",
+            ),
+        ] {
+            let deck = Deck::from_file(path).unwrap();
+            let decoded = Deck::from_code(code).unwrap();
+            assert_eq!(decoded.card_ids(), deck.card_ids(), "{path}");
+            assert_eq!(decoded.energy_types, deck.energy_types, "{path}");
+            assert_eq!(deck.to_code().unwrap(), code, "{path}");
+        }
+    }
+
+    #[test]
+    fn test_runtime_cards_cant_be_encoded() {
+        let mut deck = Deck::from_file("example_decks/venusaur-exeggutor.txt").unwrap();
+        let Card::Pokemon(bulbasaur) = deck.cards[0] else {
+            panic!("the deck starts with Bulbasaur");
+        };
+        deck.cards[0] = Card::from(PokemonCard {
+            id: "Z7 001".to_string(),
+            ..bulbasaur.clone()
+        });
+        let err = deck.to_code().unwrap_err();
+        assert!(err.contains("Z7 001"), "{err}");
+    }
+}
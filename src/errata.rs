@@ -0,0 +1,395 @@
+use std::collections::{HashMap, HashSet};
+use std::fs;
+
+use crate::{
+    attack_ids::AttackId,
+    card_equivalence::{canonical_card_id, equivalent_prints},
+    card_ids::CardId,
+    database::get_card_ref,
+    deck_builder::parse_energy_type,
+    models::{Attack, Card, EnergyType, PokemonCard},
+    optimize::try_parse_card_id_string,
+    Deck,
+};
+
+/// What-if changes to card stats (e.g. "Mewtwo ex with 20 less HP"), applied to decks before
+/// simulating them, so a balance patch can be tried without touching `database.rs`.
+///
+/// Changes apply to every print of a card. Errata files have one change per line; `#` starts a
+/// comment. A `+`/`-` number changes a value, a plain number replaces it:
+/// - `Mewtwo ex: hp -20`
+/// - `A1 129: retreat 1`
+/// - `Mewtwo ex: weakness Darkness` (or `none`)
+/// - `Mewtwo ex: attack Psydrive damage +20`
+/// - `Mewtwo ex: attack Psydrive cost -1` (dropping a Colorless first), or `cost Psychic, Colorless`
+///
+/// Attacks implemented per card (see `AttackId`) have their damage written in the code, so only
+/// their cost can be changed.
+#[derive(Debug, Clone, Default)]
+pub struct Errata {
+    /// In file order, by canonical print
+    changes: Vec<(CardId, CardChange)>,
+    /// Every changed print, by id (e.g. "A1 129")
+    patched: HashMap<String, Card>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CardChange {
+    Hp(Amount),
+    RetreatCost(Amount),
+    Weakness(Option<EnergyType>),
+    AttackDamage { attack: String, damage: Amount },
+    AttackCost { attack: String, cost: Cost },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Amount {
+    Set(u32),
+    Add(i32),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Cost {
+    Set(Vec<EnergyType>),
+    /// Adds Colorless energy, or removes energy (Colorless first) when negative
+    Add(i32),
+}
+
+impl Errata {
+    pub fn from_file(path: &str) -> Result<Self, String> {
+        let contents = fs::read_to_string(path)
+            .map_err(|err| format!("Failed to read errata {path}: {err}"))?;
+        Self::from_string(&contents)
+    }
+
+    pub fn from_string(contents: &str) -> Result<Self, String> {
+        let mut errata = Errata::default();
+        for line in contents.lines() {
+            let line = line.split('#').next().unwrap_or_default().trim();
+            if line.is_empty() {
+                continue;
+            }
+            // Some names have a colon (e.g. "Type: Null"), changes never do
+            let (card, change) = line
+                .rsplit_once(':')
+                .ok_or_else(|| format!("Invalid errata line: {line}"))?;
+            let card_id = canonical_card_id(try_parse_card_id_string(card.trim())?);
+            let change = CardChange::parse(change.trim())
+                .map_err(|err| format!("{err} in errata line: {line}"))?;
+            errata.changes.push((card_id, change));
+        }
+        errata.patch_prints()?;
+        Ok(errata)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+
+    /// The card with the errata applied (the card itself when none apply to it)
+    pub fn patch(&self, card: Card) -> Card {
+        self.patched.get(&card.get_id()).copied().unwrap_or(card)
+    }
+
+    pub fn apply(&self, deck: &mut Deck) {
+        for card in deck.cards.iter_mut() {
+            *card = self.patch(*card);
+        }
+    }
+
+    /// One line per changed stat, e.g. "Mewtwo ex (A1 129): HP 150 -> 130"
+    pub fn describe(&self) -> Vec<String> {
+        let mut card_ids: Vec<CardId> = self.changes.iter().map(|&(id, _)| id).collect();
+        let mut seen = HashSet::new();
+        card_ids.retain(|&id| seen.insert(id));
+        let mut lines = Vec::new();
+        for card_id in card_ids {
+            let before = get_card_ref(card_id);
+            let (Card::Pokemon(before), Card::Pokemon(after)) = (before, self.patch(*before))
+            else {
+                continue;
+            };
+            let card = format!("{} ({})", before.name, before.id);
+            let mut describe = |stat: &str, old: String, new: String| {
+                if old != new {
+                    lines.push(format!("{card}: {stat} {old} -> {new}"));
+                }
+            };
+            describe("HP", before.hp.to_string(), after.hp.to_string());
+            describe(
+                "retreat cost",
+                before.retreat_cost.len().to_string(),
+                after.retreat_cost.len().to_string(),
+            );
+            describe(
+                "weakness",
+                weakness_text(before.weakness),
+                weakness_text(after.weakness),
+            );
+            for (old, new) in before.attacks.iter().zip(&after.attacks) {
+                describe(
+                    &format!("{} damage", old.title),
+                    old.fixed_damage.to_string(),
+                    new.fixed_damage.to_string(),
+                );
+                describe(
+                    &format!("{} cost", old.title),
+                    cost_text(&old.energy_required),
+                    cost_text(&new.energy_required),
+                );
+            }
+        }
+        lines
+    }
+
+    /// Builds the patched version of every print of the changed cards
+    fn patch_prints(&mut self) -> Result<(), String> {
+        for &(card_id, _) in &self.changes {
+            for print in equivalent_prints(card_id) {
+                let Card::Pokemon(pokemon) = get_card_ref(print) else {
+                    return Err(format!(
+                        "{} is not a Pokémon, errata only change Pokémon stats",
+                        get_card_ref(print).get_name()
+                    ));
+                };
+                if self.patched.contains_key(&pokemon.id) {
+                    continue;
+                }
+                let mut patched = (*pokemon).clone();
+                for (_, change) in self.changes.iter().filter(|(id, _)| *id == card_id) {
+                    change.apply(&mut patched)?;
+                }
+                self.patched.insert(patched.id.clone(), Card::from(patched));
+            }
+        }
+        Ok(())
+    }
+}
+
+impl CardChange {
+    /// Parses the part of an errata line after the card, e.g. "attack Psydrive cost -1"
+    fn parse(text: &str) -> Result<Self, String> {
+        let (stat, value) = text
+            .split_once(char::is_whitespace)
+            .ok_or("Missing value")?;
+        let value = value.trim();
+        match stat.to_lowercase().as_str() {
+            "hp" => Ok(CardChange::Hp(Amount::parse(value)?)),
+            "retreat" => Ok(CardChange::RetreatCost(Amount::parse(value)?)),
+            "weakness" if value.eq_ignore_ascii_case("none") => Ok(CardChange::Weakness(None)),
+            "weakness" => Ok(CardChange::Weakness(Some(parse_energy_type(value)?))),
+            "attack" => {
+                let words: Vec<&str> = value.split_whitespace().collect();
+                let split = words
+                    .iter()
+                    .rposition(|word| {
+                        word.eq_ignore_ascii_case("damage") || word.eq_ignore_ascii_case("cost")
+                    })
+                    .filter(|&split| split > 0)
+                    .ok_or(
+                        "Expected `attack <name> damage <value>` or `attack <name> cost <value>`",
+                    )?;
+                let attack = words[..split].join(" ");
+                let value = words[split + 1..].join(" ");
+                if words[split].eq_ignore_ascii_case("damage") {
+                    Ok(CardChange::AttackDamage {
+                        attack,
+                        damage: Amount::parse(&value)?,
+                    })
+                } else {
+                    Ok(CardChange::AttackCost {
+                        attack,
+                        cost: Cost::parse(&value)?,
+                    })
+                }
+            }
+            _ => Err(format!("Unknown stat '{stat}'")),
+        }
+    }
+
+    fn apply(&self, pokemon: &mut PokemonCard) -> Result<(), String> {
+        match self {
+            CardChange::Hp(amount) => {
+                pokemon.hp = amount.apply(pokemon.hp);
+                if pokemon.hp == 0 {
+                    return Err(format!("{} would have no HP left", pokemon.name));
+                }
+            }
+            CardChange::RetreatCost(amount) => {
+                let retreat_cost = amount.apply(pokemon.retreat_cost.len() as u32);
+                pokemon.retreat_cost = vec![EnergyType::Colorless; retreat_cost as usize];
+            }
+            CardChange::Weakness(weakness) => pokemon.weakness = *weakness,
+            CardChange::AttackDamage { attack, damage } => {
+                let id = pokemon.id.clone();
+                let index = pokemon
+                    .attacks
+                    .iter()
+                    .position(|other| other.title.eq_ignore_ascii_case(attack));
+                // These attacks are implemented per card, with their damage written in the code
+                if index.is_some_and(|index| AttackId::from_pokemon_index(&id, index).is_some()) {
+                    return Err(format!(
+                        "The damage of {}'s {attack} can't be changed",
+                        pokemon.name
+                    ));
+                }
+                let attack = find_attack(pokemon, attack)?;
+                attack.fixed_damage = damage.apply(attack.fixed_damage);
+            }
+            CardChange::AttackCost { attack, cost } => {
+                cost.apply(&mut find_attack(pokemon, attack)?.energy_required);
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Amount {
+    fn parse(value: &str) -> Result<Self, String> {
+        let invalid = || format!("Invalid value '{value}'");
+        if value.starts_with(['+', '-']) {
+            value.parse().map(Amount::Add).map_err(|_| invalid())
+        } else {
+            value.parse().map(Amount::Set).map_err(|_| invalid())
+        }
+    }
+
+    fn apply(self, value: u32) -> u32 {
+        match self {
+            Amount::Set(new) => new,
+            Amount::Add(change) => value.saturating_add_signed(change),
+        }
+    }
+}
+
+impl Cost {
+    fn parse(value: &str) -> Result<Self, String> {
+        if value.starts_with(['+', '-']) {
+            return value
+                .parse()
+                .map(Cost::Add)
+                .map_err(|_| format!("Invalid value '{value}'"));
+        }
+        if value.eq_ignore_ascii_case("none") {
+            return Ok(Cost::Set(Vec::new()));
+        }
+        value
+            .split(',')
+            .map(|energy| parse_energy_type(energy.trim()))
+            .collect::<Result<_, _>>()
+            .map(Cost::Set)
+    }
+
+    fn apply(&self, cost: &mut Vec<EnergyType>) {
+        match *self {
+            Cost::Set(ref energy) => *cost = energy.clone(),
+            Cost::Add(change) if change >= 0 => {
+                cost.extend(std::iter::repeat_n(EnergyType::Colorless, change as usize))
+            }
+            Cost::Add(change) => {
+                for _ in 0..change.unsigned_abs() {
+                    let dropped = cost
+                        .iter()
+                        .rposition(|&energy| energy == EnergyType::Colorless)
+                        .or(cost.len().checked_sub(1));
+                    if let Some(index) = dropped {
+                        cost.remove(index);
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn find_attack<'a>(pokemon: &'a mut PokemonCard, title: &str) -> Result<&'a mut Attack, String> {
+    let name = pokemon.name.clone();
+    pokemon
+        .attacks
+        .iter_mut()
+        .find(|attack| attack.title.eq_ignore_ascii_case(title))
+        .ok_or_else(|| format!("{name} has no attack named {title}"))
+}
+
+fn weakness_text(weakness: Option<EnergyType>) -> String {
+    weakness.map_or("none".to_string(), |energy| energy.to_string())
+}
+
+fn cost_text(cost: &[EnergyType]) -> String {
+    if cost.is_empty() {
+        return "none".to_string();
+    }
+    cost.iter()
+        .map(|energy| energy.to_string())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::get_card_by_enum;
+
+    const MEWTWO_NERF: &str = "# What if Mewtwo ex got nerfed
+A1 129: hp -20
+A1 129: retreat 1
+A1 129: weakness none
+A1 129: attack Psydrive damage 130 # was 150
+A1 129: attack psydrive cost -1
+";
+
+    fn mewtwo(card: Card) -> &'static PokemonCard {
+        match card {
+            Card::Pokemon(pokemon) => pokemon,
+            Card::Trainer(_) => panic!("Mewtwo ex is a Pokémon"),
+        }
+    }
+
+    #[test]
+    fn test_patch_changes_every_print() {
+        let errata = Errata::from_string(MEWTWO_NERF).unwrap();
+        for card_id in [CardId::A1129MewtwoEx, CardId::A1262MewtwoEx] {
+            let patched = mewtwo(errata.patch(get_card_by_enum(card_id)));
+            assert_eq!(patched.hp, 130);
+            assert_eq!(patched.retreat_cost, vec![EnergyType::Colorless]);
+            assert_eq!(patched.weakness, None);
+            assert_eq!(patched.attacks[1].fixed_damage, 130);
+            assert_eq!(
+                patched.attacks[1].energy_required,
+                vec![
+                    EnergyType::Psychic,
+                    EnergyType::Psychic,
+                    EnergyType::Colorless
+                ]
+            );
+        }
+        // Other cards and the database are untouched
+        let ralts = get_card_by_enum(CardId::A1130Ralts);
+        assert_eq!(errata.patch(ralts).get_attacks(), ralts.get_attacks());
+        assert_eq!(mewtwo(get_card_by_enum(CardId::A1129MewtwoEx)).hp, 150);
+
+        assert_eq!(
+            errata.describe(),
+            vec![
+                "Mewtwo ex (A1 129): HP 150 -> 130",
+                "Mewtwo ex (A1 129): retreat cost 2 -> 1",
+                "Mewtwo ex (A1 129): weakness Darkness -> none",
+                "Mewtwo ex (A1 129): Psydrive damage 150 -> 130",
+                "Mewtwo ex (A1 129): Psydrive cost Psychic, Psychic, Colorless, Colorless -> Psychic, Psychic, Colorless",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_invalid_errata() {
+        assert!(Errata::from_string("A1 129 hp -20").is_err());
+        assert!(Errata::from_string("A1 129: speed 3").is_err());
+        assert!(Errata::from_string("A1 129: hp -150").is_err());
+        assert!(Errata::from_string("A1 129: attack Hyper Beam damage 10").is_err());
+        // Double-Edge's damage is part of its implementation
+        assert!(Errata::from_string("A1 149: attack Double-Edge damage 200").is_err());
+        assert!(Errata::from_string("A1 149: attack Double-Edge cost -1").is_ok());
+        assert!(Errata::from_string("A1 129: attack Psydrive cost Shadow").is_err());
+        assert!(Errata::from_string("P-A 007: hp 10").is_err());
+        assert!(Errata::from_string("").unwrap().is_empty());
+    }
+}
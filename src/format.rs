@@ -0,0 +1,227 @@
+use std::collections::{HashMap, HashSet};
+use std::fs;
+
+use crate::{
+    card_equivalence::{canonical_card_id, equivalent_prints},
+    card_ids::CardId,
+    database::{get_card_by_enum, get_card_ref},
+    deck::DeckIssue,
+    models::Card,
+    optimize::try_parse_card_id_string,
+};
+
+/// The cards a tournament format allows, e.g. only the sets released before B1.
+///
+/// A card is legal if any of its reprints comes from an allowed set or booster pack (every
+/// card is when none are given), and bans and copy limits apply to all of its prints.
+/// Format files have one rule per line; `#` starts a comment:
+/// - `Sets: A1, A1a, P-A` (the set is the start of the card id)
+/// - `Packs: Genetic Apex, Mythical Island` (booster pack names, or how they start)
+/// - `Banned: A1 225, Giovanni` (cards by id or name)
+/// - `Limit: Professor's Research, 1` (at most that many copies in a deck)
+#[derive(Debug, Clone, Default)]
+pub struct Format {
+    pub sets: Vec<String>,
+    pub booster_packs: Vec<String>,
+    /// By canonical print
+    banned: HashSet<CardId>,
+    /// Most copies a deck may have, by canonical print
+    limits: HashMap<CardId, usize>,
+}
+
+impl Format {
+    pub fn from_file(path: &str) -> Result<Self, String> {
+        let contents = fs::read_to_string(path)
+            .map_err(|err| format!("Failed to read format {path}: {err}"))?;
+        Self::from_string(&contents)
+    }
+
+    pub fn from_string(contents: &str) -> Result<Self, String> {
+        let mut format = Format::default();
+        for line in contents.lines() {
+            let line = line.split('#').next().unwrap_or_default().trim();
+            if line.is_empty() {
+                continue;
+            }
+            let invalid = || format!("Invalid format line: {line}");
+            let (rule, text) = line.split_once(':').ok_or_else(invalid)?;
+            let values = text.split(',').map(str::trim).filter(|v| !v.is_empty());
+            match rule.trim() {
+                "Sets" => format.sets.extend(values.map(str::to_string)),
+                "Packs" => format.booster_packs.extend(values.map(str::to_string)),
+                "Banned" => {
+                    for card in values {
+                        format.ban(try_parse_card_id_string(card)?);
+                    }
+                }
+                "Limit" => {
+                    let (card, copies) = text.rsplit_once(',').ok_or_else(invalid)?;
+                    let copies = copies
+                        .trim()
+                        .parse::<usize>()
+                        .map_err(|_| format!("Invalid limit in format line: {line}"))?;
+                    format.limit(try_parse_card_id_string(card.trim())?, copies);
+                }
+                _ => return Err(invalid()),
+            }
+        }
+        Ok(format)
+    }
+
+    pub fn ban(&mut self, card_id: CardId) {
+        self.banned.insert(canonical_card_id(card_id));
+    }
+
+    /// Allows at most `copies` of a card (across its prints) in a deck
+    pub fn limit(&mut self, card_id: CardId, copies: usize) {
+        self.limits.insert(canonical_card_id(card_id), copies);
+    }
+
+    pub fn is_banned(&self, card: &Card) -> bool {
+        card.get_canonical_card_id()
+            .is_some_and(|card_id| self.banned.contains(&card_id))
+    }
+
+    /// Whether the card comes from an allowed set or booster pack, see `Format`
+    pub fn is_released(&self, card: &Card) -> bool {
+        self.allows_print(card)
+            || card.get_canonical_card_id().is_some_and(|card_id| {
+                equivalent_prints(card_id)
+                    .into_iter()
+                    .any(|print| self.allows_print(get_card_ref(print)))
+            })
+    }
+
+    /// Whether decks of this format can play the card at all
+    pub fn is_legal(&self, card: &Card) -> bool {
+        self.is_released(card) && !self.is_banned(card)
+    }
+
+    /// What the format doesn't allow in a deck of `cards`
+    pub fn deck_issues(&self, cards: &[Card]) -> Vec<DeckIssue> {
+        let mut issues = Vec::new();
+        let mut checked = HashSet::new();
+        let mut copies: HashMap<CardId, usize> = HashMap::new();
+        for card in cards {
+            if let Some(card_id) = card.get_canonical_card_id() {
+                *copies.entry(card_id).or_default() += 1;
+            }
+            if !checked.insert(card.get_id()) {
+                continue;
+            }
+            if !self.is_released(card) {
+                issues.push(DeckIssue::NotInFormat {
+                    id: card.get_id(),
+                    name: card.get_name(),
+                });
+            } else if self.is_banned(card) {
+                issues.push(DeckIssue::Banned {
+                    id: card.get_id(),
+                    name: card.get_name(),
+                });
+            }
+        }
+        let mut over_limit: Vec<(CardId, usize, usize)> = copies
+            .into_iter()
+            .filter_map(|(card_id, count)| {
+                let limit = *self.limits.get(&card_id)?;
+                (count > limit).then_some((card_id, count, limit))
+            })
+            .collect();
+        over_limit.sort_by_key(|&(card_id, _, _)| card_id as usize);
+        for (card_id, count, limit) in over_limit {
+            issues.push(DeckIssue::OverFormatLimit {
+                name: get_card_ref(card_id).get_name(),
+                count,
+                limit,
+            });
+        }
+        issues
+    }
+
+    /// Whether a deck of `cards` respects the format
+    pub fn allows(&self, cards: &[CardId]) -> bool {
+        let cards: Vec<Card> = cards.iter().map(|&id| get_card_by_enum(id)).collect();
+        self.deck_issues(&cards).is_empty()
+    }
+
+    fn allows_print(&self, card: &Card) -> bool {
+        let (id, booster_pack) = match card {
+            Card::Pokemon(pokemon) => (&pokemon.id, &pokemon.booster_pack),
+            Card::Trainer(trainer) => (&trainer.id, &trainer.booster_pack),
+        };
+        let set = id.split(' ').next().unwrap_or_default();
+        let booster_pack = booster_pack.to_lowercase();
+        (self.sets.is_empty() && self.booster_packs.is_empty())
+            || self
+                .sets
+                .iter()
+                .any(|allowed| allowed.eq_ignore_ascii_case(set))
+            || self
+                .booster_packs
+                .iter()
+                .any(|allowed| booster_pack.starts_with(&allowed.to_lowercase()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PRE_B1: &str = "# Everything before Mega Rising
+Sets: A1, A1a, A2, A2a, A2b, A3, A3a, A3b
+Packs: Wisdom of Sea and Sky, Secluded Springs, Deluxe Pack, Promo A
+Banned: Giovanni
+Limit: A2b 111, 1 # Poké Ball
+";
+
+    #[test]
+    fn test_legality_by_set_and_ban() {
+        let format = Format::from_string(PRE_B1).unwrap();
+        assert!(format.is_legal(&get_card_by_enum(CardId::A1001Bulbasaur)));
+        assert!(format.is_legal(&get_card_by_enum(CardId::A4a001Hoppip)));
+        assert!(!format.is_legal(&get_card_by_enum(CardId::B1001Pinsir)));
+        // Reprints of legal cards are legal too
+        assert!(format.is_legal(&get_card_by_enum(CardId::B1297Poliwrath)));
+        assert!(format.is_released(&get_card_by_enum(CardId::A1223Giovanni)));
+        assert!(format.is_banned(&get_card_by_enum(CardId::A1223Giovanni)));
+
+        assert!(Format::default().is_legal(&get_card_by_enum(CardId::B1001Pinsir)));
+        assert!(Format::from_string("Sets A1").is_err());
+        assert!(Format::from_string("Limit: A1 001").is_err());
+        assert!(Format::from_string("Banned: Not A Card").is_err());
+    }
+
+    #[test]
+    fn test_deck_issues() {
+        let format = Format::from_string(PRE_B1).unwrap();
+        let cards = [
+            CardId::A1001Bulbasaur,
+            CardId::B1001Pinsir,
+            CardId::A1223Giovanni,
+            CardId::A2b111PokeBall,
+            CardId::PA005PokeBall,
+        ];
+        let cards: Vec<Card> = cards.iter().map(|&id| get_card_by_enum(id)).collect();
+        assert_eq!(
+            format.deck_issues(&cards),
+            vec![
+                DeckIssue::NotInFormat {
+                    id: "B1 001".to_string(),
+                    name: "Pinsir".to_string()
+                },
+                DeckIssue::Banned {
+                    id: "A1 223".to_string(),
+                    name: "Giovanni".to_string()
+                },
+                DeckIssue::OverFormatLimit {
+                    name: "Poké Ball".to_string(),
+                    count: 2,
+                    limit: 1
+                },
+            ]
+        );
+        assert!(format.allows(&[CardId::A1001Bulbasaur, CardId::PA005PokeBall]));
+        assert!(!format.allows(&[CardId::A2b111PokeBall, CardId::PA005PokeBall]));
+    }
+}
@@ -78,7 +78,7 @@ impl<'a> Game<'a> {
         self.state.is_game_over()
     }
 
-    // Returns None if the game times out
+    // Returns None if the game hits the turn limit (see `State::end_reason`)
     pub fn play(&mut self) -> Option<GameOutcome> {
         if self.debug {
             info!("Playing game with seed: {}", self.seed);
@@ -175,7 +175,11 @@ impl<'a> Game<'a> {
 
     /// see https://github.com/colored-rs/colored?tab=readme-ov-file#colors
     fn get_color(&self, actor: usize) -> String {
-        let energy = self.state.decks[actor].energy_types[0];
+        let energy = self.state.decks[actor]
+            .energy_types
+            .first()
+            .copied()
+            .unwrap_or(EnergyType::Colorless);
         let color = match energy {
             EnergyType::Colorless => "bright_bright_black",
             EnergyType::Fighting => "red",
@@ -0,0 +1,268 @@
+use serde::{Deserialize, Serialize};
+use std::{
+    any,
+    fs::File,
+    io::{BufWriter, Write},
+    path::Path,
+    time::Instant,
+};
+use uuid::Uuid;
+
+use crate::{
+    actions::Action,
+    simulation_event_handler::{GameSetup, SimulationEventHandler},
+    state::{GameEndReason, GameOutcome},
+    State,
+};
+
+/// One row of the per-game results export
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GameRecord {
+    pub game_id: String,
+    pub seed: u64,
+    /// Seat index of the winner, `None` for ties and games that hit the turn limit
+    pub winner: Option<usize>,
+    pub points_a: u8,
+    pub points_b: u8,
+    pub turns: u8,
+    pub plies: u32,
+    /// Seat index of the player that took turn 1
+    pub first_player: Option<usize>,
+    pub end_reason: Option<GameEndReason>,
+    pub deck_a: String,
+    pub deck_b: String,
+    pub player_a: String,
+    pub player_b: String,
+    pub duration_ms: f64,
+}
+
+/// Details of the game currently being played by a per-game handler instance
+struct GameInProgress {
+    setup: Option<GameSetup>,
+    start: Instant,
+    plies: u32,
+    first_player: Option<usize>,
+}
+
+/// Collects one `GameRecord` per game so results can be exported (e.g. for pandas).
+///
+/// Records keep the order in which games were scheduled, also in parallel runs.
+#[derive(Default)]
+pub struct GameResultsCollector {
+    records: Vec<GameRecord>,
+    current: Option<GameInProgress>,
+}
+
+impl GameResultsCollector {
+    pub fn records(&self) -> &[GameRecord] {
+        &self.records
+    }
+
+    /// Writes the records to `path`, choosing the format from its extension
+    /// (`.csv` needs the `csv-export` feature, anything else is written as JSON Lines).
+    pub fn export(&self, path: &str) -> Result<(), Box<dyn std::error::Error>> {
+        let is_csv = Path::new(path)
+            .extension()
+            .is_some_and(|ext| ext.eq_ignore_ascii_case("csv"));
+        if is_csv {
+            self.write_csv(path)
+        } else {
+            self.write_jsonl(path)
+        }
+    }
+
+    /// Reads records previously written with `write_jsonl`
+    pub fn read_jsonl(path: &str) -> Result<Vec<GameRecord>, Box<dyn std::error::Error>> {
+        let contents = std::fs::read_to_string(path)?;
+        let mut records = Vec::new();
+        for line in contents.lines().filter(|line| !line.trim().is_empty()) {
+            records.push(serde_json::from_str(line)?);
+        }
+        Ok(records)
+    }
+
+    pub fn write_jsonl(&self, path: &str) -> Result<(), Box<dyn std::error::Error>> {
+        let mut writer = BufWriter::new(File::create(path)?);
+        for record in &self.records {
+            serde_json::to_writer(&mut writer, record)?;
+            writer.write_all(b"\n")?;
+        }
+        writer.flush()?;
+        Ok(())
+    }
+
+    #[cfg(feature = "csv-export")]
+    pub fn write_csv(&self, path: &str) -> Result<(), Box<dyn std::error::Error>> {
+        let mut writer = csv::Writer::from_path(path)?;
+        for record in &self.records {
+            writer.serialize(record)?;
+        }
+        writer.flush()?;
+        Ok(())
+    }
+
+    #[cfg(not(feature = "csv-export"))]
+    pub fn write_csv(&self, _path: &str) -> Result<(), Box<dyn std::error::Error>> {
+        Err("CSV export requires building with `--features csv-export`".into())
+    }
+}
+
+impl SimulationEventHandler for GameResultsCollector {
+    fn on_game_start(&mut self, _game_id: Uuid) {
+        self.current = Some(GameInProgress {
+            setup: None,
+            start: Instant::now(),
+            plies: 0,
+            first_player: None,
+        });
+    }
+
+    fn on_game_setup(&mut self, _game_id: Uuid, setup: &GameSetup) {
+        if let Some(current) = self.current.as_mut() {
+            current.setup = Some(setup.clone());
+        }
+    }
+
+    fn on_action(
+        &mut self,
+        _game_id: Uuid,
+        state_before_action: &State,
+        _actor: usize,
+        _playable_actions: &[Action],
+        _action: &Action,
+    ) {
+        if let Some(current) = self.current.as_mut() {
+            current.plies += 1;
+            // The player that sets up first (coin toss winner) also takes turn 1
+            if current.first_player.is_none() {
+                current.first_player = Some(state_before_action.current_player);
+            }
+        }
+    }
+
+    fn on_game_end(&mut self, game_id: Uuid, state: State, outcome: Option<GameOutcome>) {
+        let Some(current) = self.current.take() else {
+            return;
+        };
+        let setup = current.setup.unwrap_or(GameSetup {
+            seed: 0,
+            deck_names: [String::new(), String::new()],
+            player_names: [String::new(), String::new()],
+        });
+        let [deck_a, deck_b] = setup.deck_names;
+        let [player_a, player_b] = setup.player_names;
+        self.records.push(GameRecord {
+            game_id: game_id.to_string(),
+            seed: setup.seed,
+            winner: match outcome {
+                Some(GameOutcome::Win(winner)) => Some(winner),
+                _ => None,
+            },
+            points_a: state.points[0],
+            points_b: state.points[1],
+            turns: state.turn_count,
+            plies: current.plies,
+            first_player: current.first_player,
+            end_reason: state.end_reason,
+            deck_a,
+            deck_b,
+            player_a,
+            player_b,
+            duration_ms: current.start.elapsed().as_secs_f64() * 1000.0,
+        });
+    }
+
+    fn merge(&mut self, other: &dyn SimulationEventHandler) {
+        if let Some(other) = (other as &dyn any::Any).downcast_ref::<GameResultsCollector>() {
+            self.records.extend(other.records.iter().cloned());
+        } else {
+            panic!("Attempted to merge GameResultsCollector with incompatible type");
+        }
+    }
+
+    fn save_state(&self) -> Option<serde_json::Value> {
+        serde_json::to_value(&self.records).ok()
+    }
+
+    fn load_state(&mut self, state: serde_json::Value) -> Result<(), String> {
+        self.records = serde_json::from_value(state).map_err(|err| err.to_string())?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{players::PlayerCode, Simulation};
+
+    #[test]
+    fn test_collects_one_record_per_game() {
+        let mut simulation = Simulation::new(
+            "example_decks/venusaur-exeggutor.txt",
+            "example_decks/weezing-arbok.txt",
+            vec![PlayerCode::R, PlayerCode::AA],
+            3,
+            Some(7),
+            false,
+            None,
+        )
+        .unwrap()
+        .register::<GameResultsCollector>();
+        simulation.run();
+
+        let collector = simulation
+            .get_event_handler::<GameResultsCollector>()
+            .unwrap();
+        let records = collector.records();
+        assert_eq!(records.len(), 3);
+        let mut seeds: Vec<u64> = records.iter().map(|record| record.seed).collect();
+        seeds.sort();
+        assert_eq!(seeds, vec![7, 8, 9]);
+        for record in records {
+            assert_eq!(record.deck_a, "venusaur-exeggutor");
+            assert_eq!(record.deck_b, "weezing-arbok");
+            assert_eq!(record.player_a, "r");
+            assert_eq!(record.player_b, "aa");
+            assert!(record.plies > 0);
+            assert!(record.first_player.is_some());
+            assert!(record.end_reason.is_some());
+            assert_eq!(
+                record.winner.is_some(),
+                matches!(
+                    record.end_reason,
+                    Some(GameEndReason::PointsReached | GameEndReason::NoPokemonInPlay)
+                )
+            );
+        }
+    }
+
+    #[test]
+    fn test_write_jsonl_round_trips() {
+        let record = GameRecord {
+            game_id: "id".to_string(),
+            seed: 1,
+            winner: Some(1),
+            points_a: 1,
+            points_b: 3,
+            turns: 9,
+            plies: 40,
+            first_player: Some(0),
+            end_reason: Some(GameEndReason::PointsReached),
+            deck_a: "a".to_string(),
+            deck_b: "b".to_string(),
+            player_a: "r".to_string(),
+            player_b: "r".to_string(),
+            duration_ms: 1.5,
+        };
+        let collector = GameResultsCollector {
+            records: vec![record.clone(), record.clone()],
+            current: None,
+        };
+        let path = std::env::temp_dir().join("deckgym_game_results_test.jsonl");
+        let path = path.to_str().unwrap();
+        collector.export(path).unwrap();
+
+        let parsed = GameResultsCollector::read_jsonl(path).unwrap();
+        assert_eq!(parsed, vec![record.clone(), record]);
+    }
+}
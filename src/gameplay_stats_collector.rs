@@ -3,6 +3,8 @@ use uuid::Uuid;
 
 use crate::{
     actions::{Action, SimpleAction},
+    database::get_card_ref,
+    models::Card,
     simulation_event_handler::SimulationEventHandler,
     State,
 };
@@ -16,7 +18,7 @@ pub struct AggregatedStats {
     pub deck_empty_avg: [Option<f64>; 2],
     /// Average hand size across all turns, indexed by player
     pub hand_sizes: [f64; 2],
-    /// Average turn cards first appeared, indexed by player then card_id
+    /// Average turn cards first appeared, indexed by player then card_id (of the canonical print)
     pub cards_seen: [HashMap<String, f64>; 2],
     /// Average turn attacks first used, indexed by player then (card_id, attack_idx)
     pub attacks_used: [HashMap<(String, u8), f64>; 2],
@@ -95,7 +97,7 @@ impl GameplayStatsCollector {
     fn track_cards_on_mat(&mut self, state: &State) {
         for player in 0..2 {
             for (_idx, played_card) in state.enumerate_in_play_pokemon(player) {
-                let card_id = played_card.card.get_id();
+                let card_id = stats_card_id(&played_card.card);
 
                 // Check if this is the first time we've seen this card in this game
                 if !self
@@ -163,7 +165,7 @@ impl GameplayStatsCollector {
                 .enumerate_in_play_pokemon(actor)
                 .find(|(i, _)| *i == 0)
             {
-                let card_id = active_pokemon.card.get_id();
+                let card_id = stats_card_id(&active_pokemon.card);
                 let attack_key = (card_id.clone(), attack_idx as u8);
 
                 // Check if this is the first time this specific attack was used in this game
@@ -253,6 +255,14 @@ impl GameplayStatsCollector {
     }
 }
 
+/// Reprints are counted under the id of their canonical print, see `canonical_card_id`
+fn stats_card_id(card: &Card) -> String {
+    match card.get_canonical_card_id() {
+        Some(canonical) if canonical != card.get_card_id() => get_card_ref(canonical).get_id(),
+        _ => card.get_id(),
+    }
+}
+
 impl SimulationEventHandler for GameplayStatsCollector {
     fn on_game_start(&mut self, game_id: Uuid) {
         self.current_game_id = Some(game_id);
@@ -0,0 +1,739 @@
+use std::{fs, path::Path};
+
+use log::warn;
+use num_format::{Locale, ToFormattedString};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    checkpoint::{deck_card_ids, play_in_batches, player_names, Checkpoint, CheckpointConfig},
+    errata::Errata,
+    optimize::{ParallelConfig, SimulationConfig},
+    players::fill_code_array,
+    simulate::{create_progress_bar, deck_name_from_path, game_seed, play_game},
+    statistics::{weighted_win_rate, MatchTally},
+    Deck,
+};
+
+/// One opponent of a metagame, with its share of the field
+#[derive(Clone)]
+pub struct MetaEntry {
+    pub name: String,
+    pub deck: Deck,
+    /// Relative share of the field; weights don't need to add up to anything in particular
+    pub weight: f64,
+}
+
+/// A field of opponent decks weighted by how often they are expected to be faced.
+///
+/// Meta files have one opponent per line: `<deck path>, <weight>[, <name>]`.
+/// Deck paths are taken as written or, if that doesn't exist, relative to the meta file.
+/// Empty lines and lines starting with `#` are ignored.
+///
+/// ```text
+/// # deck, share, name
+/// example_decks/mewtwoex.txt, 30, Mewtwo ex
+/// example_decks/weezing-arbok.txt, 12.5
+/// ```
+#[derive(Clone, Default)]
+pub struct Meta {
+    pub entries: Vec<MetaEntry>,
+}
+
+impl Meta {
+    pub fn from_file(path: &str) -> Result<Self, String> {
+        let contents = fs::read_to_string(path)
+            .map_err(|err| format!("Failed to read meta file {path}: {err}"))?;
+        let base_dir = Path::new(path).parent().unwrap_or(Path::new(""));
+        Self::from_string(&contents, base_dir)
+    }
+
+    /// Parses meta file contents, resolving relative deck paths against `base_dir` when needed
+    pub fn from_string(contents: &str, base_dir: &Path) -> Result<Self, String> {
+        let mut entries = Vec::new();
+        for (line_number, line) in contents.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let context = |msg: String| format!("Meta line {}: {msg}", line_number + 1);
+
+            let fields: Vec<&str> = line.splitn(3, ',').map(str::trim).collect();
+            if fields.len() < 2 {
+                return Err(context(format!(
+                    "expected `<deck path>, <weight>[, <name>]`, got '{line}'"
+                )));
+            }
+            let deck_path = resolve_deck_path(fields[0], base_dir);
+            let weight: f64 = fields[1]
+                .parse()
+                .map_err(|_| context(format!("invalid weight '{}'", fields[1])))?;
+            if !weight.is_finite() || weight < 0.0 {
+                return Err(context(format!(
+                    "weight must be non-negative, got {weight}"
+                )));
+            }
+            let deck = Deck::from_file(&deck_path).map_err(context)?;
+            deck.check_valid()
+                .map_err(|err| context(format!("deck {deck_path} is not valid: {err}")))?;
+            let name = match fields.get(2) {
+                Some(name) if !name.is_empty() => name.to_string(),
+                _ => deck_name_from_path(&deck_path),
+            };
+            entries.push(MetaEntry { name, deck, weight });
+        }
+
+        if entries.is_empty() {
+            return Err("Meta contains no decks".to_string());
+        }
+        if entries.iter().all(|entry| entry.weight == 0.0) {
+            return Err("Meta weights add up to zero".to_string());
+        }
+        Ok(Meta { entries })
+    }
+
+    /// Every valid deck file in a folder, all with the same weight
+    pub fn from_folder(folder: &str) -> Result<Self, String> {
+        let mut deck_paths: Vec<String> = fs::read_dir(folder)
+            .map_err(|err| format!("Failed to read decks folder {folder}: {err}"))?
+            .filter_map(|entry| {
+                let entry = entry.ok()?;
+                if entry.path().is_file() {
+                    Some(entry.path().to_str()?.to_string())
+                } else {
+                    None
+                }
+            })
+            .collect();
+        deck_paths.sort();
+
+        let entries: Vec<MetaEntry> = deck_paths
+            .iter()
+            .filter_map(|path| {
+                match Deck::from_file(path).and_then(|deck| deck.check_valid().map(|()| deck)) {
+                    Ok(deck) => Some(MetaEntry {
+                        name: deck_name_from_path(path),
+                        deck,
+                        weight: 1.0,
+                    }),
+                    Err(err) => {
+                        warn!("Skipping deck {path}: {err}");
+                        None
+                    }
+                }
+            })
+            .collect();
+        if entries.is_empty() {
+            return Err(format!("No valid decks found in folder: {folder}"));
+        }
+        Ok(Meta { entries })
+    }
+
+    /// Plays every deck of the meta with the errata's card changes
+    pub fn with_errata(mut self, errata: &Errata) -> Self {
+        for entry in self.entries.iter_mut() {
+            errata.apply(&mut entry.deck);
+        }
+        self
+    }
+
+    /// Normalized share of each entry (adds up to 1)
+    pub fn shares(&self) -> Vec<f64> {
+        let total: f64 = self.entries.iter().map(|entry| entry.weight).sum();
+        self.entries
+            .iter()
+            .map(|entry| entry.weight / total)
+            .collect()
+    }
+
+    /// Splits `total_games` across entries proportionally to their weights (largest remainder),
+    /// so the allocation always adds up to exactly `total_games`. When there are enough games,
+    /// every entry with a nonzero weight plays at least one, so none is left out of the
+    /// weighted win rate.
+    pub fn allocate_games(&self, total_games: u32) -> Vec<u32> {
+        let shares = self.shares();
+        let weighted_entries = shares.iter().filter(|&&share| share > 0.0).count() as u32;
+        let reserved = if total_games >= weighted_entries {
+            1
+        } else {
+            0
+        };
+        let remaining_games = total_games - reserved * weighted_entries;
+        let quotas: Vec<f64> = shares
+            .iter()
+            .map(|share| share * remaining_games as f64)
+            .collect();
+        let mut allocation: Vec<u32> = quotas.iter().map(|quota| quota.floor() as u32).collect();
+
+        let mut by_remainder: Vec<usize> = (0..quotas.len()).collect();
+        by_remainder.sort_by(|&a, &b| {
+            let remainder_a = quotas[a] - quotas[a].floor();
+            let remainder_b = quotas[b] - quotas[b].floor();
+            remainder_b.total_cmp(&remainder_a).then(a.cmp(&b))
+        });
+        let leftover = remaining_games - allocation.iter().sum::<u32>();
+        for &index in by_remainder.iter().take(leftover as usize) {
+            allocation[index] += 1;
+        }
+        for (games, share) in allocation.iter_mut().zip(&shares) {
+            if *share > 0.0 {
+                *games += reserved;
+            }
+        }
+        allocation
+    }
+}
+
+fn resolve_deck_path(deck_path: &str, base_dir: &Path) -> String {
+    let as_written = Path::new(deck_path);
+    if as_written.exists() || as_written.is_absolute() {
+        return deck_path.to_string();
+    }
+    base_dir.join(as_written).to_string_lossy().into_owned()
+}
+
+/// Results against one opponent of the meta
+#[derive(Debug, Clone, Serialize)]
+pub struct MatchupResult {
+    pub name: String,
+    /// Normalized share of the field
+    pub share: f64,
+    pub tally: MatchTally,
+}
+
+impl MatchupResult {
+    pub fn win_rate(&self) -> f64 {
+        self.tally.win_rate()
+    }
+
+    pub fn confidence_interval(&self) -> (f64, f64) {
+        self.tally.confidence_interval()
+    }
+}
+
+/// Summary of a deck's games against a whole meta
+#[derive(Debug, Clone, Serialize)]
+pub struct GauntletReport {
+    pub matchups: Vec<MatchupResult>,
+    /// Win rate with every matchup weighted by its share of the field
+    pub weighted_win_rate: f64,
+    /// 95% confidence interval of `weighted_win_rate`
+    pub weighted_confidence_interval: (f64, f64),
+}
+
+impl GauntletReport {
+    fn from_matchups(matchups: Vec<MatchupResult>) -> Self {
+        let items: Vec<(f64, MatchTally)> = matchups
+            .iter()
+            .map(|matchup| (matchup.share, matchup.tally))
+            .collect();
+        let (weighted_win_rate, weighted_confidence_interval) = weighted_win_rate(&items);
+        GauntletReport {
+            matchups,
+            weighted_win_rate,
+            weighted_confidence_interval,
+        }
+    }
+
+    pub fn total_games(&self) -> u32 {
+        self.matchups
+            .iter()
+            .map(|matchup| matchup.tally.games())
+            .sum()
+    }
+
+    /// Opponents with a share of the field that got no games (too few games for the meta),
+    /// so the weighted win rate leaves them out
+    pub fn excluded_matchups(&self) -> Vec<&str> {
+        self.matchups
+            .iter()
+            .filter(|matchup| matchup.share > 0.0 && matchup.tally.games() == 0)
+            .map(|matchup| matchup.name.as_str())
+            .collect()
+    }
+}
+
+/// Games of a gauntlet played so far, as saved in its checkpoints
+#[derive(Serialize, Deserialize)]
+struct GauntletProgress {
+    games_done: usize,
+    tallies: Vec<MatchTally>,
+}
+
+/// Plays `deck` (as player 0) against every deck of the meta, splitting
+/// `sim_config.num_games` proportionally to the meta weights.
+///
+/// `on_game_complete` is called after every game, e.g. to advance a progress bar.
+pub fn run_gauntlet<F>(
+    deck: &Deck,
+    meta: &Meta,
+    sim_config: &SimulationConfig,
+    parallel_config: &ParallelConfig,
+    on_game_complete: Option<F>,
+) -> GauntletReport
+where
+    F: Fn() + Sync,
+{
+    run_gauntlet_with_checkpoint(
+        deck,
+        meta,
+        sim_config,
+        parallel_config,
+        None,
+        None,
+        on_game_complete,
+    )
+    .expect("Gauntlets without a checkpoint can't fail")
+}
+
+/// Like `run_gauntlet`, but plays every deck with the `errata` (if any), and saves its progress
+/// to `checkpoint` (if any) and resumes from it
+pub fn run_gauntlet_with_checkpoint<F>(
+    deck: &Deck,
+    meta: &Meta,
+    sim_config: &SimulationConfig,
+    parallel_config: &ParallelConfig,
+    errata: Option<&Errata>,
+    checkpoint: Option<&CheckpointConfig>,
+    on_game_complete: Option<F>,
+) -> Result<GauntletReport, String>
+where
+    F: Fn() + Sync,
+{
+    let mut patched = None;
+    if let Some(errata) = errata {
+        let mut deck = deck.clone();
+        errata.apply(&mut deck);
+        patched = Some((deck, meta.clone().with_errata(errata)));
+    }
+    let (deck, meta) = match &patched {
+        Some((deck, meta)) => (deck, meta),
+        None => (deck, meta),
+    };
+
+    if let Some(num_threads) = parallel_config.num_threads {
+        rayon::ThreadPoolBuilder::new()
+            .num_threads(num_threads)
+            .build_global()
+            .ok(); // Ignore error if pool is already initialized
+    }
+
+    let allocation = meta.allocate_games(sim_config.num_games);
+    let games: Vec<usize> = allocation
+        .iter()
+        .enumerate()
+        .flat_map(|(entry_index, &num_games)| std::iter::repeat_n(entry_index, num_games as usize))
+        .collect();
+    let player_codes = fill_code_array(sim_config.players.clone());
+
+    let mut progress = GauntletProgress {
+        games_done: 0,
+        tallies: vec![MatchTally::default(); meta.entries.len()],
+    };
+    let mut seed = sim_config.seed;
+    let mut checkpoint = match checkpoint {
+        Some(config) => {
+            let mut run = serde_json::json!({
+                "deck": deck_card_ids(deck),
+                "meta": meta
+                    .entries
+                    .iter()
+                    .map(|entry| (deck_card_ids(&entry.deck), entry.weight))
+                    .collect::<Vec<_>>(),
+                "num_games": sim_config.num_games,
+                "players": player_names(&sim_config.players),
+                "seed": sim_config.seed,
+            });
+            // Card ids stay the same under errata, so its changes tell runs apart
+            if let Some(errata) = errata.filter(|errata| !errata.is_empty()) {
+                run["errata"] = serde_json::json!(errata.describe());
+            }
+            let (checkpoint, saved) = Checkpoint::open(config, "gauntlet", run, seed)?;
+            progress = saved.unwrap_or(progress);
+            seed = Some(checkpoint.seed());
+            Some(checkpoint)
+        }
+        None => None,
+    };
+
+    let play_single_game = |game_index: usize| {
+        let entry_index = games[game_index];
+        let played = play_game(
+            deck,
+            &meta.entries[entry_index].deck,
+            player_codes.clone(),
+            game_seed(seed, game_index as u64),
+        );
+        if let Some(callback) = &on_game_complete {
+            callback();
+        }
+        (entry_index, played.state.winner)
+    };
+    play_in_batches(
+        progress.games_done,
+        games.len(),
+        checkpoint.is_some(),
+        parallel_config.enabled,
+        play_single_game,
+        |games_done, outcomes| {
+            for (entry_index, outcome) in outcomes {
+                progress.tallies[entry_index].record(outcome, 0);
+            }
+            progress.games_done = games_done;
+            if let Some(checkpoint) = checkpoint.as_mut() {
+                checkpoint.save_if_due(&progress);
+            }
+        },
+    );
+    if let Some(checkpoint) = checkpoint.as_mut() {
+        checkpoint.save(&progress);
+    }
+
+    let matchups = meta
+        .entries
+        .iter()
+        .zip(meta.shares())
+        .zip(progress.tallies)
+        .map(|((entry, share), tally)| MatchupResult {
+            name: entry.name.clone(),
+            share,
+            tally,
+        })
+        .collect();
+    Ok(GauntletReport::from_matchups(matchups))
+}
+
+/// Runs a gauntlet with a progress bar and prints its summary table, as the CLI does.
+/// `errata` (if any) is applied to the deck under test and to every deck of the meta.
+pub fn cli_gauntlet(
+    deck_path: &str,
+    meta: &Meta,
+    sim_config: SimulationConfig,
+    parallel_config: ParallelConfig,
+    errata: Option<&Errata>,
+    checkpoint: Option<&CheckpointConfig>,
+) -> Option<GauntletReport> {
+    let deck = match Deck::from_file(deck_path) {
+        Ok(deck) => deck,
+        Err(err) => {
+            warn!("Failed to load deck {deck_path}: {err}");
+            return None;
+        }
+    };
+    if let Err(err) = deck.check_valid() {
+        warn!("{deck_path} is not valid: {err}");
+        return None;
+    }
+    warn!(
+        "Running {} games of {} against {} decks{}",
+        sim_config.num_games.to_formatted_string(&Locale::en),
+        deck_name_from_path(deck_path),
+        meta.entries.len(),
+        if parallel_config.enabled {
+            " (parallel)"
+        } else {
+            ""
+        }
+    );
+    if let Some(errata) = errata {
+        warn!("\tErrata:");
+        for line in errata.describe() {
+            warn!("\t\t{line}");
+        }
+    }
+
+    let pb = create_progress_bar(sim_config.num_games as u64);
+    pb.tick(); // Ensure progress bar is drawn immediately
+    let report = run_gauntlet_with_checkpoint(
+        &deck,
+        meta,
+        &sim_config,
+        &parallel_config,
+        errata,
+        checkpoint,
+        Some(|| pb.inc(1)),
+    );
+    pb.finish_with_message("Gauntlet complete!");
+    let report = match report {
+        Ok(report) => report,
+        Err(err) => {
+            warn!("{err}");
+            return None;
+        }
+    };
+
+    print_gauntlet_report(&report);
+    Some(report)
+}
+
+/// Prints the matchup table and the weighted overall win rate
+pub fn print_gauntlet_report(report: &GauntletReport) {
+    let name_width = report
+        .matchups
+        .iter()
+        .map(|matchup| matchup.name.chars().count())
+        .max()
+        .unwrap_or(0)
+        .max("Opponent".len());
+    warn!(
+        "{:<name_width$}  {:>6}  {:>7}  {:>14}  {:>6}  {:>15}",
+        "Opponent", "Share", "Games", "W-L-T", "Win%", "95% CI"
+    );
+    for matchup in &report.matchups {
+        let tally = matchup.tally;
+        let (low, high) = matchup.confidence_interval();
+        warn!(
+            "{:<name_width$}  {:>5.1}%  {:>7}  {:>14}  {:>5.1}%  {:>15}",
+            matchup.name,
+            matchup.share * 100.0,
+            tally.games().to_formatted_string(&Locale::en),
+            format!("{}-{}-{}", tally.wins, tally.losses, tally.ties),
+            matchup.win_rate() * 100.0,
+            format!("{:.1}%-{:.1}%", low * 100.0, high * 100.0)
+        );
+    }
+    let (low, high) = report.weighted_confidence_interval;
+    warn!(
+        "Weighted win rate over {} games: {:.2}% (95% CI {:.2}%-{:.2}%)",
+        report.total_games().to_formatted_string(&Locale::en),
+        report.weighted_win_rate * 100.0,
+        low * 100.0,
+        high * 100.0
+    );
+    let excluded = report.excluded_matchups();
+    if !excluded.is_empty() {
+        warn!(
+            "Not enough games to face every opponent; left out of the weighted win rate: {}",
+            excluded.join(", ")
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{models::Card, players::PlayerCode};
+
+    fn meta_with_weights(weights: &[f64]) -> Meta {
+        let deck = Deck::from_file("example_decks/weezing-arbok.txt").unwrap();
+        Meta {
+            entries: weights
+                .iter()
+                .enumerate()
+                .map(|(i, &weight)| MetaEntry {
+                    name: format!("deck{i}"),
+                    deck: deck.clone(),
+                    weight,
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn test_allocate_games_is_proportional_and_exact() {
+        let meta = meta_with_weights(&[50.0, 30.0, 20.0]);
+        assert_eq!(meta.allocate_games(10), vec![5, 3, 2]);
+
+        let meta = meta_with_weights(&[1.0, 1.0, 1.0]);
+        assert_eq!(meta.allocate_games(10), vec![4, 3, 3]);
+
+        let meta = meta_with_weights(&[2.0, 1.0, 0.0]);
+        let allocation = meta.allocate_games(7);
+        assert_eq!(allocation.iter().sum::<u32>(), 7);
+        assert_eq!(allocation[2], 0);
+
+        // Small shares still get a game, as long as there are enough games to go around
+        let meta = meta_with_weights(&[97.0, 1.0, 1.0, 1.0]);
+        assert_eq!(meta.allocate_games(10), vec![7, 1, 1, 1]);
+        assert_eq!(meta.allocate_games(3), vec![3, 0, 0, 0]);
+    }
+
+    #[test]
+    fn test_gauntlet_report_lists_excluded_matchups() {
+        let matchup = |name: &str, share: f64, wins: u32| MatchupResult {
+            name: name.to_string(),
+            share,
+            tally: MatchTally {
+                wins,
+                ..Default::default()
+            },
+        };
+        let report = GauntletReport::from_matchups(vec![
+            matchup("played", 0.9, 3),
+            matchup("skipped", 0.1, 0),
+            matchup("unweighted", 0.0, 0),
+        ]);
+        assert_eq!(report.excluded_matchups(), vec!["skipped"]);
+    }
+
+    #[test]
+    fn test_meta_with_errata_patches_every_deck() {
+        let errata = Errata::from_string("A1 165: hp 10").unwrap();
+        let meta = meta_with_weights(&[1.0, 1.0]).with_errata(&errata);
+        for entry in &meta.entries {
+            assert!(entry
+                .deck
+                .cards
+                .iter()
+                .any(|card| card.get_name() == "Arbok"));
+            for card in &entry.deck.cards {
+                if let Card::Pokemon(pokemon) = card {
+                    if pokemon.name == "Arbok" {
+                        assert_eq!(pokemon.hp, 10);
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_meta_from_string() {
+        let meta = Meta::from_string(
+            "# deck, share, name\n\
+             example_decks/mewtwoex.txt, 30, Mewtwo ex\n\
+             \n\
+             weezing-arbok.txt, 10\n",
+            Path::new("example_decks"),
+        )
+        .unwrap();
+
+        assert_eq!(meta.entries.len(), 2);
+        assert_eq!(meta.entries[0].name, "Mewtwo ex");
+        assert_eq!(meta.entries[1].name, "weezing-arbok");
+        assert_eq!(meta.shares(), vec![0.75, 0.25]);
+
+        assert!(Meta::from_string("example_decks/mewtwoex.txt", Path::new("")).is_err());
+        assert!(Meta::from_string("example_decks/mewtwoex.txt, -1", Path::new("")).is_err());
+        assert!(Meta::from_string("# only comments", Path::new("")).is_err());
+    }
+
+    #[test]
+    fn test_run_gauntlet() {
+        let deck = Deck::from_file("example_decks/venusaur-exeggutor.txt").unwrap();
+        let meta = meta_with_weights(&[3.0, 1.0]);
+        let sim_config = SimulationConfig {
+            num_games: 8,
+            players: Some(vec![PlayerCode::R, PlayerCode::R]),
+            seed: Some(3),
+        };
+
+        let report = run_gauntlet(
+            &deck,
+            &meta,
+            &sim_config,
+            &ParallelConfig::default(),
+            None::<fn()>,
+        );
+        assert_eq!(report.total_games(), 8);
+        assert_eq!(report.matchups[0].tally.games(), 6);
+        assert_eq!(report.matchups[1].tally.games(), 2);
+        let (low, high) = report.weighted_confidence_interval;
+        assert!(low <= report.weighted_win_rate && report.weighted_win_rate <= high);
+
+        // Seeded gauntlets are reproducible
+        let again = run_gauntlet(
+            &deck,
+            &meta,
+            &sim_config,
+            &ParallelConfig::default(),
+            None::<fn()>,
+        );
+        assert_eq!(report.matchups[0].tally, again.matchups[0].tally);
+    }
+
+    #[test]
+    fn test_cli_gauntlet_rejects_invalid_deck() {
+        let meta = meta_with_weights(&[1.0]);
+        let sim_config = SimulationConfig {
+            num_games: 2,
+            players: Some(vec![PlayerCode::R, PlayerCode::R]),
+            seed: Some(1),
+        };
+        let report = cli_gauntlet(
+            "example_decks/incomplete-chari.txt",
+            &meta,
+            sim_config,
+            ParallelConfig::default(),
+            None,
+            None,
+        );
+        assert!(report.is_none());
+    }
+
+    #[test]
+    fn test_resumed_gauntlet_matches_uninterrupted_one() {
+        let deck = Deck::from_file("example_decks/venusaur-exeggutor.txt").unwrap();
+        let meta = meta_with_weights(&[1.0, 1.0]);
+        let sim_config = SimulationConfig {
+            num_games: 6,
+            players: Some(vec![PlayerCode::R, PlayerCode::R]),
+            seed: None,
+        };
+        let path = std::env::temp_dir().join("deckgym_gauntlet_checkpoint_test.json");
+        let path = path.to_str().unwrap();
+        fs::remove_file(path).ok();
+        let mut checkpoint = CheckpointConfig::new(path);
+        let run = |checkpoint: &CheckpointConfig| {
+            run_gauntlet_with_checkpoint(
+                &deck,
+                &meta,
+                &sim_config,
+                &ParallelConfig::default(),
+                None,
+                Some(checkpoint),
+                None::<fn()>,
+            )
+        };
+        let complete = run(&checkpoint).unwrap();
+        assert!(run(&checkpoint).is_err());
+
+        // Rewind the checkpoint to the start: an unseeded run replays the same games from it
+        let mut saved: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(path).unwrap()).unwrap();
+        let mut progress: GauntletProgress =
+            serde_json::from_value(saved["progress"].clone()).unwrap();
+        progress.games_done = 0;
+        progress.tallies = vec![MatchTally::default(); 2];
+        saved["progress"] = serde_json::to_value(&progress).unwrap();
+        fs::write(path, saved.to_string()).unwrap();
+
+        checkpoint.resume = true;
+        let resumed = run(&checkpoint).unwrap();
+        for (a, b) in complete.matchups.iter().zip(&resumed.matchups) {
+            assert_eq!(a.tally, b.tally);
+        }
+        fs::remove_file(path).ok();
+    }
+
+    #[test]
+    fn test_checkpoint_is_not_resumed_under_other_errata() {
+        let deck = Deck::from_file("example_decks/venusaur-exeggutor.txt").unwrap();
+        let meta = meta_with_weights(&[1.0]);
+        let sim_config = SimulationConfig {
+            num_games: 2,
+            players: Some(vec![PlayerCode::R, PlayerCode::R]),
+            seed: Some(1),
+        };
+        let path = std::env::temp_dir().join("deckgym_gauntlet_errata_checkpoint_test.json");
+        let path = path.to_str().unwrap();
+        fs::remove_file(path).ok();
+        let mut checkpoint = CheckpointConfig::new(path);
+        let errata = Errata::from_string("A1 165: hp 10").unwrap();
+        let run = |checkpoint: &CheckpointConfig, errata: Option<&Errata>| {
+            run_gauntlet_with_checkpoint(
+                &deck,
+                &meta,
+                &sim_config,
+                &ParallelConfig::default(),
+                errata,
+                Some(checkpoint),
+                None::<fn()>,
+            )
+        };
+        run(&checkpoint, None).unwrap();
+
+        checkpoint.resume = true;
+        assert!(run(&checkpoint, Some(&errata)).is_err());
+        assert!(run(&checkpoint, None).is_ok());
+        fs::remove_file(path).ok();
+    }
+}
@@ -6,6 +6,7 @@ use log::debug;
 use crate::{
     actions::{abilities::AbilityMechanic, ability_mechanic_from_effect, SimpleAction},
     card_ids::CardId,
+    custom_cards::trainer_card_id,
     effects::{CardEffect, TurnEffect},
     models::{Card, EnergyType, PlayedCard, TrainerCard, TrainerType, BASIC_STAGE},
     tools::{has_tool, tool_effects_equal},
@@ -50,14 +51,7 @@ pub fn to_playable_card(card: &crate::models::Card, played_this_turn: bool) -> P
             }
         }
     };
-    PlayedCard::new(
-        card.clone(),
-        total_hp,
-        total_hp,
-        vec![],
-        played_this_turn,
-        vec![],
-    )
+    PlayedCard::new(*card, total_hp, total_hp, vec![], played_this_turn, vec![])
 }
 
 pub(crate) fn get_stage(played_card: &PlayedCard) -> u8 {
@@ -157,7 +151,7 @@ pub(crate) fn on_play_to_bench(actor: usize, state: &mut State, card: &Card, in_
         if pokemon_card.stage == 0 {
             if let Some(stadium) = state.get_stadium() {
                 use crate::card_ids::CardId;
-                if let Some(stadium_id) = CardId::from_card_id(&stadium.get_id()) {
+                if let Some(stadium_id) = trainer_card_id(&stadium.get_id()) {
                     if stadium_id == CardId::B2154StartingPlains {
                         // Add +20 HP to the newly played Basic Pokemon
                         if let Some(pokemon) = state.in_play_pokemon[actor][in_play_idx].as_mut() {
@@ -718,20 +712,19 @@ fn calculate_type_boost_bonus(
         if let Some(ability_id) = AbilityId::from_pokemon_id(&pokemon.get_id()) {
             match ability_id {
                 // Lucario's Fighting Coach: +20 damage to Fighting-type attacks
-                AbilityId::A2092LucarioFightingCoach => {
-                    if attacker_energy_type == EnergyType::Fighting {
-                        debug!("Fighting Coach (Lucario): Increasing damage by 20");
-                        bonus += 20;
-                    }
+                AbilityId::A2092LucarioFightingCoach
+                    if attacker_energy_type == EnergyType::Fighting =>
+                {
+                    debug!("Fighting Coach (Lucario): Increasing damage by 20");
+                    bonus += 20;
                 }
                 // Aegislash's Cursed Metal: +30 damage to Psychic and Metal-type attacks
-                AbilityId::B1172AegislashCursedMetal => {
+                AbilityId::B1172AegislashCursedMetal
                     if attacker_energy_type == EnergyType::Psychic
-                        || attacker_energy_type == EnergyType::Metal
-                    {
-                        debug!("Cursed Metal (Aegislash): Increasing damage by 30");
-                        bonus += 30;
-                    }
+                        || attacker_energy_type == EnergyType::Metal =>
+                {
+                    debug!("Cursed Metal (Aegislash): Increasing damage by 30");
+                    bonus += 30;
                 }
                 _ => {}
             }
@@ -746,7 +739,7 @@ fn get_stadium_damage_modifier(state: &State, attacking_pokemon: &PlayedCard) ->
     use crate::card_ids::CardId;
 
     if let Some(stadium) = state.get_stadium() {
-        if let Some(CardId::B2153TrainingArea) = CardId::from_card_id(&stadium.get_id()) {
+        if let Some(CardId::B2153TrainingArea) = trainer_card_id(&stadium.get_id()) {
             // Training Area: Attacks used by Stage 1 Pokémon do +10 damage
             if let Card::Pokemon(pokemon_card) = &attacking_pokemon.card {
                 if pokemon_card.stage == 1 {
@@ -8,18 +8,15 @@ pub(crate) fn get_counterattack_damage(card: &PlayedCard) -> u32 {
     }
 
     // Some cards have it as an ability
-    let card_id = CardId::from_card_id(&card.card.get_id());
-    match card_id {
-        Some(CardId::A1061Poliwrath)
-        | Some(CardId::A1a056Druddigon)
-        | Some(CardId::A2b028Pawmot)
-        | Some(CardId::A3a052Ferrothorn)
-        | Some(CardId::A4a065Zangoose)
-        | Some(CardId::B1297Poliwrath)
-        | Some(CardId::PA054Pawmot) => {
-            total_damage += 20;
-        }
-        _ => {}
+    if let Some(
+        CardId::A1061Poliwrath
+        | CardId::A1a056Druddigon
+        | CardId::A2b028Pawmot
+        | CardId::A3a052Ferrothorn
+        | CardId::A4a065Zangoose,
+    ) = card.card.get_canonical_card_id()
+    {
+        total_damage += 20;
     }
 
     total_damage
@@ -33,15 +30,5 @@ pub(crate) fn should_poison_attacker(card: &PlayedCard) -> bool {
     }
 
     // Some cards have it as an ability (Dragalge ex's Poison Point)
-    let card_id = CardId::from_card_id(&card.card.get_id());
-    match card_id {
-        Some(CardId::B1160DragalgeEx)
-        | Some(CardId::B1263DragalgeEx)
-        | Some(CardId::B1281DragalgeEx) => {
-            return true;
-        }
-        _ => {}
-    }
-
-    false
+    card.card.get_canonical_card_id() == Some(CardId::B1160DragalgeEx)
 }
@@ -1,6 +1,7 @@
 use crate::{
     ability_ids::AbilityId,
     card_ids::CardId,
+    custom_cards::trainer_card_id,
     effects::{CardEffect, TurnEffect},
     models::{Card, EnergyType, PlayedCard},
     tools::has_tool,
@@ -69,7 +70,7 @@ pub(crate) fn get_retreat_cost(state: &State, card: &PlayedCard) -> Vec<EnergyTy
         // Peculiar Plaza Stadium: Psychic Pokémon's Retreat Cost is 2 less
         if pokemon_card.energy_type == EnergyType::Psychic {
             if let Some(stadium) = state.get_stadium() {
-                if let Some(stadium_id) = CardId::from_card_id(&stadium.get_id()) {
+                if let Some(stadium_id) = trainer_card_id(&stadium.get_id()) {
                     if stadium_id == CardId::B2155PeculiarPlaza {
                         // Reduce retreat cost by 2
                         normal_cost.pop();
@@ -187,7 +188,7 @@ mod tests {
         state.in_play_pokemon[player][0] = Some(rotom.clone());
 
         let retreat_cost =
-            get_retreat_cost(&state, &state.in_play_pokemon[player][0].as_ref().unwrap());
+            get_retreat_cost(&state, state.in_play_pokemon[player][0].as_ref().unwrap());
         assert!(
             !retreat_cost.is_empty(),
             "Rotom should have a retreat cost without Arceus"
@@ -198,7 +199,7 @@ mod tests {
         state.in_play_pokemon[player][1] = Some(arceus);
 
         let retreat_cost =
-            get_retreat_cost(&state, &state.in_play_pokemon[player][0].as_ref().unwrap());
+            get_retreat_cost(&state, state.in_play_pokemon[player][0].as_ref().unwrap());
         assert!(
             retreat_cost.is_empty(),
             "Rotom should have 0 retreat cost with Arceus in play"
@@ -209,7 +210,7 @@ mod tests {
         state.in_play_pokemon[player][1] = Some(arceus_ex);
 
         let retreat_cost =
-            get_retreat_cost(&state, &state.in_play_pokemon[player][0].as_ref().unwrap());
+            get_retreat_cost(&state, state.in_play_pokemon[player][0].as_ref().unwrap());
         assert!(
             retreat_cost.is_empty(),
             "Rotom should have 0 retreat cost with Arceus ex in play"
@@ -222,7 +223,7 @@ mod tests {
         state.in_play_pokemon[opponent][0] = Some(arceus);
 
         let retreat_cost =
-            get_retreat_cost(&state, &state.in_play_pokemon[player][0].as_ref().unwrap());
+            get_retreat_cost(&state, state.in_play_pokemon[player][0].as_ref().unwrap());
         assert!(
             !retreat_cost.is_empty(),
             "Rotom should have a retreat cost if Arceus is only on opponent's side"
@@ -0,0 +1,203 @@
+use std::collections::HashMap;
+use std::fs;
+use std::sync::LazyLock;
+
+use strum::IntoEnumIterator;
+
+use crate::{
+    card_equivalence::canonical_card_id, card_ids::CardId, database::get_card_ref, models::Card,
+    optimize::try_parse_card_id_string,
+};
+
+/// Pack points needed to get a card of the given rarity, or `None` if it can't be crafted
+/// (promo cards can only be obtained from events and shops).
+pub fn craft_cost(card: &Card) -> Option<u32> {
+    let (id, rarity) = match card {
+        Card::Pokemon(pokemon) => (&pokemon.id, &pokemon.rarity),
+        Card::Trainer(trainer) => (&trainer.id, &trainer.rarity),
+    };
+    if id.starts_with("P-") {
+        return None;
+    }
+    match rarity.as_str() {
+        "◊" => Some(35),
+        "◊◊" => Some(70),
+        "◊◊◊" => Some(150),
+        "◊◊◊◊" => Some(500),
+        "☆" => Some(400),
+        "☆☆" => Some(1250),
+        "☆☆☆" => Some(1500),
+        "*" => Some(1000),
+        "**" => Some(1350),
+        "♛" => Some(2500),
+        _ => None,
+    }
+}
+
+/// The cards a player owns, and how many pack points they are willing to spend on missing ones.
+///
+/// Every printing of a card counts towards the same total, since any of them can be played.
+/// Inventory files have one entry per line, either `<card id>, <count>` (e.g. "A1 225, 2")
+/// or a deck list line (e.g. "2 Sabrina A1 225"), so deck files work as inventories too.
+/// Empty lines, section headers and lines starting with `#` are ignored.
+#[derive(Debug, Clone, Default)]
+pub struct Inventory {
+    /// Owned copies, by canonical print
+    owned: HashMap<CardId, u32>,
+    /// Pack points available to craft cards that aren't owned (or not in enough copies)
+    pub craft_budget: u32,
+}
+
+impl Inventory {
+    pub fn from_file(path: &str) -> Result<Self, String> {
+        let contents = fs::read_to_string(path)
+            .map_err(|err| format!("Failed to read inventory {path}: {err}"))?;
+        Self::from_string(&contents)
+    }
+
+    pub fn from_string(contents: &str) -> Result<Self, String> {
+        let mut inventory = Inventory::default();
+        for line in contents.lines() {
+            let line = line.trim();
+            if line.is_empty()
+                || line.starts_with('#')
+                || line.starts_with("Energy:")
+                || line.starts_with("Pokémon:")
+                || line.starts_with("Trainer:")
+            {
+                continue;
+            }
+            let (card_id, count) = match line.split_once(',') {
+                Some((id, count)) => {
+                    let count = count
+                        .trim()
+                        .parse::<u32>()
+                        .map_err(|_| format!("Invalid count in inventory line: {line}"))?;
+                    (try_parse_card_id_string(id)?, count)
+                }
+                None => {
+                    let (count, card) = Card::from_str_with_count(line)?;
+                    (card.try_get_card_id()?, count)
+                }
+            };
+            inventory.add(card_id, count);
+        }
+        Ok(inventory)
+    }
+
+    pub fn with_craft_budget(mut self, craft_budget: u32) -> Self {
+        self.craft_budget = craft_budget;
+        self
+    }
+
+    pub fn add(&mut self, card_id: CardId, count: u32) {
+        *self.owned.entry(canonical_card_id(card_id)).or_default() += count;
+    }
+
+    /// Owned copies of a card, across all its printings
+    pub fn owned(&self, card_id: CardId) -> u32 {
+        self.owned
+            .get(&canonical_card_id(card_id))
+            .copied()
+            .unwrap_or(0)
+    }
+
+    /// Pack points needed to craft the copies of `cards` that aren't owned, using the cheapest
+    /// printing of each card, or `None` if some missing card can't be crafted
+    pub fn crafting_cost(&self, cards: &[CardId]) -> Option<u32> {
+        let mut needed: HashMap<CardId, u32> = HashMap::new();
+        for &card_id in cards {
+            *needed.entry(canonical_card_id(card_id)).or_default() += 1;
+        }
+        needed.into_iter().try_fold(0, |total, (card_id, count)| {
+            let missing = count.saturating_sub(self.owned(card_id));
+            if missing == 0 {
+                return Some(total);
+            }
+            Some(total + missing * CHEAPEST_CRAFT.get(&card_id).copied()?)
+        })
+    }
+
+    /// Whether `cards` can be put together from the owned cards and the craft budget
+    pub fn allows(&self, cards: &[CardId]) -> bool {
+        self.crafting_cost(cards)
+            .is_some_and(|cost| cost <= self.craft_budget)
+    }
+}
+
+/// Pack points to craft the cheapest printing of each card, by canonical print
+static CHEAPEST_CRAFT: LazyLock<HashMap<CardId, u32>> = LazyLock::new(|| {
+    let mut cheapest: HashMap<CardId, u32> = HashMap::new();
+    for card_id in CardId::iter() {
+        if let Some(cost) = craft_cost(get_card_ref(card_id)) {
+            cheapest
+                .entry(canonical_card_id(card_id))
+                .and_modify(|cheapest| *cheapest = (*cheapest).min(cost))
+                .or_insert(cost);
+        }
+    }
+    cheapest
+});
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        card_database::load_card_database_from_string, database::get_card_by_enum,
+        models::PokemonCard,
+    };
+
+    #[test]
+    fn test_parse_inventory_formats() {
+        let inventory = Inventory::from_string(
+            "# my binder\nA1 225, 2\nPokémon: 1\n1 Bulbasaur A1 001\nA1001, 1\n",
+        )
+        .unwrap();
+        assert_eq!(inventory.owned(CardId::A1225Sabrina), 2);
+        assert_eq!(inventory.owned(CardId::A1001Bulbasaur), 2);
+        assert_eq!(inventory.owned(CardId::A1002Ivysaur), 0);
+        assert!(Inventory::from_string("A1 225, two").is_err());
+    }
+
+    #[test]
+    fn test_runtime_cards_cant_be_owned() {
+        let Card::Pokemon(bulbasaur) = get_card_by_enum(CardId::A1001Bulbasaur) else {
+            panic!("Bulbasaur is a Pokémon");
+        };
+        let runtime_bulbasaur = Card::from(PokemonCard {
+            id: "Z6 001".to_string(),
+            ..bulbasaur.clone()
+        });
+        let json = serde_json::to_string(&[runtime_bulbasaur]).unwrap();
+        load_card_database_from_string(&json).unwrap();
+        let err = Inventory::from_string("1 Bulbasaur Z6 001").unwrap_err();
+        assert!(err.contains("Z6 001"), "{err}");
+    }
+
+    #[test]
+    fn test_reprints_share_owned_copies() {
+        // A2b 111 is a reprint of Poké Ball
+        let mut inventory = Inventory::default();
+        inventory.add(CardId::PA005PokeBall, 1);
+        inventory.add(CardId::A2b111PokeBall, 1);
+        assert_eq!(inventory.owned(CardId::PA005PokeBall), 2);
+        assert!(inventory.allows(&[CardId::PA005PokeBall, CardId::PA005PokeBall]));
+    }
+
+    #[test]
+    fn test_crafting_cost_and_budget() {
+        let mut inventory = Inventory::default();
+        inventory.add(CardId::A1001Bulbasaur, 1);
+        let cards = [
+            CardId::A1001Bulbasaur,
+            CardId::A1001Bulbasaur,
+            CardId::A1002Ivysaur,
+        ];
+        // One Bulbasaur (◊) and one Ivysaur (◊◊) are missing
+        assert_eq!(inventory.crafting_cost(&cards), Some(35 + 70));
+        assert!(!inventory.allows(&cards));
+        assert!(inventory.clone().with_craft_budget(105).allows(&cards));
+        // Promos can't be crafted
+        assert_eq!(inventory.crafting_cost(&[CardId::PA001Potion]), None);
+    }
+}
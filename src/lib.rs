@@ -1,28 +1,47 @@
 mod ability_ids;
 pub mod actions;
 mod attack_ids;
+pub mod card_database;
+pub mod card_equivalence;
 pub mod card_ids;
 pub mod card_logic;
+pub mod card_lookup;
+pub mod card_search;
 pub mod card_validation;
+pub mod checkpoint;
 pub mod combinatorics;
+pub mod custom_cards;
 pub mod database;
 pub mod deck;
+pub mod deck_analysis;
+pub mod deck_builder;
+pub mod deck_code;
 pub mod effects;
 pub mod encoding;
+pub mod errata;
 pub mod example_utils;
+pub mod format;
 pub mod game;
+pub mod game_results;
 pub mod gameplay_stats_collector;
+pub mod gauntlet;
 pub mod hooks;
+pub mod inventory;
+pub mod matrix;
 pub mod models;
 pub mod move_generation;
+pub mod odds;
 pub mod optimize;
 pub mod players;
+pub mod ratings;
 pub mod simulate;
 pub mod simulation_event_handler;
 pub mod state;
+pub mod statistics;
 pub mod temp_deck;
 pub mod test_helpers; // TODO: Compile/Expose only in test mode?
 pub mod tools;
+pub mod tune;
 
 pub use ability_ids::AbilityId;
 pub use attack_ids::AttackId;
@@ -32,10 +51,11 @@ pub use hooks::to_playable_card;
 pub use move_generation::generate_possible_actions;
 pub use move_generation::generate_possible_trainer_actions;
 pub use optimize::{
-    cli_optimize, optimize, optimize_with_configs, EnemyDeckConfig, OptimizationConfig,
-    ParallelConfig, SimulationConfig,
+    cli_optimize, optimize, optimize_successive_halving, optimize_with_configs, CombinationResult,
+    EnemyDeckConfig, OptimizationConfig, OptimizationReport, OptimizationStrategy, ParallelConfig,
+    SimulationConfig,
 };
-pub use simulate::{simulate, Simulation, SimulationCallbacks};
+pub use simulate::{simulate, simulate_with_options, Simulation, SimulationCallbacks};
 pub use simulation_event_handler::ComputedStats;
 pub use state::State;
 
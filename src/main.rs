@@ -1,18 +1,43 @@
-use clap::{ArgAction, Parser, Subcommand};
+use clap::{error::ErrorKind, ArgAction, Args, CommandFactory, Parser, Subcommand, ValueEnum};
 use colored::Colorize;
-use deckgym::optimize::{ParallelConfig, SimulationConfig};
+use deckgym::card_database::load_card_database;
+use deckgym::checkpoint::CheckpointConfig;
+use deckgym::custom_cards::load_custom_cards;
+use deckgym::database::get_card_by_enum;
+use deckgym::deck_analysis::print_deck_analysis;
+use deckgym::deck_builder::{cli_build_decks, parse_energy_types, CardPool, EvolutionConfig};
+use deckgym::errata::Errata;
+use deckgym::format::Format;
+use deckgym::game_results::GameResultsCollector;
+use deckgym::gauntlet::{cli_gauntlet, Meta};
+use deckgym::inventory::Inventory;
+use deckgym::matrix::{cli_matrix, cli_strategy_matrix, report_matrix, MatrixOutputs};
+use deckgym::odds::{deck_odds, print_deck_odds, OddsOptions};
+use deckgym::optimize::{
+    try_parse_card_id_string, OptimizationStrategy, ParallelConfig, SimulationConfig,
+};
 use deckgym::players::{parse_player_code, PlayerCode};
-use deckgym::simulate::initialize_logger;
-use deckgym::{cli_optimize, simulate, Deck};
+use deckgym::ratings::{matrix_from_records, RatingSubject};
+use deckgym::simulate::{cli_merge, initialize_logger, parse_shard, SimulateOptions};
+use deckgym::tune::cli_tune;
+use deckgym::{cli_optimize, simulate_with_options, Deck};
 use log::warn;
-use num_format::{Locale, ToFormattedString};
-use std::fs;
 
 #[derive(Parser, Debug)]
 #[command(author, version, about, long_about = None)]
 struct Cli {
     #[command(subcommand)]
     command: Commands,
+
+    /// Also load the cards this build doesn't have from a card database in the database.json
+    /// format (database.json if no path is given), e.g. a newer one with a new set
+    #[arg(long, global = true, num_args = 0..=1, default_missing_value = "database.json")]
+    card_database: Option<String>,
+
+    /// Also load hypothetical cards from a JSON file composing existing attack, ability and
+    /// trainer mechanics; they get the ids CUSTOM 001, CUSTOM 002, ... in file order
+    #[arg(long, global = true)]
+    custom_cards: Option<String>,
 }
 
 #[derive(Subcommand, Debug)]
@@ -47,10 +72,140 @@ enum Commands {
         #[arg(short = 'j', long)]
         threads: Option<usize>,
 
+        /// Write one row per game to this file (.jsonl, or .csv with the csv-export feature)
+        #[arg(long)]
+        export: Option<String>,
+
+        #[command(flatten)]
+        checkpoint: CheckpointArgs,
+
+        /// Only play shard I of N (e.g. "2/4"), each shard getting its own range of seeds
+        #[arg(long, value_parser = parse_shard, requires = "results")]
+        shard: Option<(usize, usize)>,
+
+        /// Write a result file that the merge command can combine with other shards
+        #[arg(long)]
+        results: Option<String>,
+
+        /// Errata file (e.g. `Mewtwo ex: hp -20` lines) changing card stats for every deck
+        #[arg(long)]
+        errata: Option<String>,
+
+        /// Increase verbosity (-v, -vv, -vvv, etc.)
+        #[arg(short, long, action = ArgAction::Count, default_value_t = 1)]
+        verbose: u8,
+    },
+    /// Combine the result files of a sharded simulate run into one report
+    Merge {
+        /// Result files written by simulate --results
+        #[arg(required = true)]
+        files: Vec<String>,
+
+        /// Write one row per game to this file (shards must have been run with --export)
+        #[arg(long)]
+        export: Option<String>,
+
         /// Increase verbosity (-v, -vv, -vvv, etc.)
         #[arg(short, long, action = ArgAction::Count, default_value_t = 1)]
         verbose: u8,
     },
+    /// Simulate a deck against a weighted metagame and summarize all matchups in one table
+    Gauntlet {
+        /// Path to the deck file
+        deck: String,
+
+        /// Meta file with one `<deck path>, <weight>[, <name>]` line per opponent
+        meta: String,
+
+        /// Total number of games, split across opponents by weight
+        #[arg(short, long)]
+        num: u32,
+
+        /// Players' strategies as a comma-separated list (e.g., "e2,e4" or "r,e5")
+        /// Available codes: aa, et, r, h, w, m, v, e<depth>, er
+        /// Example: e2 = ExpectiMiniMax with depth 2
+        #[arg(long, value_delimiter = ',', value_parser = parse_player_code)]
+        players: Option<Vec<PlayerCode>>,
+
+        /// Seed for random number generation
+        #[arg(short, long)]
+        seed: Option<u64>,
+
+        /// Run simulations in parallel
+        #[arg(short, long, default_value_t = false)]
+        parallel: bool,
+
+        /// Number of threads to use (defaults to number of CPU cores if not specified)
+        #[arg(short = 'j', long)]
+        threads: Option<usize>,
+
+        /// Errata file (e.g. `Mewtwo ex: hp -20` lines) changing card stats for every deck
+        #[arg(long)]
+        errata: Option<String>,
+
+        #[command(flatten)]
+        checkpoint: CheckpointArgs,
+
+        /// Increase verbosity (-v, -vv, -vvv, etc.)
+        #[arg(short, long, action = ArgAction::Count, default_value_t = 1)]
+        verbose: u8,
+    },
+    /// Play every deck in a folder against every other deck (both seat orders) and print the win-rate matrix
+    Matrix {
+        /// Folder containing the deck files
+        decks_folder: String,
+
+        /// Number of games per pairing and seat order
+        #[arg(short, long)]
+        num: u32,
+
+        /// Players' strategies as a comma-separated list (e.g., "e2,e4" or "r,e5")
+        /// Available codes: aa, et, r, h, w, m, v, e<depth>, er
+        /// Example: e2 = ExpectiMiniMax with depth 2
+        #[arg(long, value_delimiter = ',', value_parser = parse_player_code)]
+        players: Option<Vec<PlayerCode>>,
+
+        /// Seed for random number generation
+        #[arg(short, long)]
+        seed: Option<u64>,
+
+        /// Run simulations in parallel
+        #[arg(short, long, default_value_t = false)]
+        parallel: bool,
+
+        /// Number of threads to use (defaults to number of CPU cores if not specified)
+        #[arg(short = 'j', long)]
+        threads: Option<usize>,
+
+        /// Write the win-rate matrix to this CSV file
+        #[arg(long)]
+        csv: Option<String>,
+
+        /// Write the win-rate matrix and raw results to this JSON file
+        #[arg(long)]
+        json: Option<String>,
+
+        /// Errata file (e.g. `Mewtwo ex: hp -20` lines) changing card stats for every deck
+        #[arg(long)]
+        errata: Option<String>,
+
+        #[command(flatten)]
+        checkpoint: CheckpointArgs,
+
+        /// Increase verbosity (-v, -vv, -vvv, etc.)
+        #[arg(short, long, action = ArgAction::Count, default_value_t = 1)]
+        verbose: u8,
+    },
+    /// Inspect a single deck without simulating games
+    Deck {
+        #[command(subcommand)]
+        command: DeckCommand,
+    },
+    /// Fit Elo / Bradley-Terry ratings for player strategies or decks
+    Rate {
+        #[command(subcommand)]
+        target: RateTarget,
+    },
     /// Optimize an incomplete deck against enemy decks
     Optimize {
         /// Path to the incomplete deck file (missing up to 4 cards)
@@ -63,9 +218,150 @@ enum Commands {
         enemy_decks_folder: String,
 
         /// Number of simulations to run per enemy deck for each combination
+        /// (with --strategy halving, for the combinations that survive to the last round)
+        #[arg(short, long)]
+        num: u32,
+
+        /// How to spend the game budget: play every combination fully, or race them with successive halving
+        #[arg(long, value_enum, default_value_t = Strategy::Exhaustive)]
+        strategy: Strategy,
+
+        /// Number of best combinations to report with --strategy halving
+        #[arg(long, default_value_t = 5)]
+        top_k: usize,
+
+        /// Inventory file (`<card id>, <count>` or deck-list lines) limiting which cards can be used
+        #[arg(long)]
+        inventory: Option<String>,
+
+        /// Pack points that may be spent crafting cards missing from the inventory
+        #[arg(long)]
+        craft_budget: Option<u32>,
+
+        /// Format file (`Sets:`, `Packs:`, `Banned:` and `Limit:` lines) restricting which cards can be used
+        #[arg(long)]
+        format: Option<String>,
+
+        /// Players' strategies as a comma-separated list (e.g., "e2,e4" or "r,e5")
+        /// Available codes: aa, et, r, h, w, m, v, e<depth>, er
+        /// Example: e2 = ExpectiMiniMax with depth 2
+        #[arg(long, value_delimiter = ',', value_parser = parse_player_code)]
+        players: Option<Vec<PlayerCode>>,
+
+        /// Seed for random number generation
+        #[arg(short, long)]
+        seed: Option<u64>,
+
+        /// Run simulations in parallel
+        #[arg(short, long, default_value_t = false)]
+        parallel: bool,
+
+        /// Number of threads to use (defaults to number of CPU cores if not specified)
+        #[arg(short = 'j', long)]
+        threads: Option<usize>,
+
+        /// Write the ranked combinations with their per-opponent breakdown to this JSON file
+        #[arg(long)]
+        json: Option<String>,
+
+        /// Write the deck completed with the best combination to this deck file
+        #[arg(short, long)]
+        output: Option<String>,
+
+        #[command(flatten)]
+        checkpoint: CheckpointArgs,
+
+        /// Increase verbosity (-v, -vv, -vvv, etc.)
+        #[arg(short, long, action = ArgAction::Count, default_value_t = 1)]
+        verbose: u8,
+    },
+    /// Evaluate every single-card change (swaps with candidates and -1/+1 copies) of a complete deck
+    Tune {
+        /// Path to the deck file
+        deck: String,
+
+        /// Comma-separated list of candidate card IDs to swap in
+        candidate_cards: String,
+
+        /// Meta file (`<deck path>, <weight>[, <name>]` lines) or a folder of equally weighted decks
+        meta: String,
+
+        /// Number of games per variant, split across the meta by weight
         #[arg(short, long)]
         num: u32,
 
+        /// Inventory file (`<card id>, <count>` or deck-list lines) limiting which cards can be used
+        #[arg(long)]
+        inventory: Option<String>,
+
+        /// Pack points that may be spent crafting cards missing from the inventory
+        #[arg(long)]
+        craft_budget: Option<u32>,
+
+        /// Players' strategies as a comma-separated list (e.g., "e2,e4" or "r,e5")
+        /// Available codes: aa, et, r, h, w, m, v, e<depth>, er
+        /// Example: e2 = ExpectiMiniMax with depth 2
+        #[arg(long, value_delimiter = ',', value_parser = parse_player_code)]
+        players: Option<Vec<PlayerCode>>,
+
+        /// Seed for random number generation
+        #[arg(short, long)]
+        seed: Option<u64>,
+
+        /// Run simulations in parallel
+        #[arg(short, long, default_value_t = false)]
+        parallel: bool,
+
+        /// Number of threads to use (defaults to number of CPU cores if not specified)
+        #[arg(short = 'j', long)]
+        threads: Option<usize>,
+
+        /// Increase verbosity (-v, -vv, -vvv, etc.)
+        #[arg(short, long, action = ArgAction::Count, default_value_t = 1)]
+        verbose: u8,
+    },
+    /// Build complete decks from a card pool by evolutionary search against a meta
+    Build {
+        /// Meta file (`<deck path>, <weight>[, <name>]` lines) or a folder of equally weighted decks
+        meta: String,
+
+        /// Build from every implemented card of these energy types plus Colorless and trainers
+        /// (comma-separated, e.g. "grass,psychic")
+        #[arg(long, conflicts_with = "pool", required_unless_present = "pool")]
+        energy: Option<String>,
+
+        /// File with the card IDs to build from (comma- or newline-separated)
+        #[arg(long)]
+        pool: Option<String>,
+
+        /// Number of games per evaluation of a deck, split across the meta by weight
+        #[arg(short, long)]
+        num: u32,
+
+        /// Number of decks alive in every generation
+        #[arg(long, default_value_t = 16)]
+        population: usize,
+
+        /// Number of generations to evolve
+        #[arg(long, default_value_t = 10)]
+        generations: usize,
+
+        /// Folder to write the Pareto-best decks to
+        #[arg(short, long, default_value = "built_decks")]
+        out: String,
+
+        /// Inventory file (`<card id>, <count>` or deck-list lines) limiting which cards can be used
+        #[arg(long)]
+        inventory: Option<String>,
+
+        /// Pack points that may be spent crafting cards missing from the inventory
+        #[arg(long)]
+        craft_budget: Option<u32>,
+
+        /// Format file (`Sets:`, `Packs:`, `Banned:` and `Limit:` lines) restricting which cards can be used
+        #[arg(long)]
+        format: Option<String>,
+
         /// Players' strategies as a comma-separated list (e.g., "e2,e4" or "r,e5")
         /// Available codes: aa, et, r, h, w, m, v, e<depth>, er
         /// Example: e2 = ExpectiMiniMax with depth 2
@@ -90,104 +386,170 @@ enum Commands {
     },
 }
 
-/// Simulate games between one deck and multiple decks in a folder
-fn simulate_against_folder(
-    deck_a_path: &str,
-    decks_folder: &str,
-    players: Option<Vec<PlayerCode>>,
-    total_num_simulations: u32,
-    seed: Option<u64>,
-    parallel: bool,
-    num_threads: Option<usize>,
-) {
-    // Read all deck files from the folder
-    let deck_paths: Vec<String> = fs::read_dir(decks_folder)
-        .expect("Failed to read decks folder")
-        .filter_map(|entry| {
-            let entry = entry.ok()?;
-            if entry.path().is_file() {
-                Some(entry.path().to_str()?.to_string())
-            } else {
-                None
-            }
-        })
-        .collect();
-
-    // Load and validate decks
-    let valid_decks: Vec<(String, Deck)> = deck_paths
-        .iter()
-        .filter_map(|path| {
-            let deck = Deck::from_file(path).ok()?;
-            if deck.cards.len() == 20 {
-                Some((path.clone(), deck))
-            } else {
-                warn!("Skipping deck {} (invalid)", path);
-                None
-            }
-        })
-        .collect();
+#[derive(Subcommand, Debug)]
+enum RateTarget {
+    /// Play every player strategy against every other one on a fixed deck pairing and rate them
+    Players {
+        /// Deck piloted by player 0
+        deck_a: String,
 
-    if valid_decks.is_empty() {
-        warn!("No valid decks found in folder: {}", decks_folder);
-        return;
-    }
+        /// Deck piloted by player 1
+        deck_b: String,
 
-    warn!(
-        "Found {} valid deck files in folder",
-        valid_decks.len().to_formatted_string(&Locale::en)
-    );
-
-    // Calculate games per deck (distribute evenly)
-    let num_decks = valid_decks.len() as u32;
-    let games_per_deck = total_num_simulations / num_decks;
-    let remainder = total_num_simulations % num_decks;
-
-    warn!(
-        "Running {} total games ({} per deck)",
-        total_num_simulations.to_formatted_string(&Locale::en),
-        games_per_deck.to_formatted_string(&Locale::en)
-    );
-
-    // Run simulations against each deck
-    for (i, (deck_path, _)) in valid_decks.iter().enumerate() {
-        let deck_name = deck_path.split('/').next_back().unwrap_or(deck_path);
-        let games_for_this_deck = if i < remainder as usize {
-            games_per_deck + 1
-        } else {
-            games_per_deck
-        };
-
-        if games_for_this_deck == 0 {
-            continue;
-        }
+        /// Strategies to rate as a comma-separated list (e.g., "e2,e3,m,v")
+        /// Available codes: aa, et, r, h, w, m, v, e<depth>, er
+        #[arg(long, value_delimiter = ',', value_parser = parse_player_code, required = true)]
+        players: Vec<PlayerCode>,
 
-        warn!("\n{}", "=".repeat(60));
-        warn!(
-            "Simulating against deck {}/{}: {}",
-            i + 1,
-            num_decks,
-            deck_name
-        );
-        warn!("{}", "=".repeat(60));
-
-        simulate(
-            deck_a_path,
-            deck_path,
-            players.clone(),
-            games_for_this_deck,
-            seed,
-            parallel,
-            num_threads,
-        );
+        /// Number of games per pairing and seat order
+        #[arg(short, long)]
+        num: u32,
+
+        /// Seed for random number generation
+        #[arg(short, long)]
+        seed: Option<u64>,
+
+        /// Run simulations in parallel
+        #[arg(short, long, default_value_t = false)]
+        parallel: bool,
+
+        /// Number of threads to use (defaults to number of CPU cores if not specified)
+        #[arg(short = 'j', long)]
+        threads: Option<usize>,
+
+        /// Write the win-rate matrix and ratings to this JSON file
+        #[arg(long)]
+        json: Option<String>,
+
+        /// Increase verbosity (-v, -vv, -vvv, etc.)
+        #[arg(short, long, action = ArgAction::Count, default_value_t = 1)]
+        verbose: u8,
+    },
+    /// Rate decks or player strategies from a `simulate --export` JSON Lines file
+    Results {
+        /// Path to the exported game results (.jsonl)
+        results: String,
+
+        /// What to rate
+        #[arg(long, value_enum, default_value_t = RateBy::Decks)]
+        by: RateBy,
+
+        /// Increase verbosity (-v, -vv, -vvv, etc.)
+        #[arg(short, long, action = ArgAction::Count, default_value_t = 1)]
+        verbose: u8,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+enum DeckCommand {
+    /// Print a short, URL-safe code to share the deck
+    Code {
+        /// Path to the deck file
+        deck: String,
+
+        /// Increase verbosity (-v, -vv, -vvv, etc.)
+        #[arg(short, long, action = ArgAction::Count, default_value_t = 1)]
+        verbose: u8,
+    },
+    /// Turn a deck code back into a deck file
+    Decode {
+        /// Code printed by `deck code`
+        code: String,
+
+        /// Write the deck to this file instead of printing it
+        #[arg(short, long)]
+        output: Option<String>,
+
+        /// Increase verbosity (-v, -vv, -vvv, etc.)
+        #[arg(short, long, action = ArgAction::Count, default_value_t = 1)]
+        verbose: u8,
+    },
+    /// Report a deck's composition, energy coverage, broken evolution lines and unimplemented cards
+    Inspect {
+        /// Path to the deck file
+        deck: String,
+
+        /// Format file to also check the deck against
+        #[arg(long)]
+        format: Option<String>,
+
+        /// Increase verbosity (-v, -vv, -vvv, etc.)
+        #[arg(short, long, action = ArgAction::Count, default_value_t = 1)]
+        verbose: u8,
+    },
+    /// Exact opening-hand odds and chances of drawing specific cards by each turn
+    Odds {
+        /// Path to the deck file
+        deck: String,
+
+        /// Comma-separated card IDs to look for; repeat an ID to require several copies
+        #[arg(long, default_value = "")]
+        cards: String,
+
+        /// Last game turn to report
+        #[arg(short, long, default_value_t = 4)]
+        turns: u32,
+
+        /// Ignore Poké Ball and Professor's Research instead of playing them as soon as possible
+        #[arg(long, default_value_t = false)]
+        no_draw_trainers: bool,
+
+        /// Increase verbosity (-v, -vv, -vvv, etc.)
+        #[arg(short, long, action = ArgAction::Count, default_value_t = 1)]
+        verbose: u8,
+    },
+}
+
+/// Checkpointing of long runs
+#[derive(Args, Debug)]
+struct CheckpointArgs {
+    /// Periodically save partial results to this file
+    #[arg(long)]
+    checkpoint: Option<String>,
+
+    /// Seconds between two checkpoint saves
+    #[arg(long, default_value_t = 60, requires = "checkpoint")]
+    checkpoint_interval: u64,
+
+    /// Continue the run saved in the --checkpoint file
+    #[arg(long, requires = "checkpoint")]
+    resume: bool,
+}
+
+impl CheckpointArgs {
+    fn into_config(self) -> Option<CheckpointConfig> {
+        Some(CheckpointConfig {
+            path: self.checkpoint?,
+            interval: std::time::Duration::from_secs(self.checkpoint_interval),
+            resume: self.resume,
+        })
     }
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug)]
+enum Strategy {
+    Exhaustive,
+    Halving,
+}
 
-    warn!("\n{}", "=".repeat(60));
-    warn!("All simulations complete!");
-    warn!("{}", "=".repeat(60));
+#[derive(ValueEnum, Clone, Copy, Debug)]
+enum RateBy {
+    Decks,
+    Players,
 }
 
 fn main() {
     let cli = Cli::parse();
+    if let Some(path) = &cli.card_database {
+        if let Err(err) = load_card_database(path) {
+            Cli::command().error(ErrorKind::Io, err).exit();
+        }
+    }
+    if let Some(path) = &cli.custom_cards {
+        if let Err(err) = load_custom_cards(path) {
+            Cli::command().error(ErrorKind::Io, err).exit();
+        }
+    }
 
     // Branch depending on the chosen subcommand.
     match cli.command {
@@ -199,45 +561,350 @@ fn main() {
             seed,
             parallel,
             threads,
+            export,
+            checkpoint,
+            shard,
+            results,
+            errata,
             verbose,
         } => {
+            let checkpoint = checkpoint.into_config();
             initialize_logger(verbose);
 
             warn!("Welcome to {} simulation!", "deckgym".blue().bold());
 
+            let errata = match load_errata(errata) {
+                Ok(errata) => errata,
+                Err(err) => {
+                    warn!("{err}");
+                    return;
+                }
+            };
+
             // Check if deck_b_or_folder is a directory
             let path = std::path::Path::new(&deck_b_or_folder);
             if path.is_dir() {
-                simulate_against_folder(
-                    &deck_a,
-                    &deck_b_or_folder,
-                    players,
-                    num,
-                    seed,
-                    parallel,
-                    threads,
-                );
+                if export.is_some() || results.is_some() {
+                    Cli::command()
+                        .error(
+                            ErrorKind::ArgumentConflict,
+                            "--export and --results are only supported when simulating against a single deck",
+                        )
+                        .exit();
+                }
+                match Meta::from_folder(&deck_b_or_folder) {
+                    Ok(meta) => {
+                        cli_gauntlet(
+                            &deck_a,
+                            &meta,
+                            SimulationConfig {
+                                num_games: num,
+                                players,
+                                seed,
+                            },
+                            ParallelConfig {
+                                enabled: parallel,
+                                num_threads: threads,
+                            },
+                            errata.as_ref(),
+                            checkpoint.as_ref(),
+                        );
+                    }
+                    Err(err) => warn!("{err}"),
+                }
             } else {
-                simulate(
+                simulate_with_options(
                     &deck_a,
                     &deck_b_or_folder,
-                    players,
-                    num,
-                    seed,
-                    parallel,
-                    threads,
+                    SimulationConfig {
+                        num_games: num,
+                        players,
+                        seed,
+                    },
+                    ParallelConfig {
+                        enabled: parallel,
+                        num_threads: threads,
+                    },
+                    &SimulateOptions {
+                        export_path: export,
+                        checkpoint,
+                        shard,
+                        results_path: results,
+                        errata,
+                    },
                 );
             }
         }
+        Commands::Merge {
+            files,
+            export,
+            verbose,
+        } => {
+            initialize_logger(verbose);
+            cli_merge(&files, export.as_deref());
+        }
+        Commands::Gauntlet {
+            deck,
+            meta,
+            num,
+            players,
+            seed,
+            parallel,
+            threads,
+            errata,
+            checkpoint,
+            verbose,
+        } => {
+            initialize_logger(verbose);
+
+            warn!("Welcome to {} gauntlet!", "deckgym".blue().bold());
+
+            let errata = match load_errata(errata) {
+                Ok(errata) => errata,
+                Err(err) => {
+                    warn!("{err}");
+                    return;
+                }
+            };
+            match Meta::from_file(&meta) {
+                Ok(meta) => {
+                    cli_gauntlet(
+                        &deck,
+                        &meta,
+                        SimulationConfig {
+                            num_games: num,
+                            players,
+                            seed,
+                        },
+                        ParallelConfig {
+                            enabled: parallel,
+                            num_threads: threads,
+                        },
+                        errata.as_ref(),
+                        checkpoint.into_config().as_ref(),
+                    );
+                }
+                Err(err) => warn!("{err}"),
+            }
+        }
+        Commands::Matrix {
+            decks_folder,
+            num,
+            players,
+            seed,
+            parallel,
+            threads,
+            csv,
+            json,
+            errata,
+            checkpoint,
+            verbose,
+        } => {
+            initialize_logger(verbose);
+
+            warn!("Welcome to {} matrix!", "deckgym".blue().bold());
+
+            let errata = match load_errata(errata) {
+                Ok(errata) => errata,
+                Err(err) => {
+                    warn!("{err}");
+                    return;
+                }
+            };
+            match Meta::from_folder(&decks_folder) {
+                Ok(meta) => {
+                    let decks: Vec<_> = meta
+                        .entries
+                        .into_iter()
+                        .map(|entry| (entry.name, entry.deck))
+                        .collect();
+                    cli_matrix(
+                        &decks,
+                        SimulationConfig {
+                            num_games: num,
+                            players,
+                            seed,
+                        },
+                        ParallelConfig {
+                            enabled: parallel,
+                            num_threads: threads,
+                        },
+                        &MatrixOutputs {
+                            csv_path: csv,
+                            json_path: json,
+                        },
+                        errata.as_ref(),
+                        checkpoint.into_config().as_ref(),
+                    );
+                }
+                Err(err) => warn!("{err}"),
+            }
+        }
+        Commands::Deck { command } => match command {
+            DeckCommand::Code { deck, verbose } => {
+                initialize_logger(verbose);
+
+                match Deck::from_file(&deck) {
+                    Ok(deck) => match deck.to_code() {
+                        Ok(code) => warn!("{code}"),
+                        Err(err) => warn!("{err}"),
+                    },
+                    Err(err) => warn!("{err}"),
+                }
+            }
+            DeckCommand::Decode {
+                code,
+                output,
+                verbose,
+            } => {
+                initialize_logger(verbose);
+
+                let deck = match Deck::from_code(&code) {
+                    Ok(deck) => deck,
+                    Err(err) => {
+                        warn!("{err}");
+                        return;
+                    }
+                };
+                match output {
+                    Some(path) => match std::fs::write(&path, deck.to_deckgym_string()) {
+                        Ok(()) => warn!("Wrote deck to {path}"),
+                        Err(err) => warn!("Failed to write {path}: {err}"),
+                    },
+                    None => warn!("{}", deck.to_deckgym_string().trim_end()),
+                }
+            }
+            DeckCommand::Inspect {
+                deck,
+                format,
+                verbose,
+            } => {
+                initialize_logger(verbose);
+
+                match (Deck::from_file(&deck), load_format(format)) {
+                    (Ok(deck), Ok(format)) => {
+                        let mut analysis = deck.analyze();
+                        if let Some(format) = format {
+                            analysis.issues.extend(deck.validate_in_format(&format));
+                        }
+                        print_deck_analysis(&analysis)
+                    }
+                    (Err(err), _) | (_, Err(err)) => warn!("{err}"),
+                }
+            }
+            DeckCommand::Odds {
+                deck,
+                cards,
+                turns,
+                no_draw_trainers,
+                verbose,
+            } => {
+                initialize_logger(verbose);
+
+                let targets = cards
+                    .split(',')
+                    .map(str::trim)
+                    .filter(|id| !id.is_empty())
+                    .map(try_parse_card_id_string)
+                    .collect::<Result<Vec<_>, _>>();
+                let options = OddsOptions {
+                    turns,
+                    draw_trainers: !no_draw_trainers,
+                };
+                let odds = targets.and_then(|targets| {
+                    let deck = Deck::from_file(&deck)?;
+                    Ok((deck_odds(&deck, &targets, &options)?, targets))
+                });
+                match odds {
+                    Ok((odds, targets)) => print_deck_odds(&odds, &targets),
+                    Err(err) => warn!("{err}"),
+                }
+            }
+        },
+        Commands::Rate { target } => match target {
+            RateTarget::Players {
+                deck_a,
+                deck_b,
+                players,
+                num,
+                seed,
+                parallel,
+                threads,
+                json,
+                verbose,
+            } => {
+                initialize_logger(verbose);
+
+                warn!("Welcome to {} ratings!", "deckgym".blue().bold());
+
+                let decks =
+                    Deck::from_file(&deck_a).and_then(|a| Ok((a, Deck::from_file(&deck_b)?)));
+                match decks {
+                    Ok((deck_a, deck_b)) => {
+                        cli_strategy_matrix(
+                            &deck_a,
+                            &deck_b,
+                            &players,
+                            SimulationConfig {
+                                num_games: num,
+                                players: None,
+                                seed,
+                            },
+                            ParallelConfig {
+                                enabled: parallel,
+                                num_threads: threads,
+                            },
+                            &MatrixOutputs {
+                                csv_path: None,
+                                json_path: json,
+                            },
+                        );
+                    }
+                    Err(err) => warn!("Failed to load decks: {err}"),
+                }
+            }
+            RateTarget::Results {
+                results,
+                by,
+                verbose,
+            } => {
+                initialize_logger(verbose);
+
+                match GameResultsCollector::read_jsonl(&results) {
+                    Ok(records) => {
+                        let subject = match by {
+                            RateBy::Decks => RatingSubject::Decks,
+                            RateBy::Players => RatingSubject::Players,
+                        };
+                        let matrix = matrix_from_records(&records, subject);
+                        warn!(
+                            "Rating {} entries from {} games",
+                            matrix.names.len(),
+                            records.len()
+                        );
+                        report_matrix(&matrix, &MatrixOutputs::default());
+                    }
+                    Err(err) => warn!("Failed to read {results}: {err}"),
+                }
+            }
+        },
         Commands::Optimize {
             incomplete_deck,
             candidate_cards,
             enemy_decks_folder,
             num,
+            strategy,
+            top_k,
+            inventory,
+            craft_budget,
+            format,
             players,
             seed,
             parallel,
             threads,
+            json,
+            output,
+            checkpoint,
             verbose,
         } => {
             initialize_logger(verbose);
@@ -254,13 +921,172 @@ fn main() {
                 num_threads: threads,
             };
 
-            cli_optimize(
-                &incomplete_deck,
-                &candidate_cards,
-                &enemy_decks_folder,
-                sim_config,
-                parallel_config,
-            );
+            match (load_inventory(inventory, craft_budget), load_format(format)) {
+                (Ok(inventory), Ok(format)) => {
+                    let report = cli_optimize(
+                        &incomplete_deck,
+                        &candidate_cards,
+                        &enemy_decks_folder,
+                        sim_config,
+                        parallel_config,
+                        match strategy {
+                            Strategy::Exhaustive => OptimizationStrategy::Exhaustive,
+                            Strategy::Halving => OptimizationStrategy::SuccessiveHalving { top_k },
+                        },
+                        inventory,
+                        format,
+                        checkpoint.into_config(),
+                    );
+                    if let Some(path) = json {
+                        match report.write_json(&path) {
+                            Ok(()) => warn!("Wrote optimization report to {path}"),
+                            Err(err) => warn!("{err}"),
+                        }
+                    }
+                    if let (Some(path), Some(best)) = (output, report.best()) {
+                        let written = Deck::from_file(&incomplete_deck).and_then(|mut deck| {
+                            deck.cards
+                                .extend(best.cards.iter().map(|&id| get_card_by_enum(id)));
+                            std::fs::write(&path, deck.to_deckgym_string())
+                                .map_err(|err| format!("Failed to write {path}: {err}"))
+                        });
+                        match written {
+                            Ok(()) => warn!("Wrote the completed deck to {path}"),
+                            Err(err) => warn!("{err}"),
+                        }
+                    }
+                }
+                (Err(err), _) | (_, Err(err)) => warn!("{err}"),
+            }
+        }
+        Commands::Tune {
+            deck,
+            candidate_cards,
+            meta,
+            num,
+            inventory,
+            craft_budget,
+            players,
+            seed,
+            parallel,
+            threads,
+            verbose,
+        } => {
+            initialize_logger(verbose);
+
+            warn!("Welcome to {} tuner!", "deckgym".blue().bold());
+
+            match (load_meta(&meta), load_inventory(inventory, craft_budget)) {
+                (Ok(meta), Ok(inventory)) => {
+                    cli_tune(
+                        &deck,
+                        &candidate_cards,
+                        inventory,
+                        &meta,
+                        SimulationConfig {
+                            num_games: num,
+                            players,
+                            seed,
+                        },
+                        ParallelConfig {
+                            enabled: parallel,
+                            num_threads: threads,
+                        },
+                    );
+                }
+                (Err(err), _) | (_, Err(err)) => warn!("{err}"),
+            }
+        }
+        Commands::Build {
+            meta,
+            energy,
+            pool,
+            num,
+            population,
+            generations,
+            out,
+            inventory,
+            craft_budget,
+            format,
+            players,
+            seed,
+            parallel,
+            threads,
+            verbose,
+        } => {
+            initialize_logger(verbose);
+
+            warn!("Welcome to {} deck builder!", "deckgym".blue().bold());
+
+            let meta = load_meta(&meta);
+            let pool = match (energy, pool) {
+                (Some(energy), _) => {
+                    parse_energy_types(&energy).map(|types| CardPool::from_energy_types(&types))
+                }
+                (None, Some(pool)) => CardPool::from_file(&pool),
+                (None, None) => Err("Either --energy or --pool is required".to_string()),
+            };
+            let pool = pool.and_then(|pool| {
+                let pool = match load_inventory(inventory, craft_budget)? {
+                    Some(inventory) => pool.with_inventory(inventory),
+                    None => pool,
+                };
+                Ok(match load_format(format)? {
+                    Some(format) => pool.with_format(format),
+                    None => pool,
+                })
+            });
+            match (meta, pool) {
+                (Ok(meta), Ok(pool)) => cli_build_decks(
+                    &pool,
+                    &meta,
+                    SimulationConfig {
+                        num_games: num,
+                        players,
+                        seed,
+                    },
+                    EvolutionConfig {
+                        population_size: population,
+                        generations,
+                    },
+                    ParallelConfig {
+                        enabled: parallel,
+                        num_threads: threads,
+                    },
+                    &out,
+                ),
+                (Err(err), _) | (_, Err(err)) => warn!("{err}"),
+            }
         }
     }
 }
+
+/// Meta from a meta file, or every deck of a folder with equal weights
+fn load_meta(path: &str) -> Result<Meta, String> {
+    if std::path::Path::new(path).is_dir() {
+        Meta::from_folder(path)
+    } else {
+        Meta::from_file(path)
+    }
+}
+
+/// Inventory from a file and/or a craft budget; a budget alone means nothing is owned yet
+fn load_inventory(
+    path: Option<String>,
+    craft_budget: Option<u32>,
+) -> Result<Option<Inventory>, String> {
+    let inventory = match (path, craft_budget) {
+        (None, None) => return Ok(None),
+        (Some(path), _) => Inventory::from_file(&path)?,
+        (None, Some(_)) => Inventory::default(),
+    };
+    Ok(Some(inventory.with_craft_budget(craft_budget.unwrap_or(0))))
+}
+
+fn load_format(path: Option<String>) -> Result<Option<Format>, String> {
+    path.map(|path| Format::from_file(&path)).transpose()
+}
+
+fn load_errata(path: Option<String>) -> Result<Option<Errata>, String> {
+    path.map(|path| Errata::from_file(&path)).transpose()
+}
@@ -0,0 +1,532 @@
+use std::fs;
+
+use log::warn;
+use num_format::{Locale, ToFormattedString};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    checkpoint::{deck_card_ids, play_in_batches, player_names, Checkpoint, CheckpointConfig},
+    errata::Errata,
+    optimize::{ParallelConfig, SimulationConfig},
+    players::{fill_code_array, PlayerCode},
+    ratings::{fit_ratings, print_ratings},
+    simulate::{create_progress_bar, game_seed, play_game},
+    state::GameOutcome,
+    statistics::MatchTally,
+    Deck,
+};
+
+/// Round-robin results between a pool of decks.
+///
+/// `tallies[i][j]` holds deck `i`'s results against deck `j` over both seat orders,
+/// so `tallies[j][i]` is always its flipped counterpart. The diagonal stays empty.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MatchupMatrix {
+    pub names: Vec<String>,
+    pub tallies: Vec<Vec<MatchTally>>,
+}
+
+impl MatchupMatrix {
+    pub fn new(names: Vec<String>) -> Self {
+        let n = names.len();
+        MatchupMatrix {
+            names,
+            tallies: vec![vec![MatchTally::default(); n]; n],
+        }
+    }
+
+    /// Records a game where deck `seat_0` sat as player 0 and deck `seat_1` as player 1
+    pub fn record(&mut self, seat_0: usize, seat_1: usize, outcome: Option<GameOutcome>) {
+        self.tallies[seat_0][seat_1].record(outcome, 0);
+        self.tallies[seat_1][seat_0].record(outcome, 1);
+    }
+
+    /// Win rate of deck `i` against deck `j`, `None` if they haven't played
+    pub fn win_rate(&self, i: usize, j: usize) -> Option<f64> {
+        let tally = &self.tallies[i][j];
+        (tally.games() > 0).then(|| tally.win_rate())
+    }
+
+    /// Mean of deck `i`'s win rates against every other deck (each opponent counts the same)
+    pub fn average_vs_field(&self, i: usize) -> f64 {
+        let rates: Vec<f64> = (0..self.names.len())
+            .filter_map(|j| self.win_rate(i, j))
+            .collect();
+        if rates.is_empty() {
+            0.0
+        } else {
+            rates.iter().sum::<f64>() / rates.len() as f64
+        }
+    }
+
+    /// All results of deck `i` against the rest of the pool
+    pub fn total_vs_field(&self, i: usize) -> MatchTally {
+        let mut total = MatchTally::default();
+        for tally in &self.tallies[i] {
+            total.merge(tally);
+        }
+        total
+    }
+
+    /// Win-rate matrix as CSV: one row per deck, one column per opponent and a `vs_field` column
+    pub fn to_csv_string(&self) -> String {
+        let mut csv = String::from("deck");
+        for name in &self.names {
+            csv.push(',');
+            csv.push_str(&csv_field(name));
+        }
+        csv.push_str(",vs_field\n");
+        for (i, name) in self.names.iter().enumerate() {
+            csv.push_str(&csv_field(name));
+            for j in 0..self.names.len() {
+                csv.push(',');
+                if let Some(rate) = self.win_rate(i, j) {
+                    csv.push_str(&format!("{rate:.4}"));
+                }
+            }
+            csv.push_str(&format!(",{:.4}\n", self.average_vs_field(i)));
+        }
+        csv
+    }
+
+    /// Names, raw tallies, win-rate matrix and per-deck averages as JSON
+    pub fn to_json(&self) -> serde_json::Value {
+        let n = self.names.len();
+        serde_json::json!({
+            "names": self.names,
+            "tallies": self.tallies,
+            "win_rates": (0..n)
+                .map(|i| (0..n).map(|j| self.win_rate(i, j)).collect::<Vec<_>>())
+                .collect::<Vec<_>>(),
+            "average_vs_field": (0..n).map(|i| self.average_vs_field(i)).collect::<Vec<_>>(),
+            "ratings": fit_ratings(self),
+        })
+    }
+}
+
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// Plays every deck against every other deck, `sim_config.num_games` times in each seat order.
+///
+/// All games of all pairings are scheduled together, so parallel runs keep every thread busy.
+/// `on_game_complete` is called after every game, e.g. to advance a progress bar.
+pub fn run_matrix<F>(
+    decks: &[(String, Deck)],
+    sim_config: &SimulationConfig,
+    parallel_config: &ParallelConfig,
+    on_game_complete: Option<F>,
+) -> MatchupMatrix
+where
+    F: Fn() + Sync,
+{
+    run_matrix_with_checkpoint(
+        decks,
+        sim_config,
+        parallel_config,
+        None,
+        None,
+        on_game_complete,
+    )
+    .expect("Matrices without a checkpoint can't fail")
+}
+
+/// Like `run_matrix`, but plays every deck with the `errata` (if any), and saves its progress
+/// to `checkpoint` (if any) and resumes from it
+pub fn run_matrix_with_checkpoint<F>(
+    decks: &[(String, Deck)],
+    sim_config: &SimulationConfig,
+    parallel_config: &ParallelConfig,
+    errata: Option<&Errata>,
+    checkpoint: Option<&CheckpointConfig>,
+    on_game_complete: Option<F>,
+) -> Result<MatchupMatrix, String>
+where
+    F: Fn() + Sync,
+{
+    let mut patched_decks = None;
+    if let Some(errata) = errata {
+        let mut decks = decks.to_vec();
+        for (_, deck) in decks.iter_mut() {
+            errata.apply(deck);
+        }
+        patched_decks = Some(decks);
+    }
+    let decks = patched_decks.as_deref().unwrap_or(decks);
+
+    let player_codes = fill_code_array(sim_config.players.clone());
+    let checkpoint = checkpoint.map(|config| {
+        let mut run = serde_json::json!({
+            "decks": decks
+                .iter()
+                .map(|(name, deck)| (name, deck_card_ids(deck)))
+                .collect::<Vec<_>>(),
+            "num_games": sim_config.num_games,
+            "players": player_names(&sim_config.players),
+            "seed": sim_config.seed,
+        });
+        // Card ids stay the same under errata, so its changes tell runs apart
+        if let Some(errata) = errata.filter(|errata| !errata.is_empty()) {
+            run["errata"] = serde_json::json!(errata.describe());
+        }
+        (config, run)
+    });
+    run_round_robin(
+        decks.iter().map(|(name, _)| name.clone()).collect(),
+        sim_config,
+        parallel_config,
+        checkpoint,
+        |seat_0, seat_1, seed| {
+            play_game(
+                &decks[seat_0].1,
+                &decks[seat_1].1,
+                player_codes.clone(),
+                seed,
+            )
+            .state
+            .winner
+        },
+        on_game_complete,
+    )
+}
+
+/// Plays every player strategy against every other one, `sim_config.num_games` times in
+/// each seat order. Player 0 always pilots `deck_a` and player 1 `deck_b`, so every strategy
+/// plays both decks. `sim_config.players` is ignored.
+pub fn run_strategy_matrix<F>(
+    deck_a: &Deck,
+    deck_b: &Deck,
+    strategies: &[PlayerCode],
+    sim_config: &SimulationConfig,
+    parallel_config: &ParallelConfig,
+    on_game_complete: Option<F>,
+) -> MatchupMatrix
+where
+    F: Fn() + Sync,
+{
+    run_round_robin(
+        strategies.iter().map(|code| code.to_string()).collect(),
+        sim_config,
+        parallel_config,
+        None,
+        |seat_0, seat_1, seed| {
+            let player_codes = vec![strategies[seat_0].clone(), strategies[seat_1].clone()];
+            play_game(deck_a, deck_b, player_codes, seed).state.winner
+        },
+        on_game_complete,
+    )
+    .expect("Matrices without a checkpoint can't fail")
+}
+
+/// Games of a round-robin played so far, as saved in its checkpoints
+#[derive(Serialize, Deserialize)]
+struct RoundRobinProgress {
+    games_done: usize,
+    matrix: MatchupMatrix,
+}
+
+/// Schedules every ordered pairing of `names` `sim_config.num_games` times and tallies the
+/// outcomes returned by `play(seat_0, seat_1, seed)`.
+///
+/// `checkpoint` comes with the parameters that identify the run in the checkpoint file.
+fn run_round_robin<P, F>(
+    names: Vec<String>,
+    sim_config: &SimulationConfig,
+    parallel_config: &ParallelConfig,
+    checkpoint: Option<(&CheckpointConfig, serde_json::Value)>,
+    play: P,
+    on_game_complete: Option<F>,
+) -> Result<MatchupMatrix, String>
+where
+    P: Fn(usize, usize, u64) -> Option<GameOutcome> + Sync,
+    F: Fn() + Sync,
+{
+    if let Some(num_threads) = parallel_config.num_threads {
+        rayon::ThreadPoolBuilder::new()
+            .num_threads(num_threads)
+            .build_global()
+            .ok(); // Ignore error if pool is already initialized
+    }
+
+    let n = names.len();
+    let games: Vec<(usize, usize)> = (0..n)
+        .flat_map(|i| (0..n).filter(move |&j| j != i).map(move |j| (i, j)))
+        .flat_map(|pairing| std::iter::repeat_n(pairing, sim_config.num_games as usize))
+        .collect();
+
+    let mut progress = RoundRobinProgress {
+        games_done: 0,
+        matrix: MatchupMatrix::new(names),
+    };
+    let mut seed = sim_config.seed;
+    let mut checkpoint = match checkpoint {
+        Some((config, run)) => {
+            let (checkpoint, saved) = Checkpoint::open(config, "matrix", run, seed)?;
+            progress = saved.unwrap_or(progress);
+            seed = Some(checkpoint.seed());
+            Some(checkpoint)
+        }
+        None => None,
+    };
+
+    let play_single_game = |game_index: usize| {
+        let (seat_0, seat_1) = games[game_index];
+        let outcome = play(seat_0, seat_1, game_seed(seed, game_index as u64));
+        if let Some(callback) = &on_game_complete {
+            callback();
+        }
+        (seat_0, seat_1, outcome)
+    };
+    play_in_batches(
+        progress.games_done,
+        games.len(),
+        checkpoint.is_some(),
+        parallel_config.enabled,
+        play_single_game,
+        |games_done, outcomes| {
+            for (seat_0, seat_1, outcome) in outcomes {
+                progress.matrix.record(seat_0, seat_1, outcome);
+            }
+            progress.games_done = games_done;
+            if let Some(checkpoint) = checkpoint.as_mut() {
+                checkpoint.save_if_due(&progress);
+            }
+        },
+    );
+    if let Some(checkpoint) = checkpoint.as_mut() {
+        checkpoint.save(&progress);
+    }
+    Ok(progress.matrix)
+}
+
+/// Optional outputs of `cli_matrix`
+#[derive(Clone, Default)]
+pub struct MatrixOutputs {
+    pub csv_path: Option<String>,
+    pub json_path: Option<String>,
+}
+
+/// Runs a round-robin between the given decks with a progress bar, prints the matrix
+/// and writes the requested files, as the CLI `matrix` command does.
+/// `errata` (if any) is applied to every deck.
+pub fn cli_matrix(
+    decks: &[(String, Deck)],
+    sim_config: SimulationConfig,
+    parallel_config: ParallelConfig,
+    outputs: &MatrixOutputs,
+    errata: Option<&Errata>,
+    checkpoint: Option<&CheckpointConfig>,
+) -> Option<MatchupMatrix> {
+    let n = decks.len() as u64;
+    let total_games = n * n.saturating_sub(1) * sim_config.num_games as u64;
+    warn!(
+        "Running {} games between {} decks ({} per pairing and seat order){}",
+        total_games.to_formatted_string(&Locale::en),
+        n,
+        sim_config.num_games.to_formatted_string(&Locale::en),
+        if parallel_config.enabled {
+            " (parallel)"
+        } else {
+            ""
+        }
+    );
+    if let Some(errata) = errata {
+        warn!("\tErrata:");
+        for line in errata.describe() {
+            warn!("\t\t{line}");
+        }
+    }
+
+    let pb = create_progress_bar(total_games);
+    pb.tick(); // Ensure progress bar is drawn immediately
+    let matrix = run_matrix_with_checkpoint(
+        decks,
+        &sim_config,
+        &parallel_config,
+        errata,
+        checkpoint,
+        Some(|| pb.inc(1)),
+    );
+    pb.finish_with_message("Matrix complete!");
+    let matrix = match matrix {
+        Ok(matrix) => matrix,
+        Err(err) => {
+            warn!("{err}");
+            return None;
+        }
+    };
+
+    report_matrix(&matrix, outputs);
+    Some(matrix)
+}
+
+/// Runs a round-robin between player strategies on a fixed deck pairing with a progress bar,
+/// then prints and writes the results like `cli_matrix`, as the CLI `rate players` command does
+pub fn cli_strategy_matrix(
+    deck_a: &Deck,
+    deck_b: &Deck,
+    strategies: &[PlayerCode],
+    sim_config: SimulationConfig,
+    parallel_config: ParallelConfig,
+    outputs: &MatrixOutputs,
+) -> MatchupMatrix {
+    let n = strategies.len() as u64;
+    let total_games = n * n.saturating_sub(1) * sim_config.num_games as u64;
+    warn!(
+        "Running {} games between {} player strategies ({} per pairing and seat order){}",
+        total_games.to_formatted_string(&Locale::en),
+        n,
+        sim_config.num_games.to_formatted_string(&Locale::en),
+        if parallel_config.enabled {
+            " (parallel)"
+        } else {
+            ""
+        }
+    );
+
+    let pb = create_progress_bar(total_games);
+    pb.tick(); // Ensure progress bar is drawn immediately
+    let matrix = run_strategy_matrix(
+        deck_a,
+        deck_b,
+        strategies,
+        &sim_config,
+        &parallel_config,
+        Some(|| pb.inc(1)),
+    );
+    pb.finish_with_message("Matrix complete!");
+
+    report_matrix(&matrix, outputs);
+    matrix
+}
+
+/// Prints the matrix and its ratings and writes the requested files
+pub fn report_matrix(matrix: &MatchupMatrix, outputs: &MatrixOutputs) {
+    print_matrix(matrix);
+    print_ratings(&fit_ratings(matrix));
+
+    if let Some(path) = &outputs.csv_path {
+        match fs::write(path, matrix.to_csv_string()) {
+            Ok(()) => warn!("Wrote win-rate matrix to {path}"),
+            Err(err) => warn!("Failed to write {path}: {err}"),
+        }
+    }
+    if let Some(path) = &outputs.json_path {
+        let json = serde_json::to_string_pretty(&matrix.to_json())
+            .expect("Matrix should serialize to JSON");
+        match fs::write(path, json) {
+            Ok(()) => warn!("Wrote win-rate matrix to {path}"),
+            Err(err) => warn!("Failed to write {path}: {err}"),
+        }
+    }
+}
+
+/// Prints the win-rate matrix (row entry's win rate against column entry), sorted by
+/// average win rate against the field
+pub fn print_matrix(matrix: &MatchupMatrix) {
+    let n = matrix.names.len();
+    let mut order: Vec<usize> = (0..n).collect();
+    order.sort_by(|&a, &b| {
+        matrix
+            .average_vs_field(b)
+            .total_cmp(&matrix.average_vs_field(a))
+    });
+
+    let labels: Vec<String> = order
+        .iter()
+        .enumerate()
+        .map(|(rank, &i)| format!("#{} {}", rank + 1, matrix.names[i]))
+        .collect();
+    let name_width = labels
+        .iter()
+        .map(|label| label.chars().count())
+        .max()
+        .unwrap_or(0)
+        .max("Deck".len());
+    let mut header = format!("{:<name_width$}  {:>8}", "Deck", "vs field");
+    for column in 1..=n {
+        header.push_str(&format!(" {:>6}", format!("#{column}")));
+    }
+    warn!("{header}");
+    for (label, &i) in labels.iter().zip(&order) {
+        let mut row = format!(
+            "{:<name_width$}  {:>7.1}%",
+            label,
+            matrix.average_vs_field(i) * 100.0
+        );
+        for &j in &order {
+            match matrix.win_rate(i, j) {
+                Some(rate) => row.push_str(&format!(" {:>5.1}%", rate * 100.0)),
+                None => row.push_str(&format!(" {:>6}", "-")),
+            }
+        }
+        warn!("{row}");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_record_keeps_matrix_symmetric() {
+        let mut matrix = MatchupMatrix::new(vec!["a".into(), "b".into(), "c".into()]);
+        matrix.record(0, 1, Some(GameOutcome::Win(0)));
+        matrix.record(1, 0, Some(GameOutcome::Win(0)));
+        matrix.record(0, 2, Some(GameOutcome::Win(1)));
+
+        assert_eq!(matrix.win_rate(0, 1), Some(0.5));
+        assert_eq!(matrix.win_rate(1, 0), Some(0.5));
+        assert_eq!(matrix.win_rate(2, 0), Some(1.0));
+        assert_eq!(matrix.win_rate(1, 2), None);
+        assert_eq!(matrix.win_rate(0, 0), None);
+        assert_eq!(matrix.average_vs_field(0), 0.25);
+        assert_eq!(matrix.total_vs_field(0).games(), 3);
+
+        let csv = matrix.to_csv_string();
+        assert_eq!(csv.lines().next(), Some("deck,a,b,c,vs_field"));
+        assert_eq!(csv.lines().nth(2), Some("b,0.5000,,,0.5000"));
+    }
+
+    #[test]
+    fn test_run_matrix_plays_both_seat_orders() {
+        let decks: Vec<(String, Deck)> = ["venusaur-exeggutor", "weezing-arbok", "mewtwoex"]
+            .iter()
+            .map(|name| {
+                let deck = Deck::from_file(&format!("example_decks/{name}.txt")).unwrap();
+                (name.to_string(), deck)
+            })
+            .collect();
+        let sim_config = SimulationConfig {
+            num_games: 2,
+            players: Some(vec![PlayerCode::R, PlayerCode::R]),
+            seed: Some(11),
+        };
+
+        let matrix = run_matrix(
+            &decks,
+            &sim_config,
+            &ParallelConfig {
+                enabled: true,
+                num_threads: None,
+            },
+            None::<fn()>,
+        );
+        for i in 0..3 {
+            for j in 0..3 {
+                if i == j {
+                    assert_eq!(matrix.tallies[i][j].games(), 0);
+                } else {
+                    assert_eq!(matrix.tallies[i][j].games(), 4);
+                    assert_eq!(matrix.tallies[i][j], matrix.tallies[j][i].flipped());
+                }
+            }
+        }
+        assert_eq!(matrix.to_json()["names"][2], "mewtwoex");
+    }
+}
@@ -1,9 +1,11 @@
 use core::fmt;
+use std::collections::HashMap;
 use std::hash::{Hash, Hasher};
+use std::sync::{LazyLock, Mutex};
 
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize};
 
-use crate::{card_ids::CardId, AbilityId};
+use crate::{card_database::get_card_by_id, card_ids::CardId, AbilityId};
 
 /// Represents the type of energy.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, PartialOrd, Ord)]
@@ -117,6 +119,18 @@ pub struct TrainerCard {
     pub rarity: String,
     pub booster_pack: String,
 }
+impl TrainerCard {
+    /// For `&'static TrainerCard` fields, which share the handles deserialized `Card`s use
+    pub(crate) fn deserialize_static<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<&'static TrainerCard, D::Error> {
+        let trainer_card = TrainerCard::deserialize(deserializer)?;
+        match Card::from(CardDefinition::Trainer(trainer_card)) {
+            Card::Trainer(trainer_card) => Ok(trainer_card),
+            Card::Pokemon(_) => unreachable!("Trainer definitions give trainer cards"),
+        }
+    }
+}
 impl PartialEq for TrainerCard {
     fn eq(&self, other: &Self) -> bool {
         self.id == other.id
@@ -129,11 +143,65 @@ impl Hash for TrainerCard {
     }
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+/// A cheap to copy handle to a card's definition. The definitions of the compiled cards live in
+/// the static card table (see `get_card_ref`); others (e.g. loaded with `load_card_database`) are
+/// built once with `Card::from` and live for the rest of the program.
+///
+/// Deserializing hands out the existing handle for a definition seen before (a compiled card, or
+/// one deserialized earlier), so only definitions new to the program are leaked.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(from = "CardDefinition")]
 pub enum Card {
+    Pokemon(&'static PokemonCard),
+    Trainer(&'static TrainerCard),
+}
+
+/// The serialized form of a `Card`
+#[derive(Deserialize)]
+enum CardDefinition {
     Pokemon(PokemonCard),
     Trainer(TrainerCard),
 }
+/// Handles of the definitions deserialized so far that aren't compiled in, by their JSON
+static DESERIALIZED_CARDS: LazyLock<Mutex<HashMap<String, Card>>> = LazyLock::new(Default::default);
+
+impl From<CardDefinition> for Card {
+    fn from(definition: CardDefinition) -> Self {
+        let (id, json) = match &definition {
+            CardDefinition::Pokemon(pokemon_card) => {
+                (&pokemon_card.id, serde_json::to_string(pokemon_card))
+            }
+            CardDefinition::Trainer(trainer_card) => {
+                (&trainer_card.id, serde_json::to_string(trainer_card))
+            }
+        };
+        let json = json.expect("Cards should serialize to JSON");
+        // Cards changed at runtime (e.g. by errata) share their id with the original
+        if let Some(known) = get_card_by_id(id).filter(|known| known.to_json() == json) {
+            return known;
+        }
+        *DESERIALIZED_CARDS
+            .lock()
+            .expect("Deserialized cards lock poisoned")
+            .entry(json)
+            .or_insert_with(|| match definition {
+                CardDefinition::Pokemon(pokemon_card) => pokemon_card.into(),
+                CardDefinition::Trainer(trainer_card) => trainer_card.into(),
+            })
+    }
+}
+/// Never freed, so only build cards that the program keeps using this way
+impl From<PokemonCard> for Card {
+    fn from(pokemon_card: PokemonCard) -> Self {
+        Card::Pokemon(Box::leak(Box::new(pokemon_card)))
+    }
+}
+/// Never freed, so only build cards that the program keeps using this way
+impl From<TrainerCard> for Card {
+    fn from(trainer_card: TrainerCard) -> Self {
+        Card::Trainer(Box::leak(Box::new(trainer_card)))
+    }
+}
 impl Hash for Card {
     fn hash<H: Hasher>(&self, state: &mut H) {
         match self {
@@ -143,6 +211,15 @@ impl Hash for Card {
     }
 }
 impl Card {
+    /// The definition as JSON, without the `Card` variant around it
+    fn to_json(self) -> String {
+        match self {
+            Card::Pokemon(pokemon_card) => serde_json::to_string(pokemon_card),
+            Card::Trainer(trainer_card) => serde_json::to_string(trainer_card),
+        }
+        .expect("Cards should serialize to JSON")
+    }
+
     pub fn get_id(&self) -> String {
         match self {
             Card::Pokemon(pokemon_card) => pokemon_card.id.clone(),
@@ -228,10 +305,21 @@ impl Card {
         }
     }
 
+    /// Panics for cards loaded at runtime, which have no `CardId` (see `try_get_card_id`)
     pub fn get_card_id(&self) -> CardId {
         CardId::from_card_id(self.get_id().as_str()).expect("Card ID should be valid")
     }
 
+    /// Like `get_card_id`, but an error for cards loaded at runtime
+    pub fn try_get_card_id(&self) -> Result<CardId, String> {
+        CardId::from_card_id(&self.get_id()).ok_or_else(|| {
+            format!(
+                "{} is a runtime card without a CardId",
+                self.get_full_identity()
+            )
+        })
+    }
+
     pub fn is_basic(&self) -> bool {
         match self {
             Card::Pokemon(pokemon_card) => pokemon_card.stage == BASIC_STAGE,
@@ -246,9 +334,9 @@ impl Card {
         }
     }
 
-    pub fn as_trainer(&self) -> TrainerCard {
+    pub fn as_trainer(&self) -> &'static TrainerCard {
         match self {
-            Card::Trainer(trainer_card) => trainer_card.clone(),
+            Card::Trainer(trainer_card) => trainer_card,
             _ => panic!("Card is not a Trainer"),
         }
     }
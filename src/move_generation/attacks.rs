@@ -1,6 +1,6 @@
 use crate::{
     actions::SimpleAction,
-    actions::{attacks::Mechanic, EFFECT_MECHANIC_MAP},
+    actions::{attack_mechanic_from_effect, attacks::Mechanic},
     effects::CardEffect,
     hooks::{contains_energy, get_attack_cost},
     State,
@@ -45,7 +45,7 @@ pub(crate) fn generate_attack_actions(state: &State) -> Vec<SimpleAction> {
                 // Check mechanic-specific conditions for using the attack
                 if let Some(effect_text) = &attack.effect {
                     if let Some(Mechanic::DiscardHandCard { count }) =
-                        EFFECT_MECHANIC_MAP.get(effect_text.as_str())
+                        attack_mechanic_from_effect(effect_text.as_str())
                     {
                         if state.hands[current_player].len() < *count {
                             continue;
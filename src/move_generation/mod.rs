@@ -159,7 +159,7 @@ fn generate_hand_actions(state: &State) -> Vec<SimpleAction> {
                 if pokemon_card.stage == 0 {
                     // Active slot
                     if state.in_play_pokemon[current_player][0].is_none() {
-                        actions.push(SimpleAction::Place(hand_card.clone(), 0));
+                        actions.push(SimpleAction::Place(*hand_card, 0));
                     }
                     // Bench slots (take only the first empty one)
                     if let Some((i, _)) = state.in_play_pokemon[current_player]
@@ -168,7 +168,7 @@ fn generate_hand_actions(state: &State) -> Vec<SimpleAction> {
                         .skip(1)
                         .find(|(_, x)| x.is_none())
                     {
-                        actions.push(SimpleAction::Place(hand_card.clone(), i));
+                        actions.push(SimpleAction::Place(*hand_card, i));
                     }
                 } else {
                     // Evolutions can only be played if previous stage
@@ -204,7 +204,7 @@ fn generate_hand_actions(state: &State) -> Vec<SimpleAction> {
                                 && can_evolve_at_position(state, current_player, i)
                             {
                                 actions.push(SimpleAction::Evolve {
-                                    evolution: hand_card.clone(),
+                                    evolution: *hand_card,
                                     in_play_idx: i,
                                     from_deck: false, // Normal evolution from hand
                                 });
@@ -297,7 +297,7 @@ mod tests {
 
         // Add Aerodactyl to hand
         let aerodactyl = get_card_by_enum(CardId::A1210Aerodactyl);
-        state.hands[0].push(aerodactyl.clone());
+        state.hands[0].push(aerodactyl);
 
         // Debug output
         println!("Old Amber name: {}", played_old_amber.get_name());
@@ -348,7 +348,7 @@ mod tests {
 
         // Add Aerodactyl ex to hand
         let aerodactyl_ex = get_card_by_enum(CardId::A1a046AerodactylEx);
-        state.hands[0].push(aerodactyl_ex.clone());
+        state.hands[0].push(aerodactyl_ex);
 
         // Generate actions
         let hand_actions = generate_hand_actions(&state);
@@ -386,7 +386,7 @@ mod tests {
 
         // Add Ivysaur to player 1's hand
         let ivysaur = get_card_by_enum(CardId::A1002Ivysaur);
-        state.hands[1].push(ivysaur.clone());
+        state.hands[1].push(ivysaur);
 
         // Generate actions for player 1
         let hand_actions = generate_hand_actions(&state);
@@ -428,7 +428,7 @@ mod tests {
 
         // Add Ivysaur to player 1's hand
         let ivysaur = get_card_by_enum(CardId::A1002Ivysaur);
-        state.hands[1].push(ivysaur.clone());
+        state.hands[1].push(ivysaur);
 
         // Generate actions
         let hand_actions = generate_hand_actions(&state);
@@ -458,7 +458,7 @@ mod tests {
 
         // Hand contains a basic pokemon
         let bulbasaur = get_card_by_enum(CardId::A1001Bulbasaur);
-        state.hands[0].push(bulbasaur.clone());
+        state.hands[0].push(bulbasaur);
 
         // Slot 1, 2, 3 are empty
 
@@ -491,7 +491,7 @@ mod tests {
         state.in_play_pokemon[0][1] = Some(to_playable_card(&charmander, false));
 
         let bulbasaur = get_card_by_enum(CardId::A1001Bulbasaur);
-        state.hands[0].push(bulbasaur.clone());
+        state.hands[0].push(bulbasaur);
 
         let actions = generate_hand_actions(&state);
         let place_actions: Vec<_> = actions
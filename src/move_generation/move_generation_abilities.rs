@@ -47,7 +47,9 @@ fn can_use_ability(state: &State, (in_play_index, card): (usize, &PlayedCard)) -
         )
     });
     match ability {
-        AbilityId::A1020VictreebelFragranceTrap => is_active && !card.ability_used,
+        AbilityId::A1020VictreebelFragranceTrap => {
+            is_active && !card.ability_used && can_use_victreebel_fragrance_trap(state)
+        }
         AbilityId::A1089GreninjaWaterShuriken => unreachable!("Handled by AbilityMechanic"),
         AbilityId::A1098MagnetonVoltCharge => !card.ability_used,
         AbilityId::A1123GengarExShadowySpellbind => false,
@@ -82,7 +84,9 @@ fn can_use_ability(state: &State, (in_play_index, card): (usize, &PlayedCard)) -
         AbilityId::A3b034SylveonExHappyRibbon => false,
         AbilityId::A3b056EeveeExVeeveeVolve => false,
         AbilityId::A3b057SnorlaxExFullMouthManner => false,
-        AbilityId::A4083EspeonExPsychicHealing => is_active && !card.ability_used,
+        AbilityId::A4083EspeonExPsychicHealing => {
+            is_active && !card.ability_used && can_use_espeon_psychic_healing(state)
+        }
         AbilityId::A4a010EnteiExLegendaryPulse => false,
         AbilityId::A4a020SuicuneExLegendaryPulse => false,
         AbilityId::A4a022MiloticHealingRipples => false,
@@ -175,6 +179,21 @@ fn can_use_pidgeot_drive_off(state: &State, card: &PlayedCard) -> bool {
     state.enumerate_bench_pokemon(opponent).next().is_some()
 }
 
+fn can_use_victreebel_fragrance_trap(state: &State) -> bool {
+    // Opponent must have a Benched Basic Pokémon to switch in
+    let opponent = (state.current_player + 1) % 2;
+    state
+        .enumerate_bench_pokemon(opponent)
+        .any(|(_, pokemon)| pokemon.card.is_basic())
+}
+
+fn can_use_espeon_psychic_healing(state: &State) -> bool {
+    // Needs a damaged Pokémon to heal
+    state
+        .enumerate_in_play_pokemon(state.current_player)
+        .any(|(_, pokemon)| pokemon.is_damaged())
+}
+
 fn can_use_dusknoir_shadow_void(state: &State, dusknoir_idx: usize) -> bool {
     state
         .enumerate_in_play_pokemon(state.current_player)
@@ -1,9 +1,11 @@
 use crate::{
     actions::SimpleAction,
+    card_equivalence::canonical_card_id,
     card_ids::CardId,
     card_logic::{
         can_rare_candy_evolve, diantha_targets, ilima_targets, quick_grow_extract_candidates,
     },
+    custom_cards::trainer_card_id,
     hooks::{can_play_item, can_play_support, get_stage, is_ultra_beast},
     models::{Card, EnergyType, TrainerCard, TrainerType},
     tools::{enumerate_tool_choices, is_tool_effect_implemented},
@@ -11,10 +13,11 @@ use crate::{
 };
 
 /// Helper function to check if a trainer card can be played and return the appropriate action
-fn can_play_trainer(_state: &State, trainer_card: &TrainerCard) -> Option<Vec<SimpleAction>> {
-    Some(vec![SimpleAction::Play {
-        trainer_card: trainer_card.clone(),
-    }])
+fn can_play_trainer(
+    _state: &State,
+    trainer_card: &'static TrainerCard,
+) -> Option<Vec<SimpleAction>> {
+    Some(vec![SimpleAction::Play { trainer_card }])
 }
 
 /// Helper function to return empty action vector
@@ -25,7 +28,7 @@ fn cannot_play_trainer() -> Option<Vec<SimpleAction>> {
 /// Generate possible actions for a trainer card.
 pub fn generate_possible_trainer_actions(
     state: &State,
-    trainer_card: &TrainerCard,
+    trainer_card: &'static TrainerCard,
 ) -> Option<Vec<SimpleAction>> {
     if state.turn_count == 0 {
         return cannot_play_trainer(); // No trainers on initial setup phase
@@ -44,7 +47,7 @@ pub fn generate_possible_trainer_actions(
 /// card_validation module can do "feature detection", and know if a card is implemented.
 pub fn trainer_move_generation_implementation(
     state: &State,
-    trainer_card: &TrainerCard,
+    trainer_card: &'static TrainerCard,
 ) -> Option<Vec<SimpleAction>> {
     // Pokemon tools can be played if there is a space in the mat for them.
     if trainer_card.trainer_card_type == TrainerType::Tool {
@@ -59,133 +62,68 @@ pub fn trainer_move_generation_implementation(
         return can_place_fossil(state, trainer_card);
     }
 
-    let trainer_id = CardId::from_card_id(trainer_card.id.as_str()).expect("CardId should exist");
-    match trainer_id {
+    let trainer_id = trainer_card_id(&trainer_card.id).expect("CardId should exist");
+    match canonical_card_id(trainer_id) {
         // Complex cases: need to check specific conditions
         CardId::PA001Potion => can_play_potion(state, trainer_card),
-        CardId::A1219Erika | CardId::A1266Erika | CardId::A4b328Erika | CardId::A4b329Erika => {
-            can_play_erika(state, trainer_card)
-        }
-        CardId::A1220Misty | CardId::A1267Misty => can_play_misty(state, trainer_card),
-        CardId::A1221Blaine | CardId::A1268Blaine => can_play_trainer(state, trainer_card),
-        CardId::A1224Brock | CardId::A1271Brock => can_play_trainer(state, trainer_card),
-        CardId::A2a072Irida | CardId::A2a087Irida | CardId::A4b330Irida | CardId::A4b331Irida => {
-            can_play_irida(state, trainer_card)
-        }
-        CardId::A3155Lillie
-        | CardId::A3197Lillie
-        | CardId::A3209Lillie
-        | CardId::A4b348Lillie
-        | CardId::A4b349Lillie
-        | CardId::A4b374Lillie => can_play_lillie(state, trainer_card),
-        CardId::A1222Koga | CardId::A1269Koga => can_play_koga(state, trainer_card),
-        CardId::A1225Sabrina
-        | CardId::A1272Sabrina
-        | CardId::A4b338Sabrina
-        | CardId::A4b339Sabrina => can_play_sabrina(state, trainer_card),
-        CardId::A2150Cyrus | CardId::A2190Cyrus | CardId::A4b326Cyrus | CardId::A4b327Cyrus => {
-            can_play_cyrus(state, trainer_card)
-        }
-        CardId::A2155Mars | CardId::A2195Mars | CardId::A4b344Mars | CardId::A4b345Mars => {
-            can_play_trainer(state, trainer_card)
-        }
-        CardId::A3144RareCandy
-        | CardId::A4b314RareCandy
-        | CardId::A4b315RareCandy
-        | CardId::A4b379RareCandy => can_play_rare_candy(state, trainer_card),
-        CardId::A2b070PokemonCenterLady | CardId::A2b089PokemonCenterLady => {
-            can_play_pokemon_center_lady(state, trainer_card)
-        }
-        CardId::A4151ElementalSwitch
-        | CardId::A4b310ElementalSwitch
-        | CardId::A4b311ElementalSwitch => can_play_elemental_switch(state, trainer_card),
+        CardId::A1219Erika => can_play_erika(state, trainer_card),
+        CardId::A1220Misty => can_play_misty(state, trainer_card),
+        CardId::A1221Blaine => can_play_trainer(state, trainer_card),
+        CardId::A1224Brock => can_play_trainer(state, trainer_card),
+        CardId::A2a072Irida => can_play_irida(state, trainer_card),
+        CardId::A3155Lillie => can_play_lillie(state, trainer_card),
+        CardId::A1222Koga => can_play_koga(state, trainer_card),
+        CardId::A1225Sabrina => can_play_sabrina(state, trainer_card),
+        CardId::A2150Cyrus => can_play_cyrus(state, trainer_card),
+        CardId::A2155Mars => can_play_trainer(state, trainer_card),
+        CardId::A3144RareCandy => can_play_rare_candy(state, trainer_card),
+        CardId::A2b070PokemonCenterLady => can_play_pokemon_center_lady(state, trainer_card),
+        CardId::A4151ElementalSwitch => can_play_elemental_switch(state, trainer_card),
         CardId::A3a064Repel => can_play_repel(state, trainer_card),
-        CardId::A2146PokemonCommunication
-        | CardId::A4b316PokemonCommunication
-        | CardId::A4b317PokemonCommunication => can_play_pokemon_communication(state, trainer_card),
-        CardId::A3a067Gladion | CardId::A3a081Gladion => can_play_gladion(state, trainer_card),
-        CardId::A3a069Lusamine
-        | CardId::A3a083Lusamine
-        | CardId::A4b350Lusamine
-        | CardId::A4b351Lusamine
-        | CardId::A4b375Lusamine => can_play_lusamine(state, trainer_card),
-        CardId::A3149Ilima | CardId::A3191Ilima => can_play_ilima(state, trainer_card),
-        CardId::A4157Lyra | CardId::A4197Lyra | CardId::A4b332Lyra | CardId::A4b333Lyra => {
-            can_play_lyra(state, trainer_card)
-        }
-        CardId::A3151Guzma | CardId::A3193Guzma | CardId::A3208Guzma => {
-            can_play_guzma(state, trainer_card)
-        }
+        CardId::A2146PokemonCommunication => can_play_pokemon_communication(state, trainer_card),
+        CardId::A3a067Gladion => can_play_gladion(state, trainer_card),
+        CardId::A3a069Lusamine => can_play_lusamine(state, trainer_card),
+        CardId::A3149Ilima => can_play_ilima(state, trainer_card),
+        CardId::A4157Lyra => can_play_lyra(state, trainer_card),
+        CardId::A3151Guzma => can_play_guzma(state, trainer_card),
         // Simple cases: always can play
         CardId::A4158Silver
-        | CardId::A4198Silver
-        | CardId::A4b336Silver
-        | CardId::A4b337Silver
         | CardId::A4156Will
-        | CardId::A4196Will
         | CardId::PA002XSpeed
-        | CardId::PA005PokeBall
         | CardId::A2b111PokeBall
         | CardId::PA006RedCard
-        | CardId::PA007ProfessorsResearch
         | CardId::A4b373ProfessorsResearch
         | CardId::A1223Giovanni
-        | CardId::A1270Giovanni
-        | CardId::A4b334Giovanni
-        | CardId::A4b335Giovanni
         | CardId::A1a065MythicalSlab
         | CardId::A1a068Leaf
-        | CardId::A1a082Leaf
         | CardId::A1a067Blue
-        | CardId::A1a081Blue
-        | CardId::A4b346Leaf
-        | CardId::A4b347Leaf
-        | CardId::A2b071Red
-        | CardId::A2b090Red
-        | CardId::A4b352Red
-        | CardId::A4b353Red => can_play_trainer(state, trainer_card),
+        | CardId::A2b071Red => can_play_trainer(state, trainer_card),
 
         CardId::B2145LuckyIcePop => can_play_lucky_ice_pop(state, trainer_card),
-        CardId::A3b066EeveeBag
-        | CardId::A3b107EeveeBag
-        | CardId::A4b308EeveeBag
-        | CardId::A4b309EeveeBag => can_play_eevee_bag(state, trainer_card),
-        CardId::B1217FlamePatch | CardId::B1331FlamePatch => {
-            can_play_flame_patch(state, trainer_card)
-        }
-        CardId::B1225Copycat | CardId::B1270Copycat => can_play_trainer(state, trainer_card),
-        CardId::A2b069Iono | CardId::A2b088Iono | CardId::A4b340Iono | CardId::A4b341Iono => {
-            can_play_trainer(state, trainer_card)
-        }
-        CardId::A2b072TeamRocketGrunt | CardId::A2b091TeamRocketGrunt => {
-            can_play_team_rocket_grunt(state, trainer_card)
-        }
-        CardId::B1223May | CardId::B1268May => can_play_trainer(state, trainer_card),
-        CardId::B1224Fantina | CardId::B1269Fantina => can_play_trainer(state, trainer_card),
-        CardId::B1226Lisia | CardId::B1271Lisia => can_play_trainer(state, trainer_card),
-        CardId::A2a073CelesticTownElder | CardId::A2a088CelesticTownElder => {
-            can_play_celestic_town_elder(state, trainer_card)
-        }
-        CardId::A2a075Adaman | CardId::A2a090Adaman => can_play_trainer(state, trainer_card),
-        CardId::A2a074Barry | CardId::A2a089Barry => can_play_trainer(state, trainer_card),
-        CardId::B2149Diantha | CardId::B2190Diantha => can_play_diantha(state, trainer_card),
-        CardId::B2150Sightseer | CardId::B2191Sightseer => can_play_sightseer(state, trainer_card),
-        CardId::B2151Juggler | CardId::B2192Juggler => can_play_juggler(state, trainer_card),
-        CardId::B2152Piers | CardId::B2193Piers => can_play_piers(state, trainer_card),
+        CardId::A3b066EeveeBag => can_play_eevee_bag(state, trainer_card),
+        CardId::B1217FlamePatch => can_play_flame_patch(state, trainer_card),
+        CardId::B1225Copycat => can_play_trainer(state, trainer_card),
+        CardId::A2b069Iono => can_play_trainer(state, trainer_card),
+        CardId::A2b072TeamRocketGrunt => can_play_team_rocket_grunt(state, trainer_card),
+        CardId::B1223May => can_play_trainer(state, trainer_card),
+        CardId::B1224Fantina => can_play_trainer(state, trainer_card),
+        CardId::B1226Lisia => can_play_trainer(state, trainer_card),
+        CardId::A2a073CelesticTownElder => can_play_celestic_town_elder(state, trainer_card),
+        CardId::A2a075Adaman => can_play_trainer(state, trainer_card),
+        CardId::A2a074Barry => can_play_trainer(state, trainer_card),
+        CardId::B2149Diantha => can_play_diantha(state, trainer_card),
+        CardId::B2150Sightseer => can_play_sightseer(state, trainer_card),
+        CardId::B2151Juggler => can_play_juggler(state, trainer_card),
+        CardId::B2152Piers => can_play_piers(state, trainer_card),
         CardId::B1a066ClemontsBackpack => can_play_trainer(state, trainer_card),
-        CardId::B1a068Clemont | CardId::B1a081Clemont => can_play_trainer(state, trainer_card),
-        CardId::B1a067QuickGrowExtract | CardId::B1a103QuickGrowExtract => {
-            can_play_quick_grow_extract(state, trainer_card)
-        }
-        CardId::B1a069Serena | CardId::B1a082Serena => can_play_trainer(state, trainer_card),
+        CardId::B1a068Clemont => can_play_trainer(state, trainer_card),
+        CardId::B1a067QuickGrowExtract => can_play_quick_grow_extract(state, trainer_card),
+        CardId::B1a069Serena => can_play_trainer(state, trainer_card),
         CardId::A1216HelixFossil
         | CardId::A1217DomeFossil
         | CardId::A1218OldAmber
-        | CardId::A1a063OldAmber
         | CardId::A2144SkullFossil
         | CardId::A2145ArmorFossil
-        | CardId::A4b312OldAmber
-        | CardId::A4b313OldAmber
         | CardId::B1214PlumeFossil
         | CardId::B1216CoverFossil => can_play_fossil(state, trainer_card),
 
@@ -194,15 +132,13 @@ pub fn trainer_move_generation_implementation(
         | CardId::B2154StartingPlains
         | CardId::B2155PeculiarPlaza
         | CardId::B2a093Mesagoza => can_play_trainer(state, trainer_card),
-        CardId::B2a086ElectricGenerator | CardId::B2a131ElectricGenerator => {
-            can_play_electric_generator(state, trainer_card)
-        }
+        CardId::B2a086ElectricGenerator => can_play_electric_generator(state, trainer_card),
         _ => None,
     }
 }
 
 /// Check if a Fossil card can be played (requires at least 1 empty bench spot)
-fn can_play_fossil(state: &State, trainer_card: &TrainerCard) -> Option<Vec<SimpleAction>> {
+fn can_play_fossil(state: &State, trainer_card: &'static TrainerCard) -> Option<Vec<SimpleAction>> {
     let first_empty_bench = state.in_play_pokemon[state.current_player]
         .iter()
         .enumerate()
@@ -211,29 +147,24 @@ fn can_play_fossil(state: &State, trainer_card: &TrainerCard) -> Option<Vec<Simp
         .map(|(i, _)| i);
 
     if let Some(i) = first_empty_bench {
-        Some(vec![SimpleAction::Place(
-            Card::Trainer(trainer_card.clone()),
-            i,
-        )])
+        Some(vec![SimpleAction::Place(Card::Trainer(trainer_card), i)])
     } else {
         cannot_play_trainer()
     }
 }
 
 /// Check if a Pokemon tool can be played (requires at least 1 pokemon in play without a tool)
-fn can_play_tool(state: &State, trainer_card: &TrainerCard) -> Option<Vec<SimpleAction>> {
+fn can_play_tool(state: &State, trainer_card: &'static TrainerCard) -> Option<Vec<SimpleAction>> {
     let valid_targets = enumerate_tool_choices(trainer_card, state, state.current_player).len();
     if valid_targets > 0 {
-        Some(vec![SimpleAction::Play {
-            trainer_card: trainer_card.clone(),
-        }])
+        Some(vec![SimpleAction::Play { trainer_card }])
     } else {
         Some(vec![])
     }
 }
 
 /// Check if Potion can be played (requires at least 1 damaged pokemon in play)
-fn can_play_potion(state: &State, trainer_card: &TrainerCard) -> Option<Vec<SimpleAction>> {
+fn can_play_potion(state: &State, trainer_card: &'static TrainerCard) -> Option<Vec<SimpleAction>> {
     let damaged_count = state
         .enumerate_in_play_pokemon(state.current_player)
         .filter(|(_, x)| x.is_damaged())
@@ -246,7 +177,7 @@ fn can_play_potion(state: &State, trainer_card: &TrainerCard) -> Option<Vec<Simp
 }
 
 /// Check if Ilima can be played (requires a damaged Colorless Pokemon in play)
-fn can_play_ilima(state: &State, trainer_card: &TrainerCard) -> Option<Vec<SimpleAction>> {
+fn can_play_ilima(state: &State, trainer_card: &'static TrainerCard) -> Option<Vec<SimpleAction>> {
     if !ilima_targets(state, state.current_player).is_empty() {
         can_play_trainer(state, trainer_card)
     } else {
@@ -254,7 +185,10 @@ fn can_play_ilima(state: &State, trainer_card: &TrainerCard) -> Option<Vec<Simpl
     }
 }
 
-fn can_play_lucky_ice_pop(state: &State, trainer_card: &TrainerCard) -> Option<Vec<SimpleAction>> {
+fn can_play_lucky_ice_pop(
+    state: &State,
+    trainer_card: &'static TrainerCard,
+) -> Option<Vec<SimpleAction>> {
     if let Some(active) = state.maybe_get_active(state.current_player) {
         if active.is_damaged() {
             return can_play_trainer(state, trainer_card);
@@ -264,7 +198,7 @@ fn can_play_lucky_ice_pop(state: &State, trainer_card: &TrainerCard) -> Option<V
 }
 
 /// Check if Erika can be played (requires at least 1 damaged Grass pokemon in play)
-fn can_play_erika(state: &State, trainer_card: &TrainerCard) -> Option<Vec<SimpleAction>> {
+fn can_play_erika(state: &State, trainer_card: &'static TrainerCard) -> Option<Vec<SimpleAction>> {
     let damaged_grass_count = state
         .enumerate_in_play_pokemon(state.current_player)
         .filter(|(_, x)| x.is_damaged() && x.get_energy_type() == Some(EnergyType::Grass))
@@ -277,7 +211,7 @@ fn can_play_erika(state: &State, trainer_card: &TrainerCard) -> Option<Vec<Simpl
 }
 
 /// Check if Irida can be played (requires at least 1 damaged pokemon with Water energy attached)
-fn can_play_irida(state: &State, trainer_card: &TrainerCard) -> Option<Vec<SimpleAction>> {
+fn can_play_irida(state: &State, trainer_card: &'static TrainerCard) -> Option<Vec<SimpleAction>> {
     let damaged_water_count = state
         .enumerate_in_play_pokemon(state.current_player)
         .filter(|(_, x)| x.is_damaged() && x.attached_energy.contains(&EnergyType::Water))
@@ -291,7 +225,7 @@ fn can_play_irida(state: &State, trainer_card: &TrainerCard) -> Option<Vec<Simpl
 
 fn can_play_elemental_switch(
     state: &State,
-    trainer_card: &TrainerCard,
+    trainer_card: &'static TrainerCard,
 ) -> Option<Vec<SimpleAction>> {
     if state.maybe_get_active(state.current_player).is_none() {
         return cannot_play_trainer();
@@ -317,7 +251,7 @@ fn can_play_elemental_switch(
 /// Check if Pokemon Center Lady can be played (requires at least 1 damaged or status-affected pokemon)
 fn can_play_pokemon_center_lady(
     state: &State,
-    trainer_card: &TrainerCard,
+    trainer_card: &'static TrainerCard,
 ) -> Option<Vec<SimpleAction>> {
     let has_valid_target = state
         .enumerate_in_play_pokemon(state.current_player)
@@ -330,7 +264,7 @@ fn can_play_pokemon_center_lady(
 }
 
 /// Check if Lillie can be played (requires at least 1 damaged Stage 2 pokemon in play)
-fn can_play_lillie(state: &State, trainer_card: &TrainerCard) -> Option<Vec<SimpleAction>> {
+fn can_play_lillie(state: &State, trainer_card: &'static TrainerCard) -> Option<Vec<SimpleAction>> {
     let damaged_stage2_count = state
         .enumerate_in_play_pokemon(state.current_player)
         .filter(|(_, x)| x.is_damaged() && get_stage(x) == 2)
@@ -343,7 +277,7 @@ fn can_play_lillie(state: &State, trainer_card: &TrainerCard) -> Option<Vec<Simp
 }
 
 /// Check if Misty can be played (requires at least 1 water pokemon in play)
-fn can_play_misty(state: &State, trainer_card: &TrainerCard) -> Option<Vec<SimpleAction>> {
+fn can_play_misty(state: &State, trainer_card: &'static TrainerCard) -> Option<Vec<SimpleAction>> {
     let water_in_player_count = state.num_in_play_of_type(state.current_player, EnergyType::Water);
     if water_in_player_count > 0 {
         can_play_trainer(state, trainer_card)
@@ -353,13 +287,13 @@ fn can_play_misty(state: &State, trainer_card: &TrainerCard) -> Option<Vec<Simpl
 }
 
 /// Check if Koga can be played (requires active pokemon to be Weezing or Muk)
-fn can_play_koga(state: &State, trainer_card: &TrainerCard) -> Option<Vec<SimpleAction>> {
+fn can_play_koga(state: &State, trainer_card: &'static TrainerCard) -> Option<Vec<SimpleAction>> {
     let active_pokemon = &state.maybe_get_active(state.current_player);
     if let Some(played_card) = active_pokemon {
         let card_id =
             CardId::from_card_id(played_card.get_id().as_str()).expect("CardId should be known");
-        match card_id {
-            CardId::A1177Weezing | CardId::A1243Weezing | CardId::A1175Muk => {
+        match canonical_card_id(card_id) {
+            CardId::A1177Weezing | CardId::A1175Muk => {
                 return can_play_trainer(state, trainer_card);
             }
             _ => {}
@@ -369,7 +303,10 @@ fn can_play_koga(state: &State, trainer_card: &TrainerCard) -> Option<Vec<Simple
 }
 
 /// Check if Sabrina can be played (requires opponent to have benched pokemon)
-fn can_play_sabrina(state: &State, trainer_card: &TrainerCard) -> Option<Vec<SimpleAction>> {
+fn can_play_sabrina(
+    state: &State,
+    trainer_card: &'static TrainerCard,
+) -> Option<Vec<SimpleAction>> {
     let opponent = (state.current_player + 1) % 2;
     let opponent_has_bench = state.enumerate_bench_pokemon(opponent).count() > 0;
     if opponent_has_bench {
@@ -380,7 +317,7 @@ fn can_play_sabrina(state: &State, trainer_card: &TrainerCard) -> Option<Vec<Sim
 }
 
 /// Check if Cyrus can be played (requires opponent to have at least 1 damaged bench pokemon)
-fn can_play_cyrus(state: &State, trainer_card: &TrainerCard) -> Option<Vec<SimpleAction>> {
+fn can_play_cyrus(state: &State, trainer_card: &'static TrainerCard) -> Option<Vec<SimpleAction>> {
     let opponent = (state.current_player + 1) % 2;
     let damaged_bench_count = state
         .enumerate_bench_pokemon(opponent)
@@ -394,7 +331,7 @@ fn can_play_cyrus(state: &State, trainer_card: &TrainerCard) -> Option<Vec<Simpl
 }
 
 /// Check if Repel can be played (requires opponent's active to be a Basic pokemon)
-fn can_play_repel(state: &State, trainer_card: &TrainerCard) -> Option<Vec<SimpleAction>> {
+fn can_play_repel(state: &State, trainer_card: &'static TrainerCard) -> Option<Vec<SimpleAction>> {
     let opponent = (state.current_player + 1) % 2;
     let opponent_active = &state.maybe_get_active(opponent);
     let opponent_bench_count = state.enumerate_bench_pokemon(opponent).count();
@@ -406,7 +343,10 @@ fn can_play_repel(state: &State, trainer_card: &TrainerCard) -> Option<Vec<Simpl
     cannot_play_trainer()
 }
 
-fn can_play_rare_candy(state: &State, trainer_card: &TrainerCard) -> Option<Vec<SimpleAction>> {
+fn can_play_rare_candy(
+    state: &State,
+    trainer_card: &'static TrainerCard,
+) -> Option<Vec<SimpleAction>> {
     if state.is_users_first_turn() {
         return cannot_play_trainer();
     }
@@ -428,7 +368,7 @@ fn can_play_rare_candy(state: &State, trainer_card: &TrainerCard) -> Option<Vec<
 /// Check if Pokemon Communication can be played (requires at least 1 Pokemon in hand and 1 in deck)
 fn can_play_pokemon_communication(
     state: &State,
-    trainer_card: &TrainerCard,
+    trainer_card: &'static TrainerCard,
 ) -> Option<Vec<SimpleAction>> {
     let player = state.current_player;
     let has_pokemon_in_hand = state.hands[player]
@@ -446,7 +386,10 @@ fn can_play_pokemon_communication(
 }
 
 /// Check if Gladion can be played (requires possibility of Type: Null or Silvally in deck)
-fn can_play_gladion(state: &State, trainer_card: &TrainerCard) -> Option<Vec<SimpleAction>> {
+fn can_play_gladion(
+    state: &State,
+    trainer_card: &'static TrainerCard,
+) -> Option<Vec<SimpleAction>> {
     let player = state.current_player;
 
     // Count Type: Null and Silvally in play and discard
@@ -491,7 +434,10 @@ fn can_play_gladion(state: &State, trainer_card: &TrainerCard) -> Option<Vec<Sim
 }
 
 /// Check if Lusamine can be played (requires opponent has >= 1 point, player has Ultra Beast, >= 1 energy in discard)
-fn can_play_lusamine(state: &State, trainer_card: &TrainerCard) -> Option<Vec<SimpleAction>> {
+fn can_play_lusamine(
+    state: &State,
+    trainer_card: &'static TrainerCard,
+) -> Option<Vec<SimpleAction>> {
     let player = state.current_player;
     let opponent = (player + 1) % 2;
 
@@ -518,7 +464,7 @@ fn can_play_lusamine(state: &State, trainer_card: &TrainerCard) -> Option<Vec<Si
 }
 
 /// Check if Lyra can be played (requires active pokemon to have damage and at least 1 benched pokemon)
-fn can_play_lyra(state: &State, trainer_card: &TrainerCard) -> Option<Vec<SimpleAction>> {
+fn can_play_lyra(state: &State, trainer_card: &'static TrainerCard) -> Option<Vec<SimpleAction>> {
     let player = state.current_player;
     let active_pokemon = state.maybe_get_active(player);
     let bench_count = state.enumerate_bench_pokemon(player).count();
@@ -532,7 +478,10 @@ fn can_play_lyra(state: &State, trainer_card: &TrainerCard) -> Option<Vec<Simple
 }
 
 /// Check if Eevee Bag can be played (requires at least 1 Pokemon that evolved from Eevee in play)
-fn can_play_eevee_bag(state: &State, trainer_card: &TrainerCard) -> Option<Vec<SimpleAction>> {
+fn can_play_eevee_bag(
+    state: &State,
+    trainer_card: &'static TrainerCard,
+) -> Option<Vec<SimpleAction>> {
     let has_eevee_evolution = state
         .enumerate_in_play_pokemon(state.current_player)
         .any(|(_, pokemon)| pokemon.evolved_from("Eevee"));
@@ -544,7 +493,10 @@ fn can_play_eevee_bag(state: &State, trainer_card: &TrainerCard) -> Option<Vec<S
 }
 
 /// Check if Flame Patch can be played (requires active Fire pokemon and Fire energy in discard)
-fn can_play_flame_patch(state: &State, trainer_card: &TrainerCard) -> Option<Vec<SimpleAction>> {
+fn can_play_flame_patch(
+    state: &State,
+    trainer_card: &'static TrainerCard,
+) -> Option<Vec<SimpleAction>> {
     let player = state.current_player;
     let active_pokemon = state.maybe_get_active(player);
 
@@ -564,7 +516,7 @@ fn can_play_flame_patch(state: &State, trainer_card: &TrainerCard) -> Option<Vec
 }
 
 /// Check if Piers can be played (requires Galarian Obstagoon in play and opponent has energy)
-fn can_play_piers(state: &State, trainer_card: &TrainerCard) -> Option<Vec<SimpleAction>> {
+fn can_play_piers(state: &State, trainer_card: &'static TrainerCard) -> Option<Vec<SimpleAction>> {
     let has_obstagoon = state
         .enumerate_in_play_pokemon(state.current_player)
         .any(|(_, pokemon)| pokemon.get_name() == "Galarian Obstagoon");
@@ -582,7 +534,10 @@ fn can_play_piers(state: &State, trainer_card: &TrainerCard) -> Option<Vec<Simpl
     }
 }
 
-fn can_play_sightseer(state: &State, trainer_card: &TrainerCard) -> Option<Vec<SimpleAction>> {
+fn can_play_sightseer(
+    state: &State,
+    trainer_card: &'static TrainerCard,
+) -> Option<Vec<SimpleAction>> {
     if !state.decks[state.current_player].cards.is_empty() {
         can_play_trainer(state, trainer_card)
     } else {
@@ -590,7 +545,10 @@ fn can_play_sightseer(state: &State, trainer_card: &TrainerCard) -> Option<Vec<S
     }
 }
 
-fn can_play_juggler(state: &State, trainer_card: &TrainerCard) -> Option<Vec<SimpleAction>> {
+fn can_play_juggler(
+    state: &State,
+    trainer_card: &'static TrainerCard,
+) -> Option<Vec<SimpleAction>> {
     let actor = state.current_player;
     let mut energy_types = std::collections::HashSet::new();
     for pokemon in state.in_play_pokemon[actor].iter().flatten() {
@@ -613,7 +571,10 @@ fn can_play_juggler(state: &State, trainer_card: &TrainerCard) -> Option<Vec<Sim
 }
 
 /// Check if Diantha can be played (requires damaged Psychic Pokemon with >= 2 Psychic Energy)
-fn can_play_diantha(state: &State, trainer_card: &TrainerCard) -> Option<Vec<SimpleAction>> {
+fn can_play_diantha(
+    state: &State,
+    trainer_card: &'static TrainerCard,
+) -> Option<Vec<SimpleAction>> {
     let has_target = !diantha_targets(state, state.current_player).is_empty();
     if has_target {
         can_play_trainer(state, trainer_card)
@@ -625,7 +586,7 @@ fn can_play_diantha(state: &State, trainer_card: &TrainerCard) -> Option<Vec<Sim
 /// Check if Celestic Town Elder can be played (requires at least 1 Basic Pokemon in discard pile)
 fn can_play_celestic_town_elder(
     state: &State,
-    trainer_card: &TrainerCard,
+    trainer_card: &'static TrainerCard,
 ) -> Option<Vec<SimpleAction>> {
     let player = state.current_player;
     let has_basic_pokemon_in_discard = state.discard_piles[player]
@@ -639,13 +600,16 @@ fn can_play_celestic_town_elder(
 }
 
 /// Fossil cards can be placed in empty Active or Bench slots, like Basic Pokemon
-fn can_place_fossil(state: &State, trainer_card: &TrainerCard) -> Option<Vec<SimpleAction>> {
+fn can_place_fossil(
+    state: &State,
+    trainer_card: &'static TrainerCard,
+) -> Option<Vec<SimpleAction>> {
     let current_player = state.current_player;
     let mut actions = Vec::new();
 
     // Active slot
     if state.in_play_pokemon[current_player][0].is_none() {
-        actions.push(SimpleAction::Place(Card::Trainer(trainer_card.clone()), 0));
+        actions.push(SimpleAction::Place(Card::Trainer(trainer_card), 0));
     }
     // Bench slots (take only the first empty one)
     if let Some((i, _)) = state.in_play_pokemon[current_player]
@@ -654,7 +618,7 @@ fn can_place_fossil(state: &State, trainer_card: &TrainerCard) -> Option<Vec<Sim
         .skip(1)
         .find(|(_, x)| x.is_none())
     {
-        actions.push(SimpleAction::Place(Card::Trainer(trainer_card.clone()), i));
+        actions.push(SimpleAction::Place(Card::Trainer(trainer_card), i));
     }
 
     Some(actions)
@@ -665,7 +629,7 @@ fn can_place_fossil(state: &State, trainer_card: &TrainerCard) -> Option<Vec<Sim
 /// with a valid Grass evolution available in deck
 fn can_play_quick_grow_extract(
     state: &State,
-    trainer_card: &TrainerCard,
+    trainer_card: &'static TrainerCard,
 ) -> Option<Vec<SimpleAction>> {
     // Can't use during first turn
     if state.is_users_first_turn() {
@@ -683,7 +647,7 @@ fn can_play_quick_grow_extract(
 /// Check if Team Rocket Grunt can be played (requires opponent's active Pokemon to have at least 1 energy)
 fn can_play_team_rocket_grunt(
     state: &State,
-    trainer_card: &TrainerCard,
+    trainer_card: &'static TrainerCard,
 ) -> Option<Vec<SimpleAction>> {
     let opponent = (state.current_player + 1) % 2;
     let opponent_has_energy = state
@@ -699,7 +663,7 @@ fn can_play_team_rocket_grunt(
 }
 
 /// Check if Guzma can be played (requires opponent to have at least one tool attached)
-fn can_play_guzma(state: &State, trainer_card: &TrainerCard) -> Option<Vec<SimpleAction>> {
+fn can_play_guzma(state: &State, trainer_card: &'static TrainerCard) -> Option<Vec<SimpleAction>> {
     let opponent = (state.current_player + 1) % 2;
     let opponent_has_tool = state
         .enumerate_in_play_pokemon(opponent)
@@ -715,7 +679,7 @@ fn can_play_guzma(state: &State, trainer_card: &TrainerCard) -> Option<Vec<Simpl
 /// Check if Electric Generator can be played (requires at least 1 Benched [L] Pokémon)
 fn can_play_electric_generator(
     state: &State,
-    trainer_card: &TrainerCard,
+    trainer_card: &'static TrainerCard,
 ) -> Option<Vec<SimpleAction>> {
     let has_benched_lightning = state
         .enumerate_bench_pokemon(state.current_player)
@@ -0,0 +1,436 @@
+use std::collections::HashMap;
+
+use log::warn;
+
+use crate::{
+    card_equivalence::canonical_card_id, card_ids::CardId, database::get_card_ref, deck::is_basic,
+    models::Card, Deck,
+};
+
+/// What `deck_odds` computes
+#[derive(Debug, Clone)]
+pub struct OddsOptions {
+    /// Last game turn to report (turn 1 is the first player's first turn)
+    pub turns: u32,
+    /// Play every Poké Ball and one Professor's Research per turn as soon as they are drawn
+    pub draw_trainers: bool,
+}
+
+impl Default for OddsOptions {
+    fn default() -> Self {
+        OddsOptions {
+            turns: 4,
+            draw_trainers: true,
+        }
+    }
+}
+
+/// Chance of holding the target cards after a given game turn
+#[derive(Debug, Clone, PartialEq)]
+pub struct TurnOdds {
+    pub turn: u32,
+    pub going_first: f64,
+    pub going_second: f64,
+}
+
+/// Exact opening-hand and draw probabilities of a deck
+#[derive(Debug, Clone)]
+pub struct DeckOdds {
+    /// Chance of opening with exactly `i` basic Pokémon, by `i` (never 0, see `Deck::shuffle`)
+    pub basics_in_opening_hand: Vec<f64>,
+    /// Chance that a plain shuffle deals no basic, i.e. how often the game's
+    /// basic guarantee replaces what would be a mulligan
+    pub mulligan_rate: f64,
+    /// Chance of holding every target card in the opening hand
+    pub targets_in_opening_hand: f64,
+    /// Chance of having held every target card by the end of each of the player's turns
+    pub by_turn: Vec<TurnOdds>,
+}
+
+/// How a card moves through the draw model
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum CardKind {
+    PokeBall,
+    ProfessorsResearch,
+    Other,
+}
+
+/// Cards that behave the same for the query (same target, same kind, both basic or not)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+struct CardClass {
+    target: Option<usize>,
+    basic: bool,
+    kind: CardKind,
+}
+
+/// What is left in the deck and which draw trainers wait in hand. Cards that left the deck
+/// are in hand (or were played), so target counts follow from the remaining ones.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct DrawState {
+    remaining: Vec<u8>,
+    poke_balls: u8,
+    research: u8,
+}
+
+/// Probability of each reachable `DrawState`
+type Distribution = HashMap<DrawState, f64>;
+
+/// Computes exact probabilities (no simulation) of drawing into `targets` (repeat an id to
+/// require several copies) and of the opening hand's basics.
+///
+/// Follows the engine's rules: the opening hand is one random basic plus 4 random cards (so
+/// mulligans never happen), and each player draws a card at the start of each of their turns.
+/// Other draw and search effects are not modeled.
+pub fn deck_odds(
+    deck: &Deck,
+    targets: &[CardId],
+    options: &OddsOptions,
+) -> Result<DeckOdds, String> {
+    let mut required: Vec<(CardId, u32)> = Vec::new();
+    for &target in targets {
+        let target = canonical_card_id(target);
+        match required.iter_mut().find(|(id, _)| *id == target) {
+            Some((_, count)) => *count += 1,
+            None => required.push((target, 1)),
+        }
+    }
+    for (target, count) in &required {
+        let in_deck = deck
+            .cards
+            .iter()
+            .filter(|card| card_id(card) == Some(*target))
+            .count() as u32;
+        if in_deck < *count {
+            warn!(
+                "The deck has {in_deck} copies of {}, but {count} are required",
+                get_card_ref(*target).get_name()
+            );
+        }
+    }
+
+    let mut classes: Vec<CardClass> = Vec::new();
+    let mut counts: Vec<u8> = Vec::new();
+    for card in &deck.cards {
+        let id = card_id(card);
+        let class = CardClass {
+            target: required.iter().position(|(target, _)| Some(*target) == id),
+            basic: is_basic(card),
+            kind: match id {
+                Some(CardId::A2b111PokeBall) => CardKind::PokeBall,
+                Some(CardId::A4b373ProfessorsResearch) => CardKind::ProfessorsResearch,
+                _ => CardKind::Other,
+            },
+        };
+        match classes.iter().position(|&existing| existing == class) {
+            Some(index) => counts[index] += 1,
+            None => {
+                classes.push(class);
+                counts.push(1);
+            }
+        }
+    }
+    let model = DrawModel {
+        classes,
+        initial: counts,
+        required: required.iter().map(|&(_, count)| count).collect(),
+        draw_trainers: options.draw_trainers,
+    };
+
+    let num_basics = model.count(&model.initial, |class| class.basic);
+    if num_basics == 0 {
+        return Err("Decks must have at least 1 basic".to_string());
+    }
+    let deck_size = deck.cards.len() as u64;
+    let mulligan_rate =
+        binomial(deck_size - num_basics as u64, 5) as f64 / binomial(deck_size, 5) as f64;
+
+    // Opening hand: the guaranteed basic, then 4 more cards
+    let start = DrawState {
+        remaining: model.initial.clone(),
+        poke_balls: 0,
+        research: 0,
+    };
+    let mut opening = model.take_basic(HashMap::from([(start, 1.0)]));
+    for _ in 0..4 {
+        opening = model.draw(opening);
+    }
+    let mut basics_in_opening_hand = vec![0.0; 6];
+    for (state, probability) in &opening {
+        let basics = num_basics - model.count(&state.remaining, |class| class.basic);
+        basics_in_opening_hand[basics as usize] += probability;
+    }
+    let targets_in_opening_hand = model.success(&opening);
+
+    // The first player takes the odd turns and the second player the even ones
+    let mut first = opening.clone();
+    let mut second = opening;
+    let mut going_first = targets_in_opening_hand;
+    let mut going_second = targets_in_opening_hand;
+    let mut by_turn = Vec::new();
+    for turn in 1..=options.turns {
+        if turn % 2 == 1 {
+            first = model.play_turn(first);
+            going_first = model.success(&first);
+        } else {
+            second = model.play_turn(second);
+            going_second = model.success(&second);
+        }
+        by_turn.push(TurnOdds {
+            turn,
+            going_first,
+            going_second,
+        });
+    }
+
+    Ok(DeckOdds {
+        basics_in_opening_hand,
+        mulligan_rate,
+        targets_in_opening_hand,
+        by_turn,
+    })
+}
+
+struct DrawModel {
+    classes: Vec<CardClass>,
+    initial: Vec<u8>,
+    /// Copies needed of each target
+    required: Vec<u32>,
+    draw_trainers: bool,
+}
+
+impl DrawModel {
+    fn count(&self, remaining: &[u8], filter: impl Fn(&CardClass) -> bool) -> u32 {
+        self.classes
+            .iter()
+            .zip(remaining)
+            .filter(|(class, _)| filter(class))
+            .map(|(_, &count)| count as u32)
+            .sum()
+    }
+
+    /// Chance that every target has been drawn often enough
+    fn success(&self, distribution: &Distribution) -> f64 {
+        distribution
+            .iter()
+            .filter(|(state, _)| {
+                self.required.iter().enumerate().all(|(target, &required)| {
+                    let is_target = |class: &CardClass| class.target == Some(target);
+                    let drawn = self.count(&self.initial, is_target)
+                        - self.count(&state.remaining, is_target);
+                    drawn >= required
+                })
+            })
+            .map(|(_, probability)| probability)
+            .sum()
+    }
+
+    /// Moves one random card matching `filter` from the deck to the hand. States without
+    /// such a card stay as they are.
+    fn take(
+        &self,
+        distribution: Distribution,
+        filter: impl Fn(&CardClass) -> bool,
+    ) -> Distribution {
+        let mut next = Distribution::new();
+        for (state, probability) in distribution {
+            let total = self.count(&state.remaining, &filter);
+            if total == 0 {
+                *next.entry(state).or_insert(0.0) += probability;
+                continue;
+            }
+            for (index, class) in self.classes.iter().enumerate() {
+                let count = state.remaining[index];
+                if count == 0 || !filter(class) {
+                    continue;
+                }
+                let mut drawn = state.clone();
+                drawn.remaining[index] -= 1;
+                match class.kind {
+                    CardKind::PokeBall => drawn.poke_balls += 1,
+                    CardKind::ProfessorsResearch => drawn.research += 1,
+                    CardKind::Other => {}
+                }
+                *next.entry(drawn).or_insert(0.0) += probability * count as f64 / total as f64;
+            }
+        }
+        next
+    }
+
+    fn draw(&self, distribution: Distribution) -> Distribution {
+        self.take(distribution, |_| true)
+    }
+
+    fn take_basic(&self, distribution: Distribution) -> Distribution {
+        self.take(distribution, |class| class.basic)
+    }
+
+    /// Plays every Poké Ball in hand, each fetching a random basic from the deck
+    fn play_poke_balls(&self, mut distribution: Distribution) -> Distribution {
+        loop {
+            let (playing, mut waiting): (Distribution, Distribution) = distribution
+                .into_iter()
+                .partition(|(state, _)| state.poke_balls > 0);
+            if playing.is_empty() {
+                return waiting;
+            }
+            let played = playing
+                .into_iter()
+                .map(|(mut state, probability)| {
+                    state.poke_balls -= 1;
+                    (state, probability)
+                })
+                .collect();
+            for (state, probability) in self.take_basic(played) {
+                *waiting.entry(state).or_insert(0.0) += probability;
+            }
+            distribution = waiting;
+        }
+    }
+
+    /// Draws for the turn, then plays Poké Balls and one Professor's Research (draw 2)
+    fn play_turn(&self, distribution: Distribution) -> Distribution {
+        let distribution = self.draw(distribution);
+        if !self.draw_trainers {
+            return distribution;
+        }
+        let distribution = self.play_poke_balls(distribution);
+        let (playing, mut waiting): (Distribution, Distribution) = distribution
+            .into_iter()
+            .partition(|(state, _)| state.research > 0);
+        let played = playing
+            .into_iter()
+            .map(|(mut state, probability)| {
+                state.research -= 1;
+                (state, probability)
+            })
+            .collect();
+        let played = self.play_poke_balls(self.draw(self.draw(played)));
+        for (state, probability) in played {
+            *waiting.entry(state).or_insert(0.0) += probability;
+        }
+        waiting
+    }
+}
+
+/// The canonical print of the card, so that reprints count as the same target
+fn card_id(card: &Card) -> Option<CardId> {
+    CardId::from_card_id(&card.get_id()).map(canonical_card_id)
+}
+
+fn binomial(n: u64, k: u64) -> u64 {
+    if k > n {
+        return 0;
+    }
+    (0..k).fold(1, |result, i| result * (n - i) / (i + 1))
+}
+
+/// Prints the result of `deck_odds`, as the CLI `deck odds` command does
+pub fn print_deck_odds(odds: &DeckOdds, targets: &[CardId]) {
+    warn!("Basic Pokémon in the opening hand:");
+    for (basics, probability) in odds.basics_in_opening_hand.iter().enumerate().skip(1) {
+        warn!("\t{basics}: {:.2}%", probability * 100.0);
+    }
+    warn!(
+        "Hands that would be a mulligan without the basic guarantee: {:.2}%",
+        odds.mulligan_rate * 100.0
+    );
+    if targets.is_empty() {
+        return;
+    }
+    let names: Vec<String> = targets
+        .iter()
+        .map(|&id| get_card_ref(id).get_name())
+        .collect();
+    warn!("Chance of holding {}:", names.join(", "));
+    warn!(
+        "\tOpening hand: {:.2}%",
+        odds.targets_in_opening_hand * 100.0
+    );
+    warn!(
+        "\t{:<6} {:>12} {:>12}",
+        "Turn", "Going first", "Going second"
+    );
+    for turn in &odds.by_turn {
+        warn!(
+            "\t{:<6} {:>11.2}% {:>11.2}%",
+            turn.turn,
+            turn.going_first * 100.0,
+            turn.going_second * 100.0
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(actual: f64, expected: f64) {
+        assert!(
+            (actual - expected).abs() < 1e-9,
+            "expected {expected}, got {actual}"
+        );
+    }
+
+    #[test]
+    fn test_binomial() {
+        assert_eq!(binomial(20, 5), 15504);
+        assert_eq!(binomial(5, 0), 1);
+        assert_eq!(binomial(3, 5), 0);
+    }
+
+    #[test]
+    fn test_opening_hand_always_has_a_basic() {
+        let deck = Deck::from_file("example_decks/venusaur-exeggutor.txt").unwrap();
+        let odds = deck_odds(&deck, &[], &OddsOptions::default()).unwrap();
+        assert_eq!(odds.basics_in_opening_hand[0], 0.0);
+        assert_close(odds.basics_in_opening_hand.iter().sum(), 1.0);
+        assert!(odds.mulligan_rate > 0.0 && odds.mulligan_rate < 1.0);
+    }
+
+    #[test]
+    fn test_matches_hypergeometric_without_trainers() {
+        let deck = Deck::from_file("example_decks/venusaur-exeggutor.txt").unwrap();
+        let target = deck
+            .cards
+            .iter()
+            .filter_map(card_id)
+            .find(|&id| {
+                deck.cards.iter().filter(|c| card_id(c) == Some(id)).count() == 2
+                    && !is_basic(get_card_ref(id))
+            })
+            .expect("deck should have a non-basic card with 2 copies");
+        let options = OddsOptions {
+            turns: 3,
+            draw_trainers: false,
+        };
+        let odds = deck_odds(&deck, &[target], &options).unwrap();
+
+        // The guaranteed basic isn't the target, so the other n cards come from the 19 left
+        let miss = |n: u64| binomial(17, n) as f64 / binomial(19, n) as f64;
+        assert_close(odds.targets_in_opening_hand, 1.0 - miss(4));
+        assert_close(odds.by_turn[0].going_first, 1.0 - miss(5));
+        assert_close(odds.by_turn[0].going_second, 1.0 - miss(4));
+        assert_close(odds.by_turn[1].going_second, 1.0 - miss(5));
+        assert_close(odds.by_turn[2].going_first, 1.0 - miss(6));
+    }
+
+    #[test]
+    fn test_draw_trainers_only_help() {
+        let deck = Deck::from_file("example_decks/venusaur-exeggutor.txt").unwrap();
+        let target = card_id(deck.cards.iter().find(|c| is_basic(c)).unwrap()).unwrap();
+        let without = deck_odds(
+            &deck,
+            &[target, target],
+            &OddsOptions {
+                turns: 4,
+                draw_trainers: false,
+            },
+        )
+        .unwrap();
+        let with = deck_odds(&deck, &[target, target], &OddsOptions::default()).unwrap();
+        for (without, with) in without.by_turn.iter().zip(&with.by_turn) {
+            assert!(with.going_first >= without.going_first - 1e-12);
+            assert!(with.going_second >= without.going_second - 1e-12);
+        }
+    }
+}
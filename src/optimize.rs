@@ -3,19 +3,29 @@ use std::fs;
 use log::warn;
 use num_format::{Locale, ToFormattedString};
 use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
 
 use crate::{
+    card_equivalence::canonical_card_id,
     card_ids::CardId,
-    database::get_card_by_enum,
-    players::{create_players, fill_code_array, PlayerCode},
-    simulate::create_progress_bar,
+    card_lookup::{resolve_card_name, CardNameError},
+    checkpoint::{deck_card_ids, player_names, Checkpoint, CheckpointConfig},
+    database::{get_card_by_enum, get_card_ref},
+    format::Format,
+    inventory::Inventory,
+    models::Card,
+    players::{fill_code_array, PlayerCode},
+    simulate::{create_progress_bar, deck_name_from_path, game_seed, play_game},
     state::GameOutcome,
-    Deck, Game,
+    statistics::MatchTally,
+    Deck,
 };
 
 /// Configuration for a single enemy deck in optimization
 #[derive(Clone)]
 pub struct EnemyDeckConfig {
+    /// Name shown in reports (e.g. the deck file name)
+    pub name: String,
     pub deck: Deck,
     pub num_games: u32,
 }
@@ -26,6 +36,12 @@ pub struct OptimizationConfig {
     pub enemy_deck_configs: Vec<EnemyDeckConfig>,
     pub players: Option<Vec<PlayerCode>>,
     pub seed: Option<u64>,
+    /// When set, only combinations the inventory can provide are tried
+    pub inventory: Option<Inventory>,
+    /// When set, only combinations completing a deck the format allows are tried
+    pub format: Option<Format>,
+    /// When set, partial results are saved to (and resumed from) this checkpoint
+    pub checkpoint: Option<CheckpointConfig>,
 }
 
 /// Configuration for running simulations
@@ -86,14 +102,20 @@ where
     }
 }
 
-/// Optimizes a deck by simulating games with different combinations of candidate cards.
+/// Optimizes a deck by simulating games with different combinations of candidate cards,
+/// printing and returning the ranked report.
+#[allow(clippy::too_many_arguments)]
 pub fn cli_optimize(
     incomplete_deck_path: &str,
     candidate_cards_str: &str,
     enemy_decks_folder: &str,
     sim_config: SimulationConfig,
     parallel_config: ParallelConfig,
-) {
+    strategy: OptimizationStrategy,
+    inventory: Option<Inventory>,
+    format: Option<Format>,
+    checkpoint: Option<CheckpointConfig>,
+) -> OptimizationReport {
     let incomplete_deck =
         Deck::from_file(incomplete_deck_path).expect("Failed to parse incomplete deck file");
     let candidate_cards: Vec<String> = candidate_cards_str
@@ -113,15 +135,15 @@ pub fn cli_optimize(
             }
         })
         .collect();
-    let enemy_valid_decks: Vec<Deck> = enemy_deck_paths
+    let enemy_valid_decks: Vec<(String, Deck)> = enemy_deck_paths
         .iter()
         .filter_map(|path| {
-            let deck = Deck::from_file(path).ok()?;
-            if deck.cards.len() == 20 {
-                Some(deck)
-            } else {
-                warn!("Skipping enemy deck {path} since not valid");
-                None
+            match Deck::from_file(path).and_then(|deck| deck.check_valid().map(|()| deck)) {
+                Ok(deck) => Some((deck_name_from_path(path), deck)),
+                Err(err) => {
+                    warn!("Skipping enemy deck {path}: {err}");
+                    None
+                }
             }
         })
         .collect();
@@ -136,15 +158,69 @@ pub fn cli_optimize(
     );
 
     // Create progress bar for total games (not combinations)
-    let candidate_card_ids: Vec<CardId> = candidate_cards
-        .iter()
-        .map(|s| robustly_parse_card_id_string(s))
-        .collect();
+    let Some(candidate_card_ids) = parse_candidate_cards(&candidate_cards) else {
+        return OptimizationReport::new(
+            enemy_valid_decks
+                .into_iter()
+                .map(|(name, _)| name)
+                .collect(),
+            Vec::new(),
+        );
+    };
     let missing_count = 20 - incomplete_deck.cards.len();
-    let combinations_count =
-        count_valid_combinations(&incomplete_deck, &candidate_card_ids, missing_count);
-    let total_games =
-        (combinations_count * enemy_valid_decks.len() * sim_config.num_games as usize) as u64;
+    let combinations_count = count_valid_combinations(
+        &incomplete_deck,
+        &candidate_card_ids,
+        missing_count,
+        inventory.as_ref(),
+        format.as_ref(),
+    );
+    let num_enemy_decks = enemy_valid_decks.len();
+    let opt_config = OptimizationConfig {
+        enemy_deck_configs: enemy_valid_decks
+            .into_iter()
+            .map(|(name, deck)| EnemyDeckConfig {
+                name,
+                deck,
+                num_games: sim_config.num_games,
+            })
+            .collect(),
+        players: sim_config.players,
+        seed: sim_config.seed,
+        inventory,
+        format,
+        checkpoint,
+    };
+
+    if let OptimizationStrategy::SuccessiveHalving { top_k } = strategy {
+        // Upper bound: racing may drop combinations faster than halving does
+        let schedule = halving_schedule(combinations_count, top_k, sim_config.num_games);
+        let mut survivors = combinations_count;
+        let mut played = 0;
+        let mut games_per_enemy = 0;
+        for &target in &schedule {
+            games_per_enemy += survivors as u64 * (target - played) as u64;
+            played = target;
+            survivors = survivors.div_ceil(2).max(top_k);
+        }
+        let pb = create_progress_bar(games_per_enemy * num_enemy_decks as u64);
+        pb.tick(); // Ensure progress bar is drawn immediately
+
+        let report = optimize_successive_halving(
+            &incomplete_deck,
+            &candidate_cards,
+            &opt_config,
+            top_k,
+            &parallel_config,
+            Some(|| pb.inc(1)),
+        );
+
+        pb.finish_with_message("Optimization complete!");
+        print_optimization_report(&report);
+        return report;
+    }
+
+    let total_games = (combinations_count * num_enemy_decks * sim_config.num_games as usize) as u64;
     let pb = create_progress_bar(total_games);
     pb.tick(); // Ensure progress bar is drawn immediately
 
@@ -152,16 +228,17 @@ pub fn cli_optimize(
     let callbacks: OptimizationCallbacks<NeverCalled, _> =
         OptimizationCallbacks::new().with_game_callback(|| pb.inc(1));
 
-    optimize(
+    let report = optimize_with_configs(
         &incomplete_deck,
         &candidate_cards,
-        &enemy_valid_decks,
-        sim_config,
+        opt_config,
         parallel_config,
         Some(callbacks),
     );
 
     pb.finish_with_message("Optimization complete!");
+    print_optimization_report(&report);
+    report
 }
 
 /// Wrapper function that accepts simple Deck slice for backward compatibility
@@ -180,7 +257,9 @@ where
     // Convert to OptimizationConfig format
     let enemy_deck_configs: Vec<EnemyDeckConfig> = enemy_decks
         .iter()
-        .map(|deck| EnemyDeckConfig {
+        .enumerate()
+        .map(|(i, deck)| EnemyDeckConfig {
+            name: format!("enemy_{}", i + 1),
             deck: deck.clone(),
             num_games: sim_config.num_games,
         })
@@ -189,6 +268,9 @@ where
         enemy_deck_configs,
         players: sim_config.players,
         seed: sim_config.seed,
+        inventory: None,
+        format: None,
+        checkpoint: None,
     };
 
     optimize_with_configs(
@@ -198,30 +280,41 @@ where
         parallel_config,
         callbacks,
     )
+    .combinations
+    .into_iter()
+    .map(|result| {
+        let win_percent = (result.win_rate() * 100.0) as f32;
+        (result.cards, win_percent)
+    })
+    .collect()
 }
 
-/// Optimizes with per-deck game configuration
+/// Optimizes with per-deck game configuration.
+///
+/// Every combination plays the same seeds against each enemy deck, so they are compared on the
+/// same draws. The report ranks combinations from the highest win rate down.
 pub fn optimize_with_configs<F, G>(
     incomplete_deck: &Deck,
     candidate_cards: &[String],
-    opt_config: OptimizationConfig,
+    mut opt_config: OptimizationConfig,
     parallel_config: ParallelConfig,
     callbacks: Option<OptimizationCallbacks<F, G>>,
-) -> Vec<(Vec<CardId>, f32)>
+) -> OptimizationReport
 where
     F: Fn(usize, usize, &[CardId], f32),
     G: Fn() + Sync,
 {
-    if opt_config.enemy_deck_configs.is_empty() {
+    let enemies = &opt_config.enemy_deck_configs;
+    let enemy_names: Vec<String> = enemies.iter().map(|enemy| enemy.name.clone()).collect();
+    if enemies.is_empty() {
         warn!("No valid enemy decks provided. Optimization cannot proceed.");
-        return Vec::new();
+        return OptimizationReport::new(enemy_names, Vec::new());
     }
 
     // Parse the candidate cards list.
-    let candidate_card_ids: Vec<CardId> = candidate_cards
-        .iter()
-        .map(|s| robustly_parse_card_id_string(s))
-        .collect();
+    let Some(candidate_card_ids) = parse_candidate_cards(candidate_cards) else {
+        return OptimizationReport::new(enemy_names, Vec::new());
+    };
 
     // Read and validate the incomplete deck.
     let current_count = incomplete_deck.cards.len();
@@ -229,22 +322,28 @@ where
     warn!("Incomplete deck has {current_count} cards, missing {missing_count} cards");
     if missing_count == 0 {
         warn!("Deck is already complete (20 cards). No optimization needed.");
-        return Vec::new();
+        return OptimizationReport::new(enemy_names, Vec::new());
     }
 
     // Generate all valid combinations for the incomplete deck
-    let combinations =
+    let mut combinations =
         generate_valid_combinations(incomplete_deck, &candidate_card_ids, missing_count);
+    retain_affordable(
+        &mut combinations,
+        incomplete_deck,
+        opt_config.inventory.as_ref(),
+    );
+    retain_legal(
+        &mut combinations,
+        incomplete_deck,
+        opt_config.format.as_ref(),
+    );
 
     warn!(
         "Valid combinations ({}): {combinations:?}",
         combinations.len()
     );
-    let total_games_per_combination: u32 = opt_config
-        .enemy_deck_configs
-        .iter()
-        .map(|config| config.num_games)
-        .sum();
+    let total_games_per_combination: u32 = enemies.iter().map(|config| config.num_games).sum();
     warn!(
         "Games to Play: {} combinations × {} total games per combination",
         combinations.len(),
@@ -259,100 +358,82 @@ where
             .ok(); // Ignore error if pool is already initialized
     }
 
-    // For every valid combination, complete the deck and simulate games.
-    let mut results = Vec::new();
+    let mut progress = ExhaustiveProgress {
+        combinations_done: 0,
+        results: Vec::new(),
+    };
+    let mut checkpoint = match &opt_config.checkpoint {
+        Some(config) => {
+            let run = optimization_run(incomplete_deck, &combinations, &opt_config, None);
+            match Checkpoint::open(config, "optimize", run, opt_config.seed) {
+                Ok((checkpoint, saved)) => {
+                    progress = saved.unwrap_or(progress);
+                    Some(checkpoint)
+                }
+                Err(err) => {
+                    warn!("{err}");
+                    return OptimizationReport::new(enemy_names, Vec::new());
+                }
+            }
+        }
+        None => None,
+    };
+    if let Some(checkpoint) = &checkpoint {
+        opt_config.seed = Some(checkpoint.seed());
+    }
+    let enemies = &opt_config.enemy_deck_configs;
+
+    // The same games (enemy deck and seed) for every combination
+    let seed_offsets = enemy_seed_offsets(enemies);
+    let games: Vec<(usize, usize, u64)> = enemies
+        .iter()
+        .enumerate()
+        .flat_map(|(enemy, config)| {
+            let offset = seed_offsets[enemy];
+            (0..config.num_games).map(move |game| (0, enemy, offset + game as u64))
+        })
+        .collect();
+    let player_codes = fill_code_array(opt_config.players.clone());
+    // Extract the game callback to avoid capturing the entire callbacks struct in parallel closures
+    let game_callback = callbacks
+        .as_ref()
+        .and_then(|cbs| cbs.on_game_complete.as_ref());
 
-    for comb in &combinations {
+    // For every valid combination, complete the deck and simulate games.
+    for (index, comb) in combinations
+        .iter()
+        .enumerate()
+        .skip(progress.combinations_done)
+    {
         // Create a completed deck by cloning the incomplete one and adding the candidate cards.
-        let mut completed_deck = incomplete_deck.clone();
-        for card_id in comb {
-            let card = get_card_by_enum(*card_id);
-            completed_deck.cards.push(card);
-        }
+        let completed_deck = complete_deck(incomplete_deck, comb);
         if !completed_deck.is_valid() {
             warn!(
                 "Completed deck is invalid. Num cards: {}, num basics: {}",
                 completed_deck.cards.len(),
                 completed_deck.cards.iter().filter(|x| x.is_basic()).count()
             );
+            progress.combinations_done = index + 1;
             continue;
         }
 
-        // Generate all games to simulate for this combination
-        let games_to_simulate: Vec<(Deck, Deck, Vec<PlayerCode>)> = opt_config
-            .enemy_deck_configs
-            .iter()
-            .flat_map(|enemy_config| {
-                let deck_clone = completed_deck.clone();
-                let players_clone = opt_config.players.clone();
-                let enemy_deck_clone = enemy_config.deck.clone();
-                (0..enemy_config.num_games).map(move |_| {
-                    (
-                        deck_clone.clone(),
-                        enemy_deck_clone.clone(),
-                        fill_code_array(players_clone.clone()),
-                    )
-                })
-            })
-            .collect();
-
-        // Extract the game callback to avoid capturing the entire callbacks struct in parallel closures
-        let game_callback = callbacks
-            .as_ref()
-            .and_then(|cbs| cbs.on_game_complete.as_ref());
-
-        // Run games either in parallel or sequentially
-        let wins: usize = if parallel_config.enabled {
-            games_to_simulate
-                .par_iter()
-                .map(|(deck_a, deck_b, player_codes)| {
-                    let players =
-                        create_players(deck_a.clone(), deck_b.clone(), player_codes.clone());
-                    let seed = opt_config.seed.unwrap_or(rand::random::<u64>());
-                    let mut game = Game::new(players, seed);
-                    let outcome = game.play();
-
-                    if let Some(callback) = game_callback {
-                        callback();
-                    }
-
-                    // Count as win if first player (our deck) wins
-                    if let Some(GameOutcome::Win(winner)) = outcome {
-                        if winner == 0 {
-                            return 1;
-                        }
-                    }
-                    0
-                })
-                .sum()
-        } else {
-            games_to_simulate
-                .iter()
-                .map(|(deck_a, deck_b, player_codes)| {
-                    let players =
-                        create_players(deck_a.clone(), deck_b.clone(), player_codes.clone());
-                    let seed = opt_config.seed.unwrap_or(rand::random::<u64>());
-                    let mut game = Game::new(players, seed);
-                    let outcome = game.play();
-
-                    if let Some(callback) = game_callback {
-                        callback();
-                    }
-
-                    // Count as win if first player (our deck) wins
-                    if let Some(GameOutcome::Win(winner)) = outcome {
-                        if winner == 0 {
-                            return 1;
-                        }
-                    }
-                    0
-                })
-                .sum()
-        };
-
-        let total_games = games_to_simulate.len();
-        let win_percent = (wins as f32 / total_games as f32) * 100.0;
-        results.push((comb.clone(), win_percent));
+        let mut result = [CombinationResult::new(comb.clone(), enemies.len())];
+        play_games(
+            &[completed_deck],
+            &games,
+            &opt_config,
+            &player_codes,
+            &parallel_config,
+            game_callback,
+            &mut result,
+        );
+        let [result] = result;
+        let win_percent = (result.win_rate() * 100.0) as f32;
+        progress.results.push(result);
+        progress.combinations_done = index + 1;
+        if let Some(checkpoint) = checkpoint.as_mut() {
+            checkpoint.save_if_due(&progress);
+        }
 
         warn!("Combination {comb:?} win percentage: {win_percent:.2}%");
 
@@ -360,40 +441,551 @@ where
         if let Some(ref cbs) = callbacks {
             if let Some(ref callback) = cbs.on_combination_complete {
                 let total_combinations = combinations.len();
-                callback(results.len(), total_combinations, comb, win_percent);
+                callback(
+                    progress.results.len(),
+                    total_combinations,
+                    comb,
+                    win_percent,
+                );
             }
         }
     }
-
-    // Find the best combination
-    let mut best_win_percent = 0.0;
-    let mut best_combination = None;
-    for (comb, win_percent) in &results {
-        if *win_percent > best_win_percent {
-            best_win_percent = *win_percent;
-            best_combination = Some(comb.clone());
-        }
+    if let Some(checkpoint) = checkpoint.as_mut() {
+        checkpoint.save(&progress);
     }
 
-    // Report the best combination found.
-    match best_combination {
-        Some(comb) => {
-            warn!("Best combination: {comb:?} with win percentage: {best_win_percent:.2}%");
+    let report = OptimizationReport::new(enemy_names, progress.results);
+    match report.combinations.first() {
+        Some(best) => {
+            warn!(
+                "Best combination: {:?} with win percentage: {:.2}%",
+                best.cards,
+                best.win_rate() * 100.0
+            );
         }
         None => {
             warn!("No valid combination found.");
         }
     }
+    report
+}
+
+/// Combinations evaluated so far by `optimize_with_configs`, as saved in its checkpoints
+#[derive(Serialize, Deserialize)]
+struct ExhaustiveProgress {
+    combinations_done: usize,
+    results: Vec<CombinationResult>,
+}
+
+/// Rounds raced so far by `optimize_successive_halving`, as saved in its checkpoints
+#[derive(Serialize, Deserialize)]
+struct HalvingProgress {
+    rounds_done: usize,
+    /// Indices of the combinations still racing, best first
+    survivors: Vec<usize>,
+    /// Games played per enemy deck by every survivor
+    played: Vec<u32>,
+    results: Vec<CombinationResult>,
+}
+
+/// Parameters that identify an optimization run in its checkpoints
+fn optimization_run(
+    incomplete_deck: &Deck,
+    combinations: &[Vec<CardId>],
+    opt_config: &OptimizationConfig,
+    top_k: Option<usize>,
+) -> serde_json::Value {
+    serde_json::json!({
+        "incomplete_deck": deck_card_ids(incomplete_deck),
+        "combinations": combinations,
+        "enemy_decks": opt_config
+            .enemy_deck_configs
+            .iter()
+            .map(|enemy| (&enemy.name, deck_card_ids(&enemy.deck), enemy.num_games))
+            .collect::<Vec<_>>(),
+        "players": player_names(&opt_config.players),
+        "seed": opt_config.seed,
+        "top_k": top_k,
+    })
+}
+
+/// Plays every game of `games`, given as `(deck index, enemy index, seed index)`, with
+/// the deck as player 0, and records the outcomes in `results` (indexed like `decks`)
+fn play_games<G>(
+    decks: &[Deck],
+    games: &[(usize, usize, u64)],
+    opt_config: &OptimizationConfig,
+    player_codes: &[PlayerCode],
+    parallel_config: &ParallelConfig,
+    on_game_complete: Option<&G>,
+    results: &mut [CombinationResult],
+) where
+    G: Fn() + Sync,
+{
+    let play_single_game = |&(deck, enemy, seed_index): &(usize, usize, u64)| {
+        let seed = game_seed(opt_config.seed, seed_index);
+        let played = play_game(
+            &decks[deck],
+            &opt_config.enemy_deck_configs[enemy].deck,
+            player_codes.to_vec(),
+            seed,
+        );
+        if let Some(callback) = on_game_complete {
+            callback();
+        }
+        (deck, enemy, played.first_player, played.state.winner)
+    };
+    let outcomes: Vec<_> = if parallel_config.enabled {
+        games.par_iter().map(play_single_game).collect()
+    } else {
+        games.iter().map(play_single_game).collect()
+    };
+    for (deck, enemy, first_player, outcome) in outcomes {
+        results[deck].record(enemy, first_player, outcome);
+    }
+}
+
+/// Index of the first seed of each enemy deck's games, so that no two games share a seed
+fn enemy_seed_offsets(enemies: &[EnemyDeckConfig]) -> Vec<u64> {
+    enemies
+        .iter()
+        .scan(0u64, |offset, enemy| {
+            let start = *offset;
+            *offset += enemy.num_games as u64;
+            Some(start)
+        })
+        .collect()
+}
+
+/// How `cli_optimize` spends its game budget
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum OptimizationStrategy {
+    /// Every valid combination plays the full number of games
+    #[default]
+    Exhaustive,
+    /// Combinations race in rounds of successive halving; only the best `top_k`
+    /// play the full number of games
+    SuccessiveHalving { top_k: usize },
+}
+
+/// Results of one completed deck during an optimization
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CombinationResult {
+    /// Cards added to the incomplete deck
+    pub cards: Vec<CardId>,
+    pub tally: MatchTally,
+    /// Games where the completed deck went first
+    pub going_first: MatchTally,
+    pub going_second: MatchTally,
+    /// One tally per enemy deck, in the order of the optimization's enemy decks
+    pub per_enemy: Vec<MatchTally>,
+}
+
+impl CombinationResult {
+    pub fn new(cards: Vec<CardId>, num_enemies: usize) -> Self {
+        CombinationResult {
+            cards,
+            tally: MatchTally::default(),
+            going_first: MatchTally::default(),
+            going_second: MatchTally::default(),
+            per_enemy: vec![MatchTally::default(); num_enemies],
+        }
+    }
+
+    /// Records a game of the completed deck (player 0) against the `enemy`-th enemy deck
+    pub fn record(&mut self, enemy: usize, first_player: usize, outcome: Option<GameOutcome>) {
+        self.tally.record(outcome, 0);
+        self.per_enemy[enemy].record(outcome, 0);
+        if first_player == 0 {
+            self.going_first.record(outcome, 0);
+        } else {
+            self.going_second.record(outcome, 0);
+        }
+    }
+
+    pub fn win_rate(&self) -> f64 {
+        self.tally.win_rate()
+    }
+
+    pub fn confidence_interval(&self) -> (f64, f64) {
+        self.tally.confidence_interval()
+    }
+
+    /// Short description of the added cards (e.g. "Erika, Erika")
+    pub fn label(&self) -> String {
+        self.cards
+            .iter()
+            .map(|&id| get_card_ref(id).get_name())
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+}
+
+/// Outcome of an optimization: every evaluated combination, best first
+#[derive(Debug, Clone)]
+pub struct OptimizationReport {
+    pub enemy_names: Vec<String>,
+    pub combinations: Vec<CombinationResult>,
+}
+
+impl OptimizationReport {
+    /// Ranks the combinations by win rate (ties keep their order)
+    pub fn new(enemy_names: Vec<String>, mut combinations: Vec<CombinationResult>) -> Self {
+        combinations.sort_by(|a, b| b.win_rate().total_cmp(&a.win_rate()));
+        OptimizationReport {
+            enemy_names,
+            combinations,
+        }
+    }
+
+    pub fn best(&self) -> Option<&CombinationResult> {
+        self.combinations.first()
+    }
+
+    /// Ranked combinations with their cards (by id), overall, going-first/second and
+    /// per-enemy results, each with its win rate and 95% confidence interval
+    pub fn to_json(&self) -> serde_json::Value {
+        let tally_json = |tally: &MatchTally| {
+            serde_json::json!({
+                "wins": tally.wins,
+                "losses": tally.losses,
+                "ties": tally.ties,
+                "games": tally.games(),
+                "win_rate": tally.win_rate(),
+                "confidence_interval": tally.confidence_interval(),
+            })
+        };
+        let combinations: Vec<serde_json::Value> = self
+            .combinations
+            .iter()
+            .enumerate()
+            .map(|(rank, result)| {
+                serde_json::json!({
+                    "rank": rank + 1,
+                    "cards": result
+                        .cards
+                        .iter()
+                        .map(|&id| get_card_ref(id).get_id())
+                        .collect::<Vec<_>>(),
+                    "card_names": result
+                        .cards
+                        .iter()
+                        .map(|&id| get_card_ref(id).get_name())
+                        .collect::<Vec<_>>(),
+                    "overall": tally_json(&result.tally),
+                    "going_first": tally_json(&result.going_first),
+                    "going_second": tally_json(&result.going_second),
+                    "per_enemy": self
+                        .enemy_names
+                        .iter()
+                        .zip(&result.per_enemy)
+                        .map(|(name, tally)| {
+                            let mut value = tally_json(tally);
+                            value["name"] = serde_json::json!(name);
+                            value
+                        })
+                        .collect::<Vec<_>>(),
+                })
+            })
+            .collect();
+        serde_json::json!({
+            "enemy_decks": self.enemy_names,
+            "combinations": combinations,
+        })
+    }
+
+    pub fn write_json(&self, path: &str) -> Result<(), String> {
+        let json = serde_json::to_string_pretty(&self.to_json())
+            .map_err(|err| format!("Failed to serialize optimization report: {err}"))?;
+        fs::write(path, json).map_err(|err| format!("Failed to write {path}: {err}"))
+    }
+}
+
+/// Cumulative games per enemy deck that surviving combinations have played after each
+/// round of successive halving. Rounds halve the field until at most `top_k` combinations
+/// are left, and the last round brings them to `num_games`.
+pub fn halving_schedule(num_combinations: usize, top_k: usize, num_games: u32) -> Vec<u32> {
+    let top_k = top_k.max(1);
+    let mut rounds = 1;
+    let mut survivors = num_combinations;
+    while survivors > top_k {
+        survivors = survivors.div_ceil(2).max(top_k);
+        rounds += 1;
+    }
+    (0..rounds)
+        .map(|round| {
+            let divisor = 1u64 << (rounds - 1 - round).min(63);
+            ((num_games as u64).div_ceil(divisor) as u32).max(1)
+        })
+        .collect()
+}
+
+/// Optimizes an incomplete deck by racing combinations with successive halving.
+///
+/// Every round, the surviving combinations play more games against each enemy deck (twice as
+/// many as before), then the worse half is dropped, as is any combination whose confidence
+/// interval lies entirely below that of the `top_k`-th best. All combinations replay the same
+/// seeds, so they are compared on the same draws. Returns the best `top_k` combinations,
+/// each having played the configured number of games against every enemy deck.
+pub fn optimize_successive_halving<G>(
+    incomplete_deck: &Deck,
+    candidate_cards: &[String],
+    opt_config: &OptimizationConfig,
+    top_k: usize,
+    parallel_config: &ParallelConfig,
+    on_game_complete: Option<G>,
+) -> OptimizationReport
+where
+    G: Fn() + Sync,
+{
+    let enemies = &opt_config.enemy_deck_configs;
+    let enemy_names: Vec<String> = enemies.iter().map(|enemy| enemy.name.clone()).collect();
+    if enemies.is_empty() {
+        warn!("No valid enemy decks provided. Optimization cannot proceed.");
+        return OptimizationReport::new(enemy_names, Vec::new());
+    }
+    let Some(candidate_card_ids) = parse_candidate_cards(candidate_cards) else {
+        return OptimizationReport::new(enemy_names, Vec::new());
+    };
+    let missing_count = 20 - incomplete_deck.cards.len();
+    if missing_count == 0 {
+        warn!("Deck is already complete (20 cards). No optimization needed.");
+        return OptimizationReport::new(enemy_names, Vec::new());
+    }
+    let mut combinations =
+        generate_valid_combinations(incomplete_deck, &candidate_card_ids, missing_count);
+    retain_affordable(
+        &mut combinations,
+        incomplete_deck,
+        opt_config.inventory.as_ref(),
+    );
+    retain_legal(
+        &mut combinations,
+        incomplete_deck,
+        opt_config.format.as_ref(),
+    );
+    let completed_decks: Vec<Deck> = combinations
+        .iter()
+        .map(|comb| complete_deck(incomplete_deck, comb))
+        .collect();
+
+    if let Some(num_threads) = parallel_config.num_threads {
+        rayon::ThreadPoolBuilder::new()
+            .num_threads(num_threads)
+            .build_global()
+            .ok(); // Ignore error if pool is already initialized
+    }
+
+    // Every enemy deck gets its own schedule; they all have the same number of rounds
+    let schedules: Vec<Vec<u32>> = enemies
+        .iter()
+        .map(|enemy| halving_schedule(combinations.len(), top_k, enemy.num_games))
+        .collect();
+    let num_rounds = schedules[0].len();
+    let seed_offsets = enemy_seed_offsets(enemies);
+    warn!(
+        "Racing {} combinations over {} rounds (top {} play {} games in total)",
+        combinations.len(),
+        num_rounds,
+        top_k,
+        enemies.iter().map(|enemy| enemy.num_games).sum::<u32>()
+    );
+    let player_codes = fill_code_array(opt_config.players.clone());
+    let mut progress = HalvingProgress {
+        rounds_done: 0,
+        survivors: (0..combinations.len()).collect(),
+        played: vec![0; enemies.len()],
+        results: combinations
+            .iter()
+            .map(|comb| CombinationResult::new(comb.clone(), enemies.len()))
+            .collect(),
+    };
+    let mut checkpoint = match &opt_config.checkpoint {
+        Some(config) => {
+            let run = optimization_run(incomplete_deck, &combinations, opt_config, Some(top_k));
+            match Checkpoint::open(config, "optimize", run, opt_config.seed) {
+                Ok((checkpoint, saved)) => {
+                    progress = saved.unwrap_or(progress);
+                    Some(checkpoint)
+                }
+                Err(err) => {
+                    warn!("{err}");
+                    return OptimizationReport::new(enemy_names, Vec::new());
+                }
+            }
+        }
+        None => None,
+    };
+    let seeded_config;
+    let opt_config = match &checkpoint {
+        Some(checkpoint) => {
+            seeded_config = OptimizationConfig {
+                seed: Some(checkpoint.seed()),
+                ..opt_config.clone()
+            };
+            &seeded_config
+        }
+        None => opt_config,
+    };
+    let HalvingProgress {
+        rounds_done,
+        mut survivors,
+        mut played,
+        mut results,
+    } = progress;
+
+    for round in rounds_done..num_rounds {
+        // Seeds only depend on the enemy deck and game number, never on the combination
+        let games: Vec<(usize, usize, u64)> = survivors
+            .iter()
+            .flat_map(|&combination| {
+                let (schedules, played, seed_offsets) = (&schedules, &played, &seed_offsets);
+                (0..enemies.len()).flat_map(move |enemy| {
+                    (played[enemy]..schedules[enemy][round])
+                        .map(move |game| (combination, enemy, seed_offsets[enemy] + game as u64))
+                })
+            })
+            .collect();
+        play_games(
+            &completed_decks,
+            &games,
+            opt_config,
+            &player_codes,
+            parallel_config,
+            on_game_complete.as_ref(),
+            &mut results,
+        );
+        for (played, schedule) in played.iter_mut().zip(&schedules) {
+            *played = schedule[round];
+        }
+
+        survivors.sort_by(|&a, &b| results[b].win_rate().total_cmp(&results[a].win_rate()));
+        if round + 1 < num_rounds {
+            let keep = survivors.len().div_ceil(2).max(top_k);
+            survivors.truncate(keep);
+            if let Some(&kth_best) = survivors.get(top_k.saturating_sub(1)) {
+                let threshold = results[kth_best].confidence_interval().0;
+                let mut rank = 0;
+                survivors.retain(|&combination| {
+                    rank += 1;
+                    rank <= top_k || results[combination].confidence_interval().1 >= threshold
+                });
+            }
+            warn!(
+                "Round {}: {} games per combination, {} combinations left",
+                round + 1,
+                played.iter().sum::<u32>(),
+                survivors.len()
+            );
+        }
+
+        if let Some(checkpoint) = checkpoint.as_mut() {
+            let progress = HalvingProgress {
+                rounds_done: round + 1,
+                survivors: survivors.clone(),
+                played: played.clone(),
+                results: results.clone(),
+            };
+            if round + 1 == num_rounds {
+                checkpoint.save(&progress);
+            } else {
+                checkpoint.save_if_due(&progress);
+            }
+        }
+    }
 
-    results.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap());
-    results
+    let best = survivors
+        .into_iter()
+        .take(top_k)
+        .map(|combination| results[combination].clone())
+        .collect();
+    OptimizationReport::new(enemy_names, best)
 }
 
-/// Attempts to robustly parse a card ID string, handling various formats and padding the number if needed.
-fn robustly_parse_card_id_string(orig: &str) -> CardId {
+/// Prints the ranked combinations, then how the best one did against each enemy deck
+pub fn print_optimization_report(report: &OptimizationReport) {
+    let labels: Vec<String> = report
+        .combinations
+        .iter()
+        .map(CombinationResult::label)
+        .collect();
+    let label_width = labels
+        .iter()
+        .map(|label| label.chars().count())
+        .max()
+        .unwrap_or(0)
+        .max("Cards".len());
+    warn!(
+        "{:>4}  {:<label_width$}  {:>7}  {:>6}  {:>15}  {:>6}  {:>6}",
+        "Rank", "Cards", "Games", "Win%", "95% CI", "1st%", "2nd%"
+    );
+    for (rank, (result, label)) in report.combinations.iter().zip(&labels).enumerate() {
+        let (low, high) = result.confidence_interval();
+        warn!(
+            "{:>4}  {:<label_width$}  {:>7}  {:>5.1}%  {:>15}  {:>5.1}%  {:>5.1}%",
+            rank + 1,
+            label,
+            result.tally.games().to_formatted_string(&Locale::en),
+            result.win_rate() * 100.0,
+            format!("{:.1}%-{:.1}%", low * 100.0, high * 100.0),
+            result.going_first.win_rate() * 100.0,
+            result.going_second.win_rate() * 100.0
+        );
+    }
+
+    let Some(best) = report.best() else {
+        return;
+    };
+    let name_width = report
+        .enemy_names
+        .iter()
+        .map(|name| name.chars().count())
+        .max()
+        .unwrap_or(0)
+        .max("Opponent".len());
+    warn!("Best combination ({}) by enemy deck:", best.label());
+    warn!(
+        "{:<name_width$}  {:>7}  {:>6}  {:>15}",
+        "Opponent", "Games", "Win%", "95% CI"
+    );
+    for (name, tally) in report.enemy_names.iter().zip(&best.per_enemy) {
+        let (low, high) = tally.confidence_interval();
+        warn!(
+            "{:<name_width$}  {:>7}  {:>5.1}%  {:>15}",
+            name,
+            tally.games().to_formatted_string(&Locale::en),
+            tally.win_rate() * 100.0,
+            format!("{:.1}%-{:.1}%", low * 100.0, high * 100.0)
+        );
+    }
+}
+
+/// The incomplete deck with the given cards added
+fn complete_deck(incomplete_deck: &Deck, cards: &[CardId]) -> Deck {
+    let mut completed_deck = incomplete_deck.clone();
+    for card_id in cards {
+        completed_deck.cards.push(get_card_by_enum(*card_id));
+    }
+    completed_deck
+}
+
+/// Parses a card given by ID in any of the usual formats (e.g. "A1 53", "P-A 5", "A1219"),
+/// or else by name (e.g. "Giovanni" or "Mewtwo ex (A1)", see `resolve_card_name`).
+pub fn try_parse_card_id_string(orig: &str) -> Result<CardId, String> {
+    parse_card_id(orig).or_else(|id_error| match resolve_card_name(orig) {
+        Ok(card_id) => Ok(card_id),
+        Err(CardNameError::NotFound { suggestions, .. }) if suggestions.is_empty() => Err(id_error),
+        Err(name_error) => Err(name_error.to_string()),
+    })
+}
+
+fn parse_card_id(orig: &str) -> Result<CardId, String> {
     let s = orig.trim().replace(' ', "");
     if s.len() < 3 {
-        panic!("Card ID '{}' should be at least 3 characters long", orig);
+        return Err(format!(
+            "Card ID '{}' should be at least 3 characters long",
+            orig
+        ));
     }
     // Try splitting by space first
     let (prefix, number) = if let Some(idx) = orig.find([' ']) {
@@ -402,18 +994,30 @@ fn robustly_parse_card_id_string(orig: &str) -> CardId {
         (pre.trim(), num.trim())
     } else {
         // fallback: last 3 chars as number, rest as prefix
-        let number = &s[s.len() - 3..];
-        let prefix = &s[..s.len() - 3];
-        (prefix, number)
+        let Some(split) = s.len().checked_sub(3).filter(|&i| s.is_char_boundary(i)) else {
+            return Err(format!("Invalid card ID '{}' in candidate cards", orig));
+        };
+        (&s[..split], &s[split..])
     };
     let padded_number = format!("{:0>3}", number);
     let id = format!("{prefix} {padded_number}");
     CardId::from_card_id(id.as_str())
-        .unwrap_or_else(|| panic!("Invalid card ID '{}' in candidate cards", orig))
+        .ok_or_else(|| format!("Invalid card ID '{}' in candidate cards", orig))
+}
+
+/// Parses the candidate cards, warning about the first one that isn't a card
+fn parse_candidate_cards(candidate_cards: &[String]) -> Option<Vec<CardId>> {
+    candidate_cards
+        .iter()
+        .map(|s| try_parse_card_id_string(s))
+        .collect::<Result<Vec<_>, _>>()
+        .inspect_err(|err| warn!("{err}"))
+        .ok()
 }
 
 /// Deduplicates combinations by creating canonical representations based on card counts.
-/// This is useful when the candidate pool may have repeated cards.
+/// This is useful when the candidate pool may have repeated cards, or reprints of a card
+/// (see `canonical_card_id`), which make the same deck.
 pub fn deduplicate_combinations(combinations: Vec<Vec<CardId>>) -> Vec<Vec<CardId>> {
     use std::collections::{HashMap, HashSet};
     let mut seen = HashSet::new();
@@ -423,7 +1027,7 @@ pub fn deduplicate_combinations(combinations: Vec<Vec<CardId>>) -> Vec<Vec<CardI
             // Create a canonical representation using card counts
             let mut counts: HashMap<CardId, usize> = HashMap::new();
             for &card_id in comb {
-                *counts.entry(card_id).or_insert(0) += 1;
+                *counts.entry(canonical_card_id(card_id)).or_insert(0) += 1;
             }
             // Convert to a sorted vector - sort by discriminant value (as usize)
             let mut canonical: Vec<_> = counts.into_iter().collect();
@@ -446,8 +1050,7 @@ pub fn generate_valid_combinations(
         .filter(|comb| {
             let mut test_deck = incomplete_deck.clone();
             for card_id in comb {
-                let card = get_card_by_enum(*card_id);
-                test_deck.cards.push(card);
+                test_deck.cards.push(get_card_by_enum(*card_id));
             }
             test_deck.is_valid()
         })
@@ -460,8 +1063,57 @@ fn count_valid_combinations(
     incomplete_deck: &Deck,
     candidates: &[CardId],
     missing_count: usize,
+    inventory: Option<&Inventory>,
+    format: Option<&Format>,
 ) -> usize {
-    generate_valid_combinations(incomplete_deck, candidates, missing_count).len()
+    let mut combinations = generate_valid_combinations(incomplete_deck, candidates, missing_count);
+    retain_affordable(&mut combinations, incomplete_deck, inventory);
+    retain_legal(&mut combinations, incomplete_deck, format);
+    combinations.len()
+}
+
+/// Drops the combinations whose completed deck can't be put together from the inventory.
+/// Inventories only hold `CardId`s, so none is affordable with cards loaded at runtime.
+fn retain_affordable(
+    combinations: &mut Vec<Vec<CardId>>,
+    incomplete_deck: &Deck,
+    inventory: Option<&Inventory>,
+) {
+    let Some(inventory) = inventory else {
+        return;
+    };
+    let deck_cards = match incomplete_deck.card_ids() {
+        Ok(deck_cards) => deck_cards,
+        Err(err) => {
+            warn!("The inventory can't provide the deck: {err}");
+            combinations.clear();
+            return;
+        }
+    };
+    combinations.retain(|comb| {
+        let cards: Vec<CardId> = deck_cards.iter().chain(comb).copied().collect();
+        inventory.allows(&cards)
+    });
+}
+
+/// Drops the combinations whose completed deck the format doesn't allow
+fn retain_legal(
+    combinations: &mut Vec<Vec<CardId>>,
+    incomplete_deck: &Deck,
+    format: Option<&Format>,
+) {
+    let Some(format) = format else {
+        return;
+    };
+    combinations.retain(|comb| {
+        let cards: Vec<Card> = incomplete_deck
+            .cards
+            .iter()
+            .copied()
+            .chain(comb.iter().map(|&id| get_card_by_enum(id)))
+            .collect();
+        format.deck_issues(&cards).is_empty()
+    });
 }
 
 /// Generates all unique k-combinations from the candidate cards.
@@ -544,16 +1196,193 @@ mod tests {
     }
 
     #[test]
-    fn test_robustly_parse_card_id_string() {
+    fn test_halving_schedule() {
+        assert_eq!(halving_schedule(3, 5, 100), vec![100]);
+        assert_eq!(halving_schedule(16, 2, 100), vec![13, 25, 50, 100]);
+        assert_eq!(halving_schedule(5, 1, 2), vec![1, 1, 1, 2]);
+    }
+
+    #[test]
+    fn test_optimize_successive_halving() {
+        let incomplete_deck = Deck::from_string(
+            "Energy: Grass\n2 Bulbasaur A1 1\n1 Ivysaur A1 2\n2 Venusaur ex A1 4\n2 Snivy A1a 4\n2 Serperior A1a 6\n2 Rocky Helmet A2 148\n2 Rare Candy A3 144\n2 Leaf Cape A3 147\n2 Poké Ball P-A 5\n1 Professor's Research P-A 7",
+        )
+        .unwrap();
+        let candidate_cards: Vec<String> = ["A1 219", "A1 219", "A3 155", "A3 155", "P-A 7"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+        let opt_config = OptimizationConfig {
+            enemy_deck_configs: vec![EnemyDeckConfig {
+                name: "weezing-arbok".to_string(),
+                deck: Deck::from_file("example_decks/weezing-arbok.txt").unwrap(),
+                num_games: 4,
+            }],
+            players: Some(vec![PlayerCode::R, PlayerCode::R]),
+            seed: Some(5),
+            inventory: None,
+            format: None,
+            checkpoint: None,
+        };
+
+        let report = optimize_successive_halving(
+            &incomplete_deck,
+            &candidate_cards,
+            &opt_config,
+            2,
+            &ParallelConfig::default(),
+            None::<fn()>,
+        );
+        let results = &report.combinations;
+        assert_eq!(results.len(), 2);
+        for result in results {
+            assert_eq!(result.cards.len(), 2);
+            assert_eq!(result.tally.games(), 4);
+        }
+        assert!(results[0].win_rate() >= results[1].win_rate());
+    }
+
+    #[test]
+    fn test_optimization_report_breaks_down_results() {
+        let incomplete_deck = Deck::from_string(
+            "Energy: Grass\n2 Bulbasaur A1 1\n1 Ivysaur A1 2\n2 Venusaur ex A1 4\n2 Snivy A1a 4\n2 Serperior A1a 6\n2 Rocky Helmet A2 148\n2 Rare Candy A3 144\n2 Leaf Cape A3 147\n2 Poké Ball P-A 5\n1 Professor's Research P-A 7",
+        )
+        .unwrap();
+        let candidate_cards = vec!["A1 219".to_string(), "P-A 7".to_string()];
+        let enemy = |name: &str| EnemyDeckConfig {
+            name: name.to_string(),
+            deck: Deck::from_file(&format!("example_decks/{name}.txt")).unwrap(),
+            num_games: 3,
+        };
+        let opt_config = OptimizationConfig {
+            enemy_deck_configs: vec![enemy("weezing-arbok"), enemy("mewtwoex")],
+            players: Some(vec![PlayerCode::R, PlayerCode::R]),
+            seed: Some(7),
+            inventory: None,
+            format: None,
+            checkpoint: None,
+        };
+
+        let report = optimize_with_configs(
+            &incomplete_deck,
+            &candidate_cards,
+            opt_config,
+            ParallelConfig::default(),
+            None::<OptimizationCallbacks<fn(usize, usize, &[CardId], f32), fn()>>,
+        );
+        let best = report.best().unwrap();
+        assert_eq!(best.tally.games(), 6);
+        assert_eq!(
+            best.going_first.games() + best.going_second.games(),
+            best.tally.games()
+        );
+        assert_eq!(
+            best.per_enemy
+                .iter()
+                .map(MatchTally::games)
+                .collect::<Vec<_>>(),
+            vec![3, 3]
+        );
+
+        let json = report.to_json();
+        assert_eq!(json["enemy_decks"][1], "mewtwoex");
+        assert_eq!(json["combinations"][0]["rank"], 1);
+        assert_eq!(json["combinations"][0]["overall"]["games"], 6);
+        assert_eq!(
+            json["combinations"][0]["per_enemy"][0]["name"],
+            "weezing-arbok"
+        );
+    }
+
+    #[test]
+    fn test_optimize_only_tries_owned_combinations() {
+        let deck_list = "Energy: Grass\n2 Bulbasaur A1 1\n1 Ivysaur A1 2\n2 Venusaur ex A1 4\n2 Snivy A1a 4\n2 Serperior A1a 6\n2 Rocky Helmet A2 148\n2 Rare Candy A3 144\n2 Leaf Cape A3 147\n2 Poké Ball P-A 5\n1 Professor's Research P-A 7";
+        let incomplete_deck = Deck::from_string(deck_list).unwrap();
+        let candidate_cards: Vec<String> = ["A1 219", "A1 219", "A3 155", "A3 155", "P-A 7"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+        // The deck itself plus two A3 155, and nothing to craft Erika with
+        let inventory = Inventory::from_string(&format!("{deck_list}\nA3 155, 2")).unwrap();
+        let opt_config = OptimizationConfig {
+            enemy_deck_configs: vec![EnemyDeckConfig {
+                name: "weezing-arbok".to_string(),
+                deck: Deck::from_file("example_decks/weezing-arbok.txt").unwrap(),
+                num_games: 2,
+            }],
+            players: Some(vec![PlayerCode::R, PlayerCode::R]),
+            seed: Some(5),
+            inventory: Some(inventory),
+            format: None,
+            checkpoint: None,
+        };
+
+        let results = optimize_successive_halving(
+            &incomplete_deck,
+            &candidate_cards,
+            &opt_config,
+            2,
+            &ParallelConfig::default(),
+            None::<fn()>,
+        )
+        .combinations;
+        let a3_155 = CardId::from_card_id("A3 155").unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].cards, vec![a3_155, a3_155]);
+    }
+
+    #[test]
+    fn test_optimize_only_tries_combinations_legal_in_format() {
+        let incomplete_deck = Deck::from_string("Energy: Grass\n2 Bulbasaur A1 1\n1 Ivysaur A1 2\n2 Venusaur ex A1 4\n2 Snivy A1a 4\n2 Serperior A1a 6\n2 Rocky Helmet A2 148\n2 Rare Candy A3 144\n2 Leaf Cape A3 147\n2 Poké Ball P-A 5\n1 Professor's Research P-A 7").unwrap();
+        let candidate_cards: Vec<String> = ["A1 219", "A1 219", "A3 155", "A3 155", "P-A 7"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+        // Erika is banned and the deck already runs its one allowed Professor's Research
+        let format = Format::from_string("Banned: A1 219\nLimit: P-A 7, 1").unwrap();
+        let opt_config = OptimizationConfig {
+            enemy_deck_configs: vec![EnemyDeckConfig {
+                name: "weezing-arbok".to_string(),
+                deck: Deck::from_file("example_decks/weezing-arbok.txt").unwrap(),
+                num_games: 2,
+            }],
+            players: Some(vec![PlayerCode::R, PlayerCode::R]),
+            seed: Some(5),
+            inventory: None,
+            format: Some(format),
+            checkpoint: None,
+        };
+
+        let results = optimize_successive_halving(
+            &incomplete_deck,
+            &candidate_cards,
+            &opt_config,
+            2,
+            &ParallelConfig::default(),
+            None::<fn()>,
+        )
+        .combinations;
+        let a3_155 = CardId::from_card_id("A3 155").unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].cards, vec![a3_155, a3_155]);
+    }
+
+    #[test]
+    fn test_try_parse_card_id_string() {
         let cases = vec![
             ("A1 53", CardId::from_card_id("A1 053").unwrap()),
             ("P-A 5", CardId::from_card_id("P-A 005").unwrap()),
             ("A1219", CardId::from_card_id("A1 219").unwrap()),
             ("A2a 072", CardId::from_card_id("A2a 072").unwrap()),
+            ("Pikachu ex (A1)", CardId::A1096PikachuEx),
+            ("poke ball", CardId::A2b111PokeBall),
         ];
         for (input, expected) in cases {
-            let parsed = robustly_parse_card_id_string(input);
-            assert_eq!(parsed, expected, "Failed to parse '{}'", input);
+            let parsed = try_parse_card_id_string(input);
+            assert_eq!(parsed, Ok(expected), "Failed to parse '{}'", input);
         }
+        let err = try_parse_card_id_string("Pikachu ex").unwrap_err();
+        assert!(err.contains("add the set"), "{err}");
+        assert!(try_parse_card_id_string("Z9 999").is_err());
     }
 }
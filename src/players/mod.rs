@@ -22,7 +22,7 @@ pub use weighted_random_player::WeightedRandomPlayer;
 
 use crate::{actions::Action, Deck, State};
 use rand::rngs::StdRng;
-use std::fmt::Debug;
+use std::fmt::{self, Debug};
 
 pub trait Player: Debug + Send {
     fn get_deck(&self) -> Deck;
@@ -47,6 +47,23 @@ pub enum PlayerCode {
     E { max_depth: usize },
     ER, // Evolution Rusher
 }
+
+/// Formats the code the same way `parse_player_code` reads it (e.g. "e2", "r")
+impl fmt::Display for PlayerCode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PlayerCode::AA => write!(f, "aa"),
+            PlayerCode::ET => write!(f, "et"),
+            PlayerCode::R => write!(f, "r"),
+            PlayerCode::H => write!(f, "h"),
+            PlayerCode::W => write!(f, "w"),
+            PlayerCode::M => write!(f, "m"),
+            PlayerCode::V => write!(f, "v"),
+            PlayerCode::E { max_depth } => write!(f, "e{max_depth}"),
+            PlayerCode::ER => write!(f, "er"),
+        }
+    }
+}
 /// Custom parser function enforcing case-insensitivity
 pub fn parse_player_code(s: &str) -> Result<PlayerCode, String> {
     let lower = s.to_ascii_lowercase();
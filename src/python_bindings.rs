@@ -10,16 +10,27 @@ use std::collections::HashMap;
 use crate::{
     actions::attacks::Mechanic,
     actions::{
-        get_attack_mechanic, get_enhanced_ability_mechanic, get_simulator_ability_mechanic,
-        trainer_mechanic::TrainerMechanic, Action, SimpleAction, EFFECT_MECHANIC_MAP,
+        attack_mechanic_from_effect, get_attack_mechanic, get_enhanced_ability_mechanic,
+        get_simulator_ability_mechanic, trainer_mechanic::TrainerMechanic, Action, SimpleAction,
     },
+    card_database::{get_card_by_id, runtime_cards},
     card_ids::CardId,
+    card_search::{search_cards as search, CardQuery},
+    database::get_card_by_enum,
     deck::Deck,
+    deck_builder::parse_energy_type,
     encoding,
     game::Game,
     generate_possible_actions,
     models::{Ability, Attack, Card, EnergyType, PlayedCard},
+    odds::{deck_odds, OddsOptions},
+    optimize::{
+        optimize_successive_halving, optimize_with_configs, try_parse_card_id_string,
+        EnemyDeckConfig, OptimizationCallbacks, OptimizationConfig, OptimizationReport,
+        ParallelConfig,
+    },
     players::{create_players, fill_code_array, parse_player_code, PlayerCode, RandomPlayer},
+    simulate::deck_name_from_path,
     state::{GameOutcome, State},
 };
 
@@ -96,7 +107,7 @@ impl PyAttack {
     #[getter]
     fn mechanic_info(&self, py: Python) -> Option<PyObject> {
         let effect_text = self.attack.effect.as_deref()?;
-        let mechanic = EFFECT_MECHANIC_MAP.get(effect_text)?;
+        let mechanic = attack_mechanic_from_effect(effect_text)?;
         let json_str = serde_json::to_string(mechanic).ok()?;
         let json_module = py.import_bound("json").ok()?;
         json_module
@@ -233,7 +244,7 @@ impl PyCard {
 
     #[getter]
     fn trainer_mechanic_info(&self, py: Python) -> Option<PyObject> {
-        let card_id = self.card.get_card_id();
+        let card_id = CardId::from_card_id(&self.card.get_id())?;
         let mechanic = card_id.get_trainer_mechanic()?;
         let json_str = serde_json::to_string(&mechanic).ok()?;
         let json_module = py.import_bound("json").ok()?;
@@ -251,7 +262,7 @@ impl PyCard {
             let mechanic = get_attack_mechanic(&self.card, i).or_else(|| {
                 atk.effect
                     .as_deref()
-                    .and_then(|text| EFFECT_MECHANIC_MAP.get(text))
+                    .and_then(attack_mechanic_from_effect)
                     .cloned()
             });
 
@@ -316,7 +327,7 @@ pub struct PyPlayedCard {
 impl PyPlayedCard {
     #[getter]
     fn card(&self) -> PyCard {
-        self.played_card.card.clone().into()
+        self.played_card.card.into()
     }
 
     #[getter]
@@ -375,7 +386,7 @@ impl PyPlayedCard {
 
     #[getter]
     fn attached_tool(&self) -> Option<PyCard> {
-        self.played_card.attached_tool.clone().map(|t| t.into())
+        self.played_card.attached_tool.map(|t| t.into())
     }
 
     #[getter]
@@ -481,6 +492,38 @@ impl PyDeck {
     fn card_count(&self) -> usize {
         self.deck.cards.len()
     }
+
+    /// Decodes a code made by `to_code`
+    #[staticmethod]
+    fn from_code(code: &str) -> PyResult<Self> {
+        let deck = Deck::from_code(code).map_err(PyValueError::new_err)?;
+        Ok(PyDeck { deck })
+    }
+
+    /// The deck in the DeckGym text format, as read from deck files
+    fn to_deckgym_string(&self) -> String {
+        self.deck.to_deckgym_string()
+    }
+
+    /// A short, URL-safe code for sharing the deck
+    fn to_code(&self) -> PyResult<String> {
+        self.deck.to_code().map_err(PyValueError::new_err)
+    }
+
+    /// Whether the deck follows the deck-building rules
+    #[getter]
+    fn is_valid(&self) -> bool {
+        self.deck.is_valid()
+    }
+
+    /// Everything wrong with the deck, including cards the engine doesn't implement yet
+    fn validate(&self) -> Vec<String> {
+        self.deck
+            .validate()
+            .iter()
+            .map(|issue| issue.to_string())
+            .collect()
+    }
 }
 
 /// Python wrapper for State
@@ -511,6 +554,12 @@ impl PyState {
         self.state.winner.map(|outcome| outcome.into())
     }
 
+    /// Why the game ended (e.g. "points_reached", "turn_limit"), None while in progress
+    #[getter]
+    fn end_reason(&self) -> Option<String> {
+        self.state.end_reason.map(|reason| reason.to_string())
+    }
+
     #[getter]
     fn current_energy(&self) -> Option<PyEnergyType> {
         self.state.current_energy.map(|e| e.into())
@@ -539,7 +588,7 @@ impl PyState {
         }
         Ok(self.state.hands[player]
             .iter()
-            .map(|card| card.clone().into())
+            .map(|card| (*card).into())
             .collect())
     }
 
@@ -562,7 +611,7 @@ impl PyState {
         }
         Ok(self.state.discard_piles[player]
             .iter()
-            .map(|card| card.clone().into())
+            .map(|card| (*card).into())
             .collect())
     }
 
@@ -774,12 +823,8 @@ impl PyGame {
         players: Option<Vec<String>>,
         seed: Option<u64>,
     ) -> PyResult<Self> {
-        let deck_a = Deck::from_file(deck_a_path).map_err(|e| {
-            PyErr::new::<pyo3::exceptions::PyIOError, _>(format!("Failed to load deck A: {}", e))
-        })?;
-        let deck_b = Deck::from_file(deck_b_path).map_err(|e| {
-            PyErr::new::<pyo3::exceptions::PyIOError, _>(format!("Failed to load deck B: {}", e))
-        })?;
+        let deck_a = load_valid_deck(deck_a_path, "deck A")?;
+        let deck_b = load_valid_deck(deck_b_path, "deck B")?;
 
         let player_codes = if let Some(player_strs) = players {
             let mut codes = Vec::new();
@@ -1063,6 +1108,15 @@ impl PyGameState {
     }
 }
 
+/// Loads a deck file that can be played, naming it `label` in errors
+fn load_valid_deck(path: &str, label: &str) -> PyResult<Deck> {
+    let deck = Deck::from_file(path)
+        .map_err(|e| PyIOError::new_err(format!("Failed to load {}: {}", label, e)))?;
+    deck.check_valid()
+        .map_err(|e| PyValueError::new_err(format!("Invalid {}: {}", label, e)))?;
+    Ok(deck)
+}
+
 // Helper functions for PyGameState
 
 fn parse_deck(py: Python, source: &PyObject) -> PyResult<Deck> {
@@ -1117,6 +1171,9 @@ pub struct PySimulationResults {
     pub player_b_win_rate: f32,
     #[pyo3(get)]
     pub tie_rate: f32,
+    /// Number of games per end reason (e.g. "points_reached", "turn_limit")
+    #[pyo3(get)]
+    pub end_reasons: HashMap<String, u32>,
 }
 
 #[pymethods]
@@ -1145,12 +1202,8 @@ pub fn py_simulate(
     num_simulations: u32,
     seed: Option<u64>,
 ) -> PyResult<PySimulationResults> {
-    let deck_a = Deck::from_file(deck_a_path).map_err(|e| {
-        PyErr::new::<pyo3::exceptions::PyIOError, _>(format!("Failed to load deck A: {}", e))
-    })?;
-    let deck_b = Deck::from_file(deck_b_path).map_err(|e| {
-        PyErr::new::<pyo3::exceptions::PyIOError, _>(format!("Failed to load deck B: {}", e))
-    })?;
+    let deck_a = load_valid_deck(deck_a_path, "deck A")?;
+    let deck_b = load_valid_deck(deck_b_path, "deck B")?;
 
     let player_codes = if let Some(player_strs) = players {
         let mut codes = Vec::new();
@@ -1168,12 +1221,16 @@ pub fn py_simulate(
 
     // Run simulations
     let mut wins_per_deck = [0u32, 0u32, 0u32]; // [player_a, player_b, ties]
+    let mut end_reasons: HashMap<String, u32> = HashMap::new();
 
     for _ in 0..num_simulations {
         let players = create_players(deck_a.clone(), deck_b.clone(), cli_players.clone());
         let game_seed = seed.unwrap_or_else(rand::random::<u64>);
         let mut game = Game::new(players, game_seed);
         let outcome = game.play();
+        if let Some(reason) = game.state().end_reason {
+            *end_reasons.entry(reason.to_string()).or_insert(0) += 1;
+        }
 
         match outcome {
             Some(GameOutcome::Win(winner)) => {
@@ -1200,6 +1257,254 @@ pub fn py_simulate(
         player_a_win_rate: wins_per_deck[0] as f32 / num_simulations as f32,
         player_b_win_rate: wins_per_deck[1] as f32 / num_simulations as f32,
         tie_rate: wins_per_deck[2] as f32 / num_simulations as f32,
+        end_reasons,
+    })
+}
+
+/// One completed deck of an optimization
+#[pyclass]
+pub struct PyCombinationResult {
+    /// Ids of the cards added to the incomplete deck
+    #[pyo3(get)]
+    pub cards: Vec<String>,
+    #[pyo3(get)]
+    pub card_names: Vec<String>,
+    #[pyo3(get)]
+    pub games: u32,
+    #[pyo3(get)]
+    pub wins: u32,
+    #[pyo3(get)]
+    pub losses: u32,
+    #[pyo3(get)]
+    pub ties: u32,
+    #[pyo3(get)]
+    pub win_rate: f64,
+    /// 95% confidence interval of the win rate
+    #[pyo3(get)]
+    pub confidence_interval: (f64, f64),
+    #[pyo3(get)]
+    pub going_first_win_rate: f64,
+    #[pyo3(get)]
+    pub going_second_win_rate: f64,
+    /// Win rate against each enemy deck, by deck name
+    #[pyo3(get)]
+    pub per_enemy_win_rates: HashMap<String, f64>,
+}
+
+#[pymethods]
+impl PyCombinationResult {
+    fn __repr__(&self) -> String {
+        format!(
+            "CombinationResult(cards={:?}, games={}, win_rate={:.1}%)",
+            self.cards,
+            self.games,
+            self.win_rate * 100.0
+        )
+    }
+}
+
+/// Ranked results of an optimization, best combination first
+#[pyclass]
+pub struct PyOptimizationReport {
+    #[pyo3(get)]
+    pub enemy_decks: Vec<String>,
+    #[pyo3(get)]
+    pub combinations: Vec<Py<PyCombinationResult>>,
+    report: OptimizationReport,
+}
+
+#[pymethods]
+impl PyOptimizationReport {
+    /// The report as a JSON string (same format as `deckgym optimize --json`)
+    fn to_json(&self) -> PyResult<String> {
+        serde_json::to_string(&self.report.to_json())
+            .map_err(|e| PyValueError::new_err(e.to_string()))
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "OptimizationReport(combinations={}, enemy_decks={})",
+            self.combinations.len(),
+            self.enemy_decks.len()
+        )
+    }
+}
+
+impl PyOptimizationReport {
+    fn new(py: Python, report: OptimizationReport) -> PyResult<Self> {
+        let combinations = report
+            .combinations
+            .iter()
+            .map(|result| {
+                let cards: Vec<_> = result
+                    .cards
+                    .iter()
+                    .map(|&id| get_card_by_enum(id))
+                    .collect();
+                Py::new(
+                    py,
+                    PyCombinationResult {
+                        cards: cards.iter().map(|card| card.get_id()).collect(),
+                        card_names: cards.iter().map(|card| card.get_name()).collect(),
+                        games: result.tally.games(),
+                        wins: result.tally.wins,
+                        losses: result.tally.losses,
+                        ties: result.tally.ties,
+                        win_rate: result.win_rate(),
+                        confidence_interval: result.confidence_interval(),
+                        going_first_win_rate: result.going_first.win_rate(),
+                        going_second_win_rate: result.going_second.win_rate(),
+                        per_enemy_win_rates: report
+                            .enemy_names
+                            .iter()
+                            .cloned()
+                            .zip(result.per_enemy.iter().map(|tally| tally.win_rate()))
+                            .collect(),
+                    },
+                )
+            })
+            .collect::<PyResult<_>>()?;
+        Ok(PyOptimizationReport {
+            enemy_decks: report.enemy_names.clone(),
+            combinations,
+            report,
+        })
+    }
+}
+
+/// Completes an incomplete deck with every valid combination of the candidate cards and
+/// ranks the combinations by win rate against the enemy decks. With `top_k`, combinations
+/// are raced with successive halving and only the best `top_k` are reported.
+#[pyfunction]
+#[pyo3(signature = (incomplete_deck_path, candidate_cards, enemy_deck_paths, num_games=100, players=None, seed=None, top_k=None, parallel=false))]
+#[allow(clippy::too_many_arguments)]
+pub fn py_optimize(
+    py: Python,
+    incomplete_deck_path: &str,
+    candidate_cards: Vec<String>,
+    enemy_deck_paths: Vec<String>,
+    num_games: u32,
+    players: Option<Vec<String>>,
+    seed: Option<u64>,
+    top_k: Option<usize>,
+    parallel: bool,
+) -> PyResult<PyOptimizationReport> {
+    let incomplete_deck = Deck::from_file(incomplete_deck_path)
+        .map_err(|e| PyIOError::new_err(format!("Failed to load incomplete deck: {}", e)))?;
+    let enemy_deck_configs = enemy_deck_paths
+        .iter()
+        .map(|path| {
+            let deck = load_valid_deck(path, path)?;
+            Ok(EnemyDeckConfig {
+                name: deck_name_from_path(path),
+                deck,
+                num_games,
+            })
+        })
+        .collect::<PyResult<Vec<_>>>()?;
+    let players = players
+        .map(|codes| {
+            codes
+                .iter()
+                .map(|code| parse_player_code(code))
+                .collect::<Result<Vec<_>, _>>()
+        })
+        .transpose()
+        .map_err(PyValueError::new_err)?;
+    let opt_config = OptimizationConfig {
+        enemy_deck_configs,
+        players,
+        seed,
+        inventory: None,
+        format: None,
+        checkpoint: None,
+    };
+    let parallel_config = ParallelConfig {
+        enabled: parallel,
+        num_threads: None,
+    };
+
+    let report = py.allow_threads(|| match top_k {
+        Some(top_k) => optimize_successive_halving(
+            &incomplete_deck,
+            &candidate_cards,
+            &opt_config,
+            top_k,
+            &parallel_config,
+            None::<fn()>,
+        ),
+        None => optimize_with_configs(
+            &incomplete_deck,
+            &candidate_cards,
+            opt_config,
+            parallel_config,
+            None::<OptimizationCallbacks<fn(usize, usize, &[CardId], f32), fn()>>,
+        ),
+    });
+    PyOptimizationReport::new(py, report)
+}
+
+/// Exact opening-hand and draw probabilities of a deck
+#[pyclass]
+pub struct PyDeckOdds {
+    /// Chance of opening with exactly `i` basic Pokémon, by `i`
+    #[pyo3(get)]
+    pub basics_in_opening_hand: Vec<f64>,
+    /// Chance that a plain shuffle deals no basic (prevented by the basic guarantee)
+    #[pyo3(get)]
+    pub mulligan_rate: f64,
+    #[pyo3(get)]
+    pub targets_in_opening_hand: f64,
+    /// Chance of having held every target card after each game turn, starting at turn 1
+    #[pyo3(get)]
+    pub going_first: Vec<f64>,
+    #[pyo3(get)]
+    pub going_second: Vec<f64>,
+}
+
+#[pymethods]
+impl PyDeckOdds {
+    fn __repr__(&self) -> String {
+        format!(
+            "DeckOdds(targets_in_opening_hand={:.1}%, turns={})",
+            self.targets_in_opening_hand * 100.0,
+            self.going_first.len()
+        )
+    }
+}
+
+/// Computes exact probabilities of the opening hand's basics and of holding `cards`
+/// (repeat an ID to require several copies) by each turn, without simulating games.
+#[pyfunction]
+#[pyo3(signature = (deck_path, cards=Vec::new(), turns=4, draw_trainers=true))]
+pub fn py_deck_odds(
+    deck_path: &str,
+    cards: Vec<String>,
+    turns: u32,
+    draw_trainers: bool,
+) -> PyResult<PyDeckOdds> {
+    let deck = Deck::from_file(deck_path)
+        .map_err(|e| PyIOError::new_err(format!("Failed to load deck: {}", e)))?;
+    let targets = cards
+        .iter()
+        .map(|id| try_parse_card_id_string(id))
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(PyValueError::new_err)?;
+    let odds = deck_odds(
+        &deck,
+        &targets,
+        &OddsOptions {
+            turns,
+            draw_trainers,
+        },
+    )
+    .map_err(PyValueError::new_err)?;
+    Ok(PyDeckOdds {
+        basics_in_opening_hand: odds.basics_in_opening_hand,
+        mulligan_rate: odds.mulligan_rate,
+        targets_in_opening_hand: odds.targets_in_opening_hand,
+        going_first: odds.by_turn.iter().map(|turn| turn.going_first).collect(),
+        going_second: odds.by_turn.iter().map(|turn| turn.going_second).collect(),
     })
 }
 
@@ -1221,22 +1526,88 @@ pub fn get_player_types() -> HashMap<String, String> {
 /// Python module definition
 #[pyfunction]
 pub fn get_card(id: String) -> PyResult<PyCard> {
-    let card_id =
-        CardId::from_card_id(&id).ok_or_else(|| PyValueError::new_err("Invalid Card ID"))?;
-    Ok(crate::database::get_card_by_enum(card_id).into())
+    let card = get_card_by_id(&id).ok_or_else(|| PyValueError::new_err("Invalid Card ID"))?;
+    Ok(card.into())
 }
 
 #[pyfunction]
 pub fn get_all_cards() -> Vec<PyCard> {
     use strum::IntoEnumIterator;
     CardId::iter()
-        .map(|id| crate::database::get_card_by_enum(id).into())
+        .map(get_card_by_enum)
+        .chain(runtime_cards())
+        .map(|card| card.into())
         .collect()
 }
 
+/// Loads the cards this build doesn't have from a file in the database.json format, so decks
+/// can use them. Returns how many cards were added.
+#[pyfunction]
+pub fn load_card_database(path: &str) -> PyResult<usize> {
+    crate::card_database::load_card_database(path).map_err(PyValueError::new_err)
+}
+
+/// Loads hypothetical cards from a JSON file composed of existing mechanics, so decks can use
+/// them. Returns the ids they were given (CUSTOM 001, ...).
+#[pyfunction]
+pub fn load_custom_cards(path: &str) -> PyResult<Vec<String>> {
+    crate::custom_cards::load_custom_cards(path).map_err(PyValueError::new_err)
+}
+
+/// Cards matching every given filter, see `CardQuery`. Energy types are names like "Grass".
+#[pyfunction]
+#[pyo3(signature = (name=None, stage=None, energy_types=Vec::new(), min_hp=None, max_hp=None, weaknesses=Vec::new(), max_retreat_cost=None, has_ability=None, attack=None, max_attack_cost=None, min_attack_damage=None, effect=None, booster_pack=None, rarity=None, implemented=None))]
+#[allow(clippy::too_many_arguments)]
+pub fn search_cards(
+    name: Option<String>,
+    stage: Option<u8>,
+    energy_types: Vec<String>,
+    min_hp: Option<u32>,
+    max_hp: Option<u32>,
+    weaknesses: Vec<String>,
+    max_retreat_cost: Option<usize>,
+    has_ability: Option<bool>,
+    attack: Option<String>,
+    max_attack_cost: Option<usize>,
+    min_attack_damage: Option<u32>,
+    effect: Option<String>,
+    booster_pack: Option<String>,
+    rarity: Option<String>,
+    implemented: Option<bool>,
+) -> PyResult<Vec<PyCard>> {
+    let parse_energy_types = |names: Vec<String>| {
+        names
+            .iter()
+            .map(|name| parse_energy_type(name))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(PyValueError::new_err)
+    };
+    let query = CardQuery {
+        name,
+        stage,
+        energy_types: parse_energy_types(energy_types)?,
+        min_hp,
+        max_hp,
+        weaknesses: parse_energy_types(weaknesses)?,
+        max_retreat_cost,
+        has_ability,
+        attack,
+        max_attack_cost,
+        min_attack_damage,
+        effect,
+        booster_pack,
+        rarity,
+        implemented,
+    };
+    Ok(search(&query).into_iter().map(|card| card.into()).collect())
+}
+
 pub fn deckgym(_py: Python, m: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add_function(wrap_pyfunction!(get_card, m)?)?;
     m.add_function(wrap_pyfunction!(get_all_cards, m)?)?;
+    m.add_function(wrap_pyfunction!(load_card_database, m)?)?;
+    m.add_function(wrap_pyfunction!(load_custom_cards, m)?)?;
+    m.add_function(wrap_pyfunction!(search_cards, m)?)?;
     m.add_class::<PyEnergyType>()?;
     m.add_class::<PyAttack>()?;
     m.add_class::<PyAbility>()?;
@@ -1249,6 +1620,11 @@ pub fn deckgym(_py: Python, m: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add_class::<PyGameOutcome>()?;
     m.add_class::<PySimulationResults>()?;
     m.add_function(wrap_pyfunction!(py_simulate, m)?)?;
+    m.add_class::<PyCombinationResult>()?;
+    m.add_class::<PyOptimizationReport>()?;
+    m.add_function(wrap_pyfunction!(py_optimize, m)?)?;
+    m.add_class::<PyDeckOdds>()?;
+    m.add_function(wrap_pyfunction!(py_deck_odds, m)?)?;
     m.add_function(wrap_pyfunction!(get_player_types, m)?)?;
     m.add_class::<PyBatchedSimulator>()?;
     Ok(())
@@ -1278,10 +1654,8 @@ impl PyBatchedSimulator {
         damage_reward: f32,
     ) -> PyResult<Self> {
         let mut deck_cache = HashMap::new();
-        let deck_a = Deck::from_file(&deck_a_path)
-            .map_err(|e| PyIOError::new_err(format!("Failed to load deck A: {}", e)))?;
-        let deck_b = Deck::from_file(&deck_b_path)
-            .map_err(|e| PyIOError::new_err(format!("Failed to load deck B: {}", e)))?;
+        let deck_a = load_valid_deck(&deck_a_path, "deck A")?;
+        let deck_b = load_valid_deck(&deck_b_path, "deck B")?;
 
         deck_cache.insert(deck_a_path, deck_a);
         deck_cache.insert(deck_b_path, deck_b);
@@ -1340,8 +1714,7 @@ impl PyBatchedSimulator {
             let deck_1 = if let Some(d) = self.deck_cache.get(deck_1_path) {
                 d.clone()
             } else {
-                let d = Deck::from_file(deck_1_path)
-                    .map_err(|e| PyIOError::new_err(format!("Failed to load deck: {}", e)))?;
+                let d = load_valid_deck(deck_1_path, "deck")?;
                 self.deck_cache.insert(deck_1_path.to_string(), d.clone());
                 d
             };
@@ -1349,8 +1722,7 @@ impl PyBatchedSimulator {
             let deck_2 = if let Some(d) = self.deck_cache.get(deck_2_path) {
                 d.clone()
             } else {
-                let d = Deck::from_file(deck_2_path)
-                    .map_err(|e| PyIOError::new_err(format!("Failed to load deck: {}", e)))?;
+                let d = load_valid_deck(deck_2_path, "deck")?;
                 self.deck_cache.insert(deck_2_path.to_string(), d.clone());
                 d
             };
@@ -0,0 +1,336 @@
+use log::warn;
+use serde::Serialize;
+
+use crate::{
+    game_results::GameRecord, matrix::MatchupMatrix, state::GameOutcome, statistics::Z_95,
+};
+
+/// Rating every entity starts from and the ratings are centered on
+pub const BASE_ELO: f64 = 1500.0;
+
+/// Elo points per unit of Bradley-Terry log-strength (400 points = 10x the odds)
+const ELO_PER_LOG_STRENGTH: f64 = 400.0 / std::f64::consts::LN_10;
+
+/// Standard deviation (in log-strength) of the zero-centered Gaussian prior on every
+/// strength, about 350 Elo. Keeps undefeated entities finite without pulling ratings much.
+const PRIOR_STD: f64 = 2.0;
+
+const MAX_ITERATIONS: usize = 100;
+const TOLERANCE: f64 = 1e-9;
+
+/// Strength of one deck or player strategy on the Elo scale
+#[derive(Debug, Clone, Serialize)]
+pub struct Rating {
+    pub name: String,
+    pub elo: f64,
+    /// Standard error of `elo`, in Elo points
+    pub std_error: f64,
+    pub games: u32,
+}
+
+impl Rating {
+    /// 95% confidence interval of the rating
+    pub fn confidence_interval(&self) -> (f64, f64) {
+        (
+            self.elo - Z_95 * self.std_error,
+            self.elo + Z_95 * self.std_error,
+        )
+    }
+}
+
+/// Expected score of a player rated `elo_a` against one rated `elo_b`
+pub fn expected_score(elo_a: f64, elo_b: f64) -> f64 {
+    1.0 / (1.0 + 10f64.powf((elo_b - elo_a) / 400.0))
+}
+
+/// Fits Bradley-Terry strengths to the pairwise results of a matrix and reports them on the
+/// Elo scale, centered on `BASE_ELO`. Ties count as half a win for each side.
+///
+/// The fit is a maximum a posteriori estimate under a weak Gaussian prior (solved with
+/// Newton's method), so it's defined even for entities that never lost or never won.
+/// Standard errors come from the inverse of the observed information matrix and describe
+/// each rating relative to the pool average.
+/// Ratings keep the order of `matrix.names`; see `ranked` to sort them.
+pub fn fit_ratings(matrix: &MatchupMatrix) -> Vec<Rating> {
+    let n = matrix.names.len();
+    let prior_precision = 1.0 / (PRIOR_STD * PRIOR_STD);
+    let mut strengths = vec![0.0; n];
+    let mut information = vec![vec![0.0; n]; n];
+
+    for _ in 0..MAX_ITERATIONS {
+        let mut gradient = vec![0.0; n];
+        information = vec![vec![0.0; n]; n];
+        for i in 0..n {
+            gradient[i] -= strengths[i] * prior_precision;
+            information[i][i] += prior_precision;
+            for j in 0..n {
+                let tally = matrix.tallies[i][j];
+                let games = tally.games() as f64;
+                if i == j || games == 0.0 {
+                    continue;
+                }
+                let score = tally.wins as f64 + 0.5 * tally.ties as f64;
+                let p = logistic(strengths[i] - strengths[j]);
+                gradient[i] += score - games * p;
+                information[i][i] += games * p * (1.0 - p);
+                information[i][j] -= games * p * (1.0 - p);
+            }
+        }
+
+        let step = solve(&information, &gradient);
+        for (strength, delta) in strengths.iter_mut().zip(&step) {
+            *strength += delta;
+        }
+        if step.iter().all(|delta| delta.abs() < TOLERANCE) {
+            break;
+        }
+    }
+
+    // Only differences between strengths are identified by the games, so report the variance
+    // of each strength relative to the mean, not the prior-dominated absolute variance
+    let covariance = invert(&information);
+    let size = n.max(1) as f64;
+    let mean = strengths.iter().sum::<f64>() / size;
+    let row_means: Vec<f64> = covariance
+        .iter()
+        .map(|row| row.iter().sum::<f64>() / size)
+        .collect();
+    let grand_mean = row_means.iter().sum::<f64>() / size;
+    (0..n)
+        .map(|i| Rating {
+            name: matrix.names[i].clone(),
+            elo: BASE_ELO + (strengths[i] - mean) * ELO_PER_LOG_STRENGTH,
+            std_error: (covariance[i][i] - 2.0 * row_means[i] + grand_mean)
+                .max(0.0)
+                .sqrt()
+                * ELO_PER_LOG_STRENGTH,
+            games: matrix.total_vs_field(i).games(),
+        })
+        .collect()
+}
+
+/// Ratings sorted from strongest to weakest
+pub fn ranked(mut ratings: Vec<Rating>) -> Vec<Rating> {
+    ratings.sort_by(|a, b| b.elo.total_cmp(&a.elo));
+    ratings
+}
+
+/// What to rate when building a matrix out of exported game records
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RatingSubject {
+    Decks,
+    Players,
+}
+
+/// Builds a matchup matrix out of exported game records (see `GameResultsCollector`),
+/// keyed by deck name or player strategy. Games between identical entities are skipped.
+pub fn matrix_from_records(records: &[GameRecord], subject: RatingSubject) -> MatchupMatrix {
+    let keys = |record: &GameRecord| match subject {
+        RatingSubject::Decks => (record.deck_a.clone(), record.deck_b.clone()),
+        RatingSubject::Players => (record.player_a.clone(), record.player_b.clone()),
+    };
+    let mut names: Vec<String> = records
+        .iter()
+        .flat_map(|record| {
+            let (a, b) = keys(record);
+            [a, b]
+        })
+        .collect();
+    names.sort();
+    names.dedup();
+
+    let mut matrix = MatchupMatrix::new(names);
+    for record in records {
+        let (a, b) = keys(record);
+        if a == b {
+            continue;
+        }
+        let index = |name: &str| matrix.names.iter().position(|n| n == name).unwrap();
+        let (seat_0, seat_1) = (index(&a), index(&b));
+        let outcome = match record.winner {
+            Some(winner) => Some(GameOutcome::Win(winner)),
+            None => Some(GameOutcome::Tie),
+        };
+        matrix.record(seat_0, seat_1, outcome);
+    }
+    matrix
+}
+
+/// Prints ratings from strongest to weakest with 95% confidence intervals
+pub fn print_ratings(ratings: &[Rating]) {
+    let ratings = ranked(ratings.to_vec());
+    let name_width = ratings
+        .iter()
+        .map(|rating| rating.name.chars().count())
+        .max()
+        .unwrap_or(0)
+        .max("Name".len());
+    warn!(
+        "{:>4}  {:<name_width$}  {:>6}  {:>15}  {:>7}",
+        "Rank", "Name", "Elo", "95% CI", "Games"
+    );
+    for (rank, rating) in ratings.iter().enumerate() {
+        let (low, high) = rating.confidence_interval();
+        warn!(
+            "{:>4}  {:<name_width$}  {:>6.0}  {:>15}  {:>7}",
+            rank + 1,
+            rating.name,
+            rating.elo,
+            format!("{low:.0}-{high:.0}"),
+            rating.games
+        );
+    }
+}
+
+fn logistic(x: f64) -> f64 {
+    1.0 / (1.0 + (-x).exp())
+}
+
+/// Solves `matrix * x = rhs` by Gaussian elimination with partial pivoting.
+/// The information matrix is positive definite thanks to the prior, so pivots never vanish.
+fn solve(matrix: &[Vec<f64>], rhs: &[f64]) -> Vec<f64> {
+    let n = rhs.len();
+    let mut augmented: Vec<Vec<f64>> = matrix
+        .iter()
+        .zip(rhs)
+        .map(|(row, &value)| {
+            let mut row = row.clone();
+            row.push(value);
+            row
+        })
+        .collect();
+
+    for column in 0..n {
+        let pivot = (column..n)
+            .max_by(|&a, &b| {
+                augmented[a][column]
+                    .abs()
+                    .total_cmp(&augmented[b][column].abs())
+            })
+            .unwrap();
+        augmented.swap(column, pivot);
+        let pivot_row = augmented[column].clone();
+        for (row, values) in augmented.iter_mut().enumerate() {
+            if row != column {
+                let factor = values[column] / pivot_row[column];
+                for (value, pivot_value) in values.iter_mut().zip(&pivot_row).skip(column) {
+                    *value -= factor * pivot_value;
+                }
+            }
+        }
+    }
+    (0..n)
+        .map(|row| augmented[row][n] / augmented[row][row])
+        .collect()
+}
+
+fn invert(matrix: &[Vec<f64>]) -> Vec<Vec<f64>> {
+    let n = matrix.len();
+    let columns: Vec<Vec<f64>> = (0..n)
+        .map(|column| {
+            let unit: Vec<f64> = (0..n).map(|row| (row == column) as u8 as f64).collect();
+            solve(matrix, &unit)
+        })
+        .collect();
+    // `columns[c][r]` is entry (r, c) of the inverse; it's symmetric, so no transpose needed
+    columns
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{state::GameEndReason, statistics::MatchTally};
+
+    fn matrix_with(names: &[&str], results: &[(usize, usize, MatchTally)]) -> MatchupMatrix {
+        let mut matrix = MatchupMatrix::new(names.iter().map(|name| name.to_string()).collect());
+        for &(i, j, tally) in results {
+            matrix.tallies[i][j] = tally;
+            matrix.tallies[j][i] = tally.flipped();
+        }
+        matrix
+    }
+
+    #[test]
+    fn test_ratings_match_win_rates() {
+        // 75% win rate is 3:1 odds, i.e. ~191 Elo
+        let matrix = matrix_with(
+            &["strong", "weak"],
+            &[(
+                0,
+                1,
+                MatchTally {
+                    wins: 750,
+                    losses: 250,
+                    ties: 0,
+                },
+            )],
+        );
+        let ratings = fit_ratings(&matrix);
+        let difference = ratings[0].elo - ratings[1].elo;
+        assert!((difference - 190.8).abs() < 5.0, "difference {difference}");
+        assert!((ratings[0].elo + ratings[1].elo - 2.0 * BASE_ELO).abs() < 1e-6);
+        assert!((expected_score(ratings[0].elo, ratings[1].elo) - 0.75).abs() < 0.01);
+        assert!(ratings[0].std_error > 0.0 && ratings[0].std_error < 20.0);
+        assert_eq!(ratings[0].games, 1000);
+    }
+
+    #[test]
+    fn test_ratings_are_transitive_and_finite() {
+        let beats = |wins, losses| MatchTally {
+            wins,
+            losses,
+            ties: 0,
+        };
+        let matrix = matrix_with(
+            &["c", "a", "b"],
+            &[
+                (1, 2, beats(60, 40)),
+                (2, 0, beats(60, 40)),
+                (1, 0, beats(20, 0)),
+            ],
+        );
+        let ratings = ranked(fit_ratings(&matrix));
+        let names: Vec<&str> = ratings.iter().map(|rating| rating.name.as_str()).collect();
+        assert_eq!(names, vec!["a", "b", "c"]);
+        assert!(ratings.iter().all(|rating| rating.elo.is_finite()));
+    }
+
+    #[test]
+    fn test_matrix_from_records() {
+        let record = |player_a: &str, player_b: &str, winner| GameRecord {
+            game_id: String::new(),
+            seed: 0,
+            winner,
+            points_a: 0,
+            points_b: 0,
+            turns: 0,
+            plies: 0,
+            first_player: None,
+            end_reason: Some(GameEndReason::PointsReached),
+            deck_a: "deck".to_string(),
+            deck_b: "deck".to_string(),
+            player_a: player_a.to_string(),
+            player_b: player_b.to_string(),
+            duration_ms: 0.0,
+        };
+        let records = vec![
+            record("e2", "r", Some(0)),
+            record("r", "e2", Some(1)),
+            record("r", "e2", None),
+            record("r", "r", Some(0)),
+        ];
+
+        let matrix = matrix_from_records(&records, RatingSubject::Players);
+        assert_eq!(matrix.names, vec!["e2", "r"]);
+        assert_eq!(
+            matrix.tallies[0][1],
+            MatchTally {
+                wins: 2,
+                losses: 0,
+                ties: 1
+            }
+        );
+        let decks = matrix_from_records(&records, RatingSubject::Decks);
+        assert_eq!(decks.total_vs_field(0).games(), 0);
+    }
+}
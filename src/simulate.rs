@@ -2,17 +2,21 @@ use env_logger::{Builder, Env};
 use indicatif::{ProgressBar, ProgressStyle};
 use log::warn;
 use num_format::{Locale, ToFormattedString};
-use rayon::prelude::*;
-use std::io::Write;
+use serde::{Deserialize, Serialize};
+use std::{io::Write, ops::Range};
 use uuid::Uuid;
 
 use crate::{
+    checkpoint::{deck_card_ids, play_in_batches, Checkpoint, CheckpointConfig},
+    errata::Errata,
+    game_results::GameResultsCollector,
+    optimize::{ParallelConfig, SimulationConfig},
     players::{create_players, fill_code_array, PlayerCode},
     simulation_event_handler::{
-        CompositeSimulationEventHandler, SimulationEventHandler, StatsCollector,
+        CompositeSimulationEventHandler, GameSetup, SimulationEventHandler, StatsCollector,
     },
     state::GameOutcome,
-    Deck, Game,
+    Deck, Game, State,
 };
 
 /// Type alias for player factory function
@@ -55,15 +59,45 @@ where
 pub struct Simulation {
     deck_a: Deck,
     deck_b: Deck,
+    deck_names: [String; 2],
     player_codes: Vec<PlayerCode>,
     num_simulations: u32,
     seed: Option<u64>,
     handler_factories: Vec<fn() -> Box<dyn SimulationEventHandler>>,
+    /// Type names of the registered handlers, stored in result files
+    handler_names: Vec<&'static str>,
+    /// Which part of the games to play, as `(shard, number of shards)`
+    shard: (usize, usize),
     parallel: bool,
     num_threads: Option<usize>,
     event_handler: Option<CompositeSimulationEventHandler>,
     callbacks: Option<SimulationCallbacks<Box<dyn Fn() + Sync>>>,
     player_factory: Option<PlayerFactory>,
+    checkpoint: Option<CheckpointConfig>,
+    /// `Errata::describe` of the errata applied to both decks
+    errata: Vec<String>,
+}
+
+/// Games of a simulation played so far, as saved in its checkpoints
+#[derive(Serialize, Deserialize)]
+struct SimulationProgress {
+    /// Index of the next game to play
+    games_done: usize,
+    /// `save_state` of the merged event handler
+    handlers: serde_json::Value,
+}
+
+/// Event handler states of a (possibly sharded) simulation, written by
+/// `Simulation::write_results` and combined by `merge_results`
+#[derive(Serialize, Deserialize)]
+struct SimulationResultsFile {
+    /// Decks, players, total number of games and seed of the whole run
+    run: serde_json::Value,
+    shard: usize,
+    num_shards: usize,
+    games: usize,
+    handler_names: Vec<String>,
+    handlers: serde_json::Value,
 }
 
 impl Simulation {
@@ -78,7 +112,13 @@ impl Simulation {
     ) -> Result<Self, Box<dyn std::error::Error>> {
         let deck_a = Deck::from_file(deck_a_path)?;
         let deck_b = Deck::from_file(deck_b_path)?;
-        Simulation::new_with_decks(
+        deck_a
+            .check_valid()
+            .map_err(|err| format!("{deck_a_path} is not valid: {err}"))?;
+        deck_b
+            .check_valid()
+            .map_err(|err| format!("{deck_b_path} is not valid: {err}"))?;
+        Ok(Simulation::new_with_decks(
             deck_a,
             deck_b,
             player_codes,
@@ -86,7 +126,11 @@ impl Simulation {
             seed,
             parallel,
             num_threads,
-        )
+        )?
+        .with_deck_names([
+            deck_name_from_path(deck_a_path),
+            deck_name_from_path(deck_b_path),
+        ]))
     }
 
     pub fn new_with_decks(
@@ -101,15 +145,20 @@ impl Simulation {
         Ok(Simulation {
             deck_a,
             deck_b,
+            deck_names: default_deck_names(),
             player_codes,
             num_simulations,
             seed,
             handler_factories: vec![],
+            handler_names: vec![],
+            shard: (0, 1),
             parallel,
             num_threads,
             event_handler: None,
             callbacks: None,
             player_factory: None,
+            checkpoint: None,
+            errata: Vec::new(),
         })
     }
 
@@ -128,20 +177,59 @@ impl Simulation {
         Ok(Simulation {
             deck_a,
             deck_b,
+            deck_names: default_deck_names(),
             player_codes: vec![], // Not used when player_factory is provided
             num_simulations,
             seed,
             handler_factories: vec![],
+            handler_names: vec![],
+            shard: (0, 1),
             parallel,
             num_threads,
             event_handler: None,
             callbacks: None,
             player_factory: Some(Box::new(player_factory)),
+            checkpoint: None,
+            errata: Vec::new(),
         })
     }
 
+    /// Names used to identify the decks in `GameSetup` (and thus in exported results)
+    pub fn with_deck_names(mut self, deck_names: [String; 2]) -> Self {
+        self.deck_names = deck_names;
+        self
+    }
+
+    /// Plays both decks with the errata's card changes
+    pub fn with_errata(mut self, errata: &Errata) -> Self {
+        errata.apply(&mut self.deck_a);
+        errata.apply(&mut self.deck_b);
+        self.errata.extend(errata.describe());
+        self
+    }
+
     pub fn register<T: SimulationEventHandler + Default + 'static>(mut self) -> Self {
         self.handler_factories.push(|| Box::new(T::default()));
+        self.handler_names.push(short_type_name::<T>());
+        self
+    }
+
+    /// Only plays the `shard`-th of `num_shards` equal parts of the games. Games keep the
+    /// index (and thus the seed) they have in the whole run, so shards never overlap and
+    /// their `write_results` files merge into the results of the whole run.
+    pub fn with_shard(mut self, shard: usize, num_shards: usize) -> Self {
+        assert!(
+            shard < num_shards,
+            "Shard {shard} is not below {num_shards}"
+        );
+        self.shard = (shard, num_shards);
+        self
+    }
+
+    /// Saves the merged event handlers to a checkpoint while running, and resumes from it.
+    /// Every registered handler must support `save_state`.
+    pub fn with_checkpoint(mut self, checkpoint: CheckpointConfig) -> Self {
+        self.checkpoint = Some(checkpoint);
         self
     }
 
@@ -153,7 +241,17 @@ impl Simulation {
         self
     }
 
+    /// Plays the games and merges their event handlers.
+    ///
+    /// Panics if the checkpoint can't be opened; see `try_run`.
     pub fn run(&mut self) -> Vec<Option<GameOutcome>> {
+        self.try_run()
+            .expect("Failed to open simulation checkpoint")
+    }
+
+    /// Like `run`, but fails if the checkpoint can't be opened or resumed.
+    /// When resuming, only the outcomes of the games played by this call are returned.
+    pub fn try_run(&mut self) -> Result<Vec<Option<GameOutcome>>, String> {
         // Configure rayon thread pool if specified
         if let Some(num_threads) = self.num_threads {
             rayon::ThreadPoolBuilder::new()
@@ -176,8 +274,34 @@ impl Simulation {
             .as_ref()
             .and_then(|cbs| cbs.on_game_complete.as_ref());
 
+        let player_names = self.player_names();
+        let games = self.game_range();
+
+        let mut games_done = games.start;
+        let mut base_seed = self.seed;
+        let mut checkpoint = match &self.checkpoint {
+            Some(config) => {
+                if main_event_handler.save_state().is_none() {
+                    return Err(
+                        "Some registered event handlers don't support checkpoints".to_string()
+                    );
+                }
+                let mut run = self.run_parameters();
+                run["shard"] = serde_json::json!(self.shard);
+                let (checkpoint, saved) =
+                    Checkpoint::open::<SimulationProgress>(config, "simulate", run, self.seed)?;
+                if let Some(saved) = saved {
+                    main_event_handler.load_state(saved.handlers)?;
+                    games_done = saved.games_done;
+                }
+                base_seed = Some(checkpoint.seed());
+                Some(checkpoint)
+            }
+            None => None,
+        };
+
         // Closure to run a single simulation
-        let run_single_simulation = |_| {
+        let run_single_simulation = |game_index: usize| {
             // Make a thread-local event handler for this simulation
             let mut event_handler = CompositeSimulationEventHandler::new(
                 self.handler_factories
@@ -195,9 +319,17 @@ impl Simulation {
                     self.player_codes.clone(),
                 )
             };
-            let seed = self.seed.unwrap_or(rand::random::<u64>());
+            let seed = game_seed(base_seed, game_index as u64);
             let game_id = Uuid::new_v4();
             event_handler.on_game_start(game_id);
+            event_handler.on_game_setup(
+                game_id,
+                &GameSetup {
+                    seed,
+                    deck_names: self.deck_names.clone(),
+                    player_names: player_names.clone(),
+                },
+            );
 
             // Give the event_handler a mutable reference to the Game
             let mut game =
@@ -215,32 +347,98 @@ impl Simulation {
             (outcome, event_handler)
         };
 
-        // Run simulations either in parallel or sequentially
-        let results: Vec<(Option<GameOutcome>, CompositeSimulationEventHandler)> = if self.parallel
-        {
-            (0..self.num_simulations)
-                .into_par_iter()
-                .map(run_single_simulation)
-                .collect()
-        } else {
-            (0..self.num_simulations)
-                .map(run_single_simulation)
-                .collect()
-        };
-
-        // Split outcomes and event handlers
-        let (outcomes, thread_event_handlers): (Vec<_>, Vec<_>) = results.into_iter().unzip();
-
-        // Merge all thread-local event handlers into the main one
-        for handler in thread_event_handlers.iter() {
-            main_event_handler.merge(handler);
+        // Run simulations either in parallel or sequentially, merging the thread-local
+        // event handlers into the main one after every batch
+        let mut outcomes = Vec::new();
+        play_in_batches(
+            games_done,
+            games.end,
+            checkpoint.is_some(),
+            self.parallel,
+            run_single_simulation,
+            |games_done, results| {
+                for (outcome, handler) in results {
+                    main_event_handler.merge(&handler);
+                    outcomes.push(outcome);
+                }
+                if let Some(checkpoint) = checkpoint.as_mut() {
+                    checkpoint.save_if_due(&SimulationProgress {
+                        games_done,
+                        handlers: main_event_handler.save_state().unwrap_or_default(),
+                    });
+                }
+            },
+        );
+        if let Some(checkpoint) = checkpoint.as_mut() {
+            checkpoint.save(&SimulationProgress {
+                games_done: games.end,
+                handlers: main_event_handler.save_state().unwrap_or_default(),
+            });
         }
         main_event_handler.on_simulation_end();
 
         // Store the merged event handler for later retrieval
         self.event_handler = Some(main_event_handler);
 
-        outcomes
+        Ok(outcomes)
+    }
+
+    /// Writes the state of the merged event handlers after `run`, so that `merge_results`
+    /// can combine it with the other shards of the run. Every registered handler must
+    /// support `save_state`.
+    pub fn write_results(&self, path: &str) -> Result<(), String> {
+        let handlers = self
+            .event_handler
+            .as_ref()
+            .ok_or("The simulation hasn't run yet")?
+            .save_state()
+            .ok_or("Some registered event handlers can't be written to a result file")?;
+        let (shard, num_shards) = self.shard;
+        let file = SimulationResultsFile {
+            run: self.run_parameters(),
+            shard,
+            num_shards,
+            games: self.game_range().len(),
+            handler_names: self
+                .handler_names
+                .iter()
+                .map(|name| name.to_string())
+                .collect(),
+            handlers,
+        };
+        let json = serde_json::to_string(&file)
+            .map_err(|err| format!("Failed to serialize simulation results: {err}"))?;
+        std::fs::write(path, json).map_err(|err| format!("Failed to write {path}: {err}"))
+    }
+
+    /// Indices of the games this simulation plays (all of them unless sharded)
+    fn game_range(&self) -> Range<usize> {
+        let (shard, num_shards) = self.shard;
+        let total = self.num_simulations as usize;
+        total * shard / num_shards..total * (shard + 1) / num_shards
+    }
+
+    fn player_names(&self) -> [String; 2] {
+        if self.player_factory.is_some() {
+            ["custom".to_string(), "custom".to_string()]
+        } else {
+            let codes = fill_code_array(Some(self.player_codes.clone()));
+            [codes[0].to_string(), codes[1].to_string()]
+        }
+    }
+
+    /// Parameters that identify the whole run in checkpoints and result files
+    fn run_parameters(&self) -> serde_json::Value {
+        let mut run = serde_json::json!({
+            "decks": [deck_card_ids(&self.deck_a), deck_card_ids(&self.deck_b)],
+            "players": self.player_names(),
+            "num_games": self.num_simulations,
+            "seed": self.seed,
+        });
+        if !self.errata.is_empty() {
+            run["errata"] = serde_json::json!(self.errata);
+        }
+        run
     }
 
     /// Get a reference to a specific event handler by type after simulation has run
@@ -249,6 +447,99 @@ impl Simulation {
     }
 }
 
+/// Combines result files written by `Simulation::write_results` (typically one per shard)
+/// into one event handler, as if the games had all been played in a single run.
+///
+/// Files must come from the same run and cover distinct shards; missing shards only warn.
+pub fn merge_results(paths: &[String]) -> Result<CompositeSimulationEventHandler, String> {
+    let mut files = Vec::new();
+    for path in paths {
+        let contents =
+            std::fs::read_to_string(path).map_err(|err| format!("Failed to read {path}: {err}"))?;
+        let file: SimulationResultsFile = serde_json::from_str(&contents)
+            .map_err(|err| format!("Failed to parse {path}: {err}"))?;
+        files.push((path, file));
+    }
+    let Some((_, first)) = files.first() else {
+        return Err("No result files to merge".to_string());
+    };
+    let factories = first
+        .handler_names
+        .iter()
+        .map(|name| builtin_handler_factory(name).ok_or(format!("Unknown event handler {name}")))
+        .collect::<Result<Vec<_>, _>>()?;
+    let new_handler =
+        || CompositeSimulationEventHandler::new(factories.iter().map(|f| f()).collect());
+
+    let mut merged = new_handler();
+    let mut shards = vec![false; first.num_shards];
+    let mut games = 0;
+    for (path, file) in &files {
+        if file.run != first.run || file.num_shards != first.num_shards {
+            return Err(format!("{path} belongs to a different run"));
+        }
+        if file.handler_names != first.handler_names {
+            return Err(format!("{path} has different event handlers"));
+        }
+        if file.shard >= file.num_shards {
+            return Err(format!("{path} has an invalid shard {}", file.shard));
+        }
+        if std::mem::replace(&mut shards[file.shard], true) {
+            return Err(format!("Shard {} is given more than once", file.shard));
+        }
+        let mut handler = new_handler();
+        handler
+            .load_state(file.handlers.clone())
+            .map_err(|err| format!("Failed to load {path}: {err}"))?;
+        merged.merge(&handler);
+        games += file.games;
+    }
+    merged.on_simulation_end();
+
+    let missing = shards.iter().filter(|&&merged| !merged).count();
+    if missing > 0 {
+        warn!(
+            "Merged {} of {} shards ({} games); {missing} shards are missing",
+            files.len(),
+            first.num_shards,
+            games.to_formatted_string(&Locale::en)
+        );
+    }
+    Ok(merged)
+}
+
+/// Handlers that `merge_results` can recreate from a result file
+fn builtin_handler_factory(name: &str) -> Option<fn() -> Box<dyn SimulationEventHandler>> {
+    if name == short_type_name::<StatsCollector>() {
+        Some(|| Box::new(StatsCollector::default()))
+    } else if name == short_type_name::<GameResultsCollector>() {
+        Some(|| Box::new(GameResultsCollector::default()))
+    } else {
+        None
+    }
+}
+
+/// Type name without its module path (e.g. "StatsCollector")
+fn short_type_name<T>() -> &'static str {
+    let name = std::any::type_name::<T>();
+    name.rsplit("::").next().unwrap_or(name)
+}
+
+/// Optional outputs of `simulate_with_options`
+#[derive(Clone, Default)]
+pub struct SimulateOptions {
+    /// Write one row per game to this path (`.jsonl`, or `.csv` with the `csv-export` feature)
+    pub export_path: Option<String>,
+    /// Save progress to this checkpoint and resume from it
+    pub checkpoint: Option<CheckpointConfig>,
+    /// Only play this part of the games, as `(shard, number of shards)`
+    pub shard: Option<(usize, usize)>,
+    /// Write a result file that `deckgym merge` can combine with other shards
+    pub results_path: Option<String>,
+    /// Card changes to play both decks with
+    pub errata: Option<Errata>,
+}
+
 /// Legacy functional API for backwards compatibility
 pub fn simulate(
     deck_a_path: &str,
@@ -259,6 +550,39 @@ pub fn simulate(
     parallel: bool,
     num_threads: Option<usize>,
 ) {
+    simulate_with_options(
+        deck_a_path,
+        deck_b_path,
+        SimulationConfig {
+            num_games: num_simulations,
+            players,
+            seed,
+        },
+        ParallelConfig {
+            enabled: parallel,
+            num_threads,
+        },
+        &SimulateOptions::default(),
+    );
+}
+
+/// Runs and reports a simulation between two deck files, as the CLI `simulate` command does
+pub fn simulate_with_options(
+    deck_a_path: &str,
+    deck_b_path: &str,
+    sim_config: SimulationConfig,
+    parallel_config: ParallelConfig,
+    options: &SimulateOptions,
+) {
+    let SimulationConfig {
+        num_games: num_simulations,
+        players,
+        seed,
+    } = sim_config;
+    let ParallelConfig {
+        enabled: parallel,
+        num_threads,
+    } = parallel_config;
     let player_codes = fill_code_array(players);
 
     warn!(
@@ -272,11 +596,17 @@ pub fn simulate(
         warn!("\tThreads: {}", threads);
     }
 
-    // Create progress bar
-    let pb = create_progress_bar(num_simulations as u64);
-    pb.tick(); // Ensure progress bar is drawn immediately
+    if let Some((shard, num_shards)) = options.shard {
+        warn!("\tShard: {} of {}", shard + 1, num_shards);
+    }
+    if let Some(errata) = &options.errata {
+        warn!("\tErrata:");
+        for line in errata.describe() {
+            warn!("\t\t{line}");
+        }
+    }
 
-    let mut simulation = Simulation::new(
+    let simulation = Simulation::new(
         deck_a_path,
         deck_b_path,
         player_codes,
@@ -284,21 +614,150 @@ pub fn simulate(
         seed,
         parallel,
         num_threads,
-    )
-    .expect("Failed to create simulation");
-    simulation = simulation.register::<StatsCollector>();
+    );
+    let mut simulation = match simulation {
+        Ok(simulation) => simulation.register::<StatsCollector>(),
+        Err(err) => {
+            warn!("Failed to create simulation: {err}");
+            return;
+        }
+    };
+    if options.export_path.is_some() {
+        simulation = simulation.register::<GameResultsCollector>();
+    }
 
+    if let Some(errata) = &options.errata {
+        simulation = simulation.with_errata(errata);
+    }
+    if let Some(checkpoint) = &options.checkpoint {
+        simulation = simulation.with_checkpoint(checkpoint.clone());
+    }
+    if let Some((shard, num_shards)) = options.shard {
+        simulation = simulation.with_shard(shard, num_shards);
+    }
+
+    // Create progress bar
+    let pb = create_progress_bar(simulation.game_range().len() as u64);
+    pb.tick(); // Ensure progress bar is drawn immediately
     let pb_clone = pb.clone();
     simulation = simulation.with_callback(move || pb_clone.inc(1));
-    simulation.run();
+    let result = simulation.try_run();
 
     pb.finish_with_message("Simulation complete!");
+    if let Err(err) = result {
+        warn!("{err}");
+        return;
+    }
 
     // Retrieve and print statistics
     if let Some(collector) = simulation.get_event_handler::<StatsCollector>() {
         let stats = collector.compute_stats();
         print_stats(&stats);
     }
+
+    if let Some(export_path) = &options.export_path {
+        let collector = simulation
+            .get_event_handler::<GameResultsCollector>()
+            .expect("GameResultsCollector should be registered");
+        export_game_results(collector, export_path);
+    }
+
+    if let Some(results_path) = &options.results_path {
+        match simulation.write_results(results_path) {
+            Ok(()) => warn!("Wrote mergeable results to {results_path}"),
+            Err(err) => warn!("{err}"),
+        }
+    }
+}
+
+/// Parses a 1-based "I/N" shard argument into `(shard, number of shards)` with a 0-based shard
+pub fn parse_shard(value: &str) -> Result<(usize, usize), String> {
+    let invalid = || format!("Invalid shard '{value}', expected I/N with 1 <= I <= N");
+    let (shard, num_shards) = value.split_once('/').ok_or_else(invalid)?;
+    let shard: usize = shard.trim().parse().map_err(|_| invalid())?;
+    let num_shards: usize = num_shards.trim().parse().map_err(|_| invalid())?;
+    if shard == 0 || shard > num_shards {
+        return Err(invalid());
+    }
+    Ok((shard - 1, num_shards))
+}
+
+/// Merges shard result files and reports them, as the CLI `merge` command does
+pub fn cli_merge(paths: &[String], export_path: Option<&str>) {
+    let merged = match merge_results(paths) {
+        Ok(merged) => merged,
+        Err(err) => {
+            warn!("{err}");
+            return;
+        }
+    };
+    if let Some(collector) = merged.get_handler::<StatsCollector>() {
+        print_stats(&collector.compute_stats());
+    }
+    if let Some(export_path) = export_path {
+        match merged.get_handler::<GameResultsCollector>() {
+            Some(collector) => export_game_results(collector, export_path),
+            None => warn!("The result files don't contain game results; run shards with --export"),
+        }
+    }
+}
+
+fn export_game_results(collector: &GameResultsCollector, export_path: &str) {
+    match collector.export(export_path) {
+        Ok(()) => warn!(
+            "Wrote {} game results to {}",
+            collector.records().len().to_formatted_string(&Locale::en),
+            export_path
+        ),
+        Err(err) => warn!("Failed to export game results to {export_path}: {err}"),
+    }
+}
+
+/// Final state of a game played with `play_game`
+pub struct PlayedGame {
+    pub state: State,
+    /// Seat index of the player that won the coin toss and took turn 1
+    pub first_player: usize,
+}
+
+/// Plays one game between two decks without event handlers.
+/// Building block for runners (gauntlet, optimizer, ...) that only need outcomes.
+pub fn play_game(
+    deck_a: &Deck,
+    deck_b: &Deck,
+    player_codes: Vec<PlayerCode>,
+    seed: u64,
+) -> PlayedGame {
+    let players = create_players(deck_a.clone(), deck_b.clone(), player_codes);
+    let mut game = Game::new(players, seed);
+    let first_player = game.state().current_player;
+    game.play();
+    PlayedGame {
+        state: game.get_state_clone(),
+        first_player,
+    }
+}
+
+/// Seed of the `game_index`-th game of a run. With a base seed every game is
+/// reproducible on its own (and games still differ from each other); without one it is random.
+pub fn game_seed(base_seed: Option<u64>, game_index: u64) -> u64 {
+    match base_seed {
+        Some(seed) => seed.wrapping_add(game_index),
+        None => rand::random::<u64>(),
+    }
+}
+
+/// Identifies a deck by its file name without extension (e.g. "venusaur-exeggutor")
+pub fn deck_name_from_path(path: &str) -> String {
+    std::path::Path::new(path)
+        .file_stem()
+        .and_then(|stem| stem.to_str())
+        .unwrap_or(path)
+        .to_string()
+}
+
+fn default_deck_names() -> [String; 2] {
+    ["deck_a".to_string(), "deck_b".to_string()]
 }
 
 /// Creates a styled progress bar with consistent styling across the codebase
@@ -351,6 +810,14 @@ pub fn print_stats(stats: &crate::simulation_event_handler::ComputedStats) {
         stats.ties.to_formatted_string(&Locale::en),
         stats.tie_rate * 100.0
     );
+    for (reason, count) in &stats.end_reasons {
+        warn!(
+            "\tEnded by {}: {} ({:.2}%)",
+            reason,
+            count.to_formatted_string(&Locale::en),
+            *count as f32 / stats.num_games as f32 * 100.0
+        );
+    }
 }
 
 // Set up the logger according to the given verbosity.
@@ -366,3 +833,119 @@ pub fn initialize_logger(verbose: u8) {
         .format(|buf, record| writeln!(buf, "{}", record.args()))
         .init();
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        errata::Errata,
+        models::{Card, PokemonCard},
+    };
+
+    fn simulation(num_games: u32) -> Simulation {
+        Simulation::new(
+            "example_decks/venusaur-exeggutor.txt",
+            "example_decks/weezing-arbok.txt",
+            vec![PlayerCode::R, PlayerCode::R],
+            num_games,
+            Some(3),
+            false,
+            None,
+        )
+        .unwrap()
+        .register::<StatsCollector>()
+        .register::<GameResultsCollector>()
+    }
+
+    #[test]
+    fn test_parse_shard() {
+        assert_eq!(parse_shard("1/4"), Ok((0, 4)));
+        assert_eq!(parse_shard("4/4"), Ok((3, 4)));
+        assert!(parse_shard("0/4").is_err());
+        assert!(parse_shard("5/4").is_err());
+        assert!(parse_shard("2").is_err());
+    }
+
+    #[test]
+    fn test_merged_shards_match_whole_run() {
+        let mut whole = simulation(5);
+        whole.run();
+        let whole_stats = whole
+            .get_event_handler::<StatsCollector>()
+            .unwrap()
+            .compute_stats();
+
+        let dir = std::env::temp_dir();
+        let paths: Vec<String> = (0..2)
+            .map(|shard| {
+                let path = dir.join(format!("deckgym_shard_test_{shard}.json"));
+                let path = path.to_str().unwrap().to_string();
+                let mut part = simulation(5).with_shard(shard, 2);
+                part.run();
+                part.write_results(&path).unwrap();
+                path
+            })
+            .collect();
+
+        let merged = merge_results(&paths).unwrap();
+        let stats = merged
+            .get_handler::<StatsCollector>()
+            .unwrap()
+            .compute_stats();
+        assert_eq!(stats.num_games, 5);
+        assert_eq!(stats.player_a_wins, whole_stats.player_a_wins);
+        assert_eq!(stats.player_b_wins, whole_stats.player_b_wins);
+        assert_eq!(stats.ties, whole_stats.ties);
+        let mut seeds: Vec<u64> = merged
+            .get_handler::<GameResultsCollector>()
+            .unwrap()
+            .records()
+            .iter()
+            .map(|record| record.seed)
+            .collect();
+        seeds.sort();
+        assert_eq!(seeds, (3..8).collect::<Vec<_>>());
+
+        // The same shard can't be counted twice
+        assert!(merge_results(&[paths[0].clone(), paths[0].clone()]).is_err());
+        for path in paths {
+            std::fs::remove_file(path).ok();
+        }
+    }
+
+    #[test]
+    fn test_simulation_plays_both_decks_with_errata() {
+        let errata = Errata::from_string("A1 129: hp 10").unwrap();
+        let simulation = Simulation::new(
+            "example_decks/mewtwoex.txt",
+            "example_decks/mewtwoex.txt",
+            vec![PlayerCode::R, PlayerCode::R],
+            2,
+            Some(7),
+            false,
+            None,
+        )
+        .unwrap()
+        .with_errata(&errata);
+        for deck in [&simulation.deck_a, &simulation.deck_b] {
+            let mewtwos: Vec<&PokemonCard> = deck
+                .cards
+                .iter()
+                .filter(|card| card.get_id() == "A1 129")
+                .map(|&card| match card {
+                    Card::Pokemon(pokemon) => pokemon,
+                    Card::Trainer(_) => panic!("Mewtwo ex is a Pokémon"),
+                })
+                .collect();
+            assert_eq!(mewtwos.len(), 2);
+            assert!(mewtwos.iter().all(|pokemon| pokemon.hp == 10));
+        }
+        assert_eq!(
+            simulation.run_parameters()["errata"],
+            serde_json::json!(["Mewtwo ex (A1 129): HP 150 -> 10"])
+        );
+
+        let mut simulation = simulation.register::<StatsCollector>();
+        assert_eq!(simulation.run().len(), 2);
+    }
+}
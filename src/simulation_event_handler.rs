@@ -1,12 +1,28 @@
 use core::panic;
 use log::info;
+use serde::{Deserialize, Serialize};
 use std::{
     any,
+    collections::BTreeMap,
     time::{Duration, Instant},
 };
 use uuid::Uuid;
 
-use crate::{actions::Action, state::GameOutcome, State};
+use crate::{
+    actions::Action,
+    state::{GameEndReason, GameOutcome},
+    State,
+};
+
+/// Per-game details known before the first action is played.
+#[derive(Debug, Clone)]
+pub struct GameSetup {
+    pub seed: u64,
+    /// Identifiers of the decks in seat order (e.g. file stems)
+    pub deck_names: [String; 2],
+    /// Strategies in seat order (e.g. "e2", "r")
+    pub player_names: [String; 2],
+}
 
 /// Trait to listen to simulation events
 /// Simulations are run in parallel. One instance of SimulationEventHandler will be created
@@ -18,8 +34,19 @@ pub trait SimulationEventHandler: any::Any + Send {
     fn on_simulation_end(&mut self) {}
     fn merge(&mut self, _other: &dyn SimulationEventHandler);
 
+    // Checkpoint Methods (called on the "main" instance, see `Simulation::with_checkpoint`)
+    /// State of the games merged so far, or `None` if this handler can't be checkpointed
+    fn save_state(&self) -> Option<serde_json::Value> {
+        None
+    }
+    /// Restores a state returned by `save_state`
+    fn load_state(&mut self, _state: serde_json::Value) -> Result<(), String> {
+        Err("This event handler doesn't support checkpoints".to_string())
+    }
+
     // Game Methods (these will be called on per-thread instances of SimulationEventHandler)
     fn on_game_start(&mut self, _game_id: Uuid) {}
+    fn on_game_setup(&mut self, _game_id: Uuid, _setup: &GameSetup) {}
     fn on_action(
         &mut self,
         _game_id: Uuid,
@@ -60,6 +87,12 @@ impl SimulationEventHandler for CompositeSimulationEventHandler {
         }
     }
 
+    fn on_game_setup(&mut self, game_id: Uuid, setup: &GameSetup) {
+        for handler in self.handlers.iter_mut() {
+            handler.on_game_setup(game_id, setup);
+        }
+    }
+
     fn on_action(
         &mut self,
         game_id: Uuid,
@@ -91,6 +124,31 @@ impl SimulationEventHandler for CompositeSimulationEventHandler {
         }
     }
 
+    fn save_state(&self) -> Option<serde_json::Value> {
+        let states: Option<Vec<serde_json::Value>> = self
+            .handlers
+            .iter()
+            .map(|handler| handler.save_state())
+            .collect();
+        states.map(serde_json::Value::Array)
+    }
+
+    fn load_state(&mut self, state: serde_json::Value) -> Result<(), String> {
+        let states: Vec<serde_json::Value> =
+            serde_json::from_value(state).map_err(|err| err.to_string())?;
+        if states.len() != self.handlers.len() {
+            return Err(format!(
+                "Expected the state of {} event handlers, got {}",
+                self.handlers.len(),
+                states.len()
+            ));
+        }
+        for (handler, state) in self.handlers.iter_mut().zip(states) {
+            handler.load_state(state)?;
+        }
+        Ok(())
+    }
+
     fn merge(&mut self, other: &dyn SimulationEventHandler) {
         if let Some(other_mytype) =
             (other as &dyn any::Any).downcast_ref::<CompositeSimulationEventHandler>()
@@ -121,6 +179,8 @@ pub struct ComputedStats {
     pub player_a_win_rate: f32,
     pub player_b_win_rate: f32,
     pub tie_rate: f32,
+    /// Number of games per end reason
+    pub end_reasons: BTreeMap<GameEndReason, u32>,
 }
 
 // Example: Statistics collector
@@ -134,9 +194,25 @@ pub struct StatsCollector {
     player_a_wins: u32,
     player_b_wins: u32,
     ties: u32,
-    turns_per_game: Vec<u8>,
-    plys_per_game: Vec<u32>,
-    total_degrees: Vec<u32>,
+    end_reasons: BTreeMap<GameEndReason, u32>,
+    total_turns: u64,
+    total_plys: u64,
+    total_degrees: u64,
+}
+
+/// What a `StatsCollector` keeps in checkpoints
+#[derive(Serialize, Deserialize)]
+struct StatsCollectorState {
+    /// Time spent on the games so far
+    duration: Duration,
+    num_games: u32,
+    player_a_wins: u32,
+    player_b_wins: u32,
+    ties: u32,
+    end_reasons: BTreeMap<GameEndReason, u32>,
+    total_turns: u64,
+    total_plys: u64,
+    total_degrees: u64,
 }
 
 impl Default for StatsCollector {
@@ -155,9 +231,10 @@ impl StatsCollector {
             player_a_wins: 0,
             player_b_wins: 0,
             ties: 0,
-            turns_per_game: vec![],
-            plys_per_game: vec![],
-            total_degrees: vec![],
+            end_reasons: BTreeMap::new(),
+            total_turns: 0,
+            total_plys: 0,
+            total_degrees: 0,
         }
     }
 }
@@ -184,9 +261,12 @@ impl SimulationEventHandler for StatsCollector {
 
         self.end = self.end.max(Instant::now()); // maximum ever seen
         self.num_games += 1;
-        self.turns_per_game.push(state.turn_count);
-        self.plys_per_game.push(self.degrees_per_ply.len() as u32);
-        self.total_degrees.extend(self.degrees_per_ply.iter());
+        self.total_turns += state.turn_count as u64;
+        self.total_plys += self.degrees_per_ply.len() as u64;
+        self.total_degrees += self.degrees_per_ply.iter().map(|&d| d as u64).sum::<u64>();
+        if let Some(reason) = state.end_reason {
+            *self.end_reasons.entry(reason).or_insert(0) += 1;
+        }
 
         match outcome {
             Some(GameOutcome::Win(winner_name)) => {
@@ -210,19 +290,54 @@ impl SimulationEventHandler for StatsCollector {
             self.start = self.start.min(other_mytype.start);
             self.end = self.end.max(other_mytype.end);
             self.num_games += other_mytype.num_games;
-            self.turns_per_game
-                .extend(other_mytype.turns_per_game.iter());
-            self.plys_per_game.extend(other_mytype.plys_per_game.iter());
-            self.total_degrees.extend(other_mytype.total_degrees.iter());
+            self.total_turns += other_mytype.total_turns;
+            self.total_plys += other_mytype.total_plys;
+            self.total_degrees += other_mytype.total_degrees;
             self.player_a_wins += other_mytype.player_a_wins;
             self.player_b_wins += other_mytype.player_b_wins;
             self.ties += other_mytype.ties;
+            for (reason, count) in &other_mytype.end_reasons {
+                *self.end_reasons.entry(*reason).or_insert(0) += count;
+            }
         } else {
             panic!("Attempted to merge StatsCollector with incompatible type");
         }
     }
 
     fn on_simulation_end(&mut self) {}
+
+    fn save_state(&self) -> Option<serde_json::Value> {
+        let state = StatsCollectorState {
+            duration: self.end.saturating_duration_since(self.start),
+            num_games: self.num_games,
+            player_a_wins: self.player_a_wins,
+            player_b_wins: self.player_b_wins,
+            ties: self.ties,
+            end_reasons: self.end_reasons.clone(),
+            total_turns: self.total_turns,
+            total_plys: self.total_plys,
+            total_degrees: self.total_degrees,
+        };
+        serde_json::to_value(state).ok()
+    }
+
+    fn load_state(&mut self, state: serde_json::Value) -> Result<(), String> {
+        let state: StatsCollectorState =
+            serde_json::from_value(state).map_err(|err| err.to_string())?;
+        // Games played from now on add their time to the saved duration
+        let now = Instant::now();
+        self.start = now.checked_sub(state.duration).unwrap_or(now);
+        self.end = now;
+        self.num_games = state.num_games;
+        self.player_a_wins = state.player_a_wins;
+        self.player_b_wins = state.player_b_wins;
+        self.ties = state.ties;
+        self.end_reasons = state.end_reasons;
+        self.total_turns = state.total_turns;
+        self.total_plys = state.total_plys;
+        self.total_degrees = state.total_degrees;
+        Ok(())
+    }
 }
 
 impl StatsCollector {
@@ -232,20 +347,14 @@ impl StatsCollector {
         let avg_time_per_game = duration.as_secs_f64() / self.num_games as f64;
         let avg_duration = Duration::from_secs_f64(avg_time_per_game);
 
-        let avg_turns_per_game = self
-            .turns_per_game
-            .iter()
-            .map(|&turns| turns as u32)
-            .sum::<u32>() as f32
-            / self.num_games as f32;
+        let avg_turns_per_game = self.total_turns as f32 / self.num_games as f32;
 
-        let avg_plys_per_game =
-            self.plys_per_game.iter().sum::<u32>() as f32 / self.num_games as f32;
+        let avg_plys_per_game = self.total_plys as f32 / self.num_games as f32;
 
-        let avg_degrees_per_ply = if self.total_degrees.is_empty() {
+        let avg_degrees_per_ply = if self.total_plys == 0 {
             0.0
         } else {
-            self.total_degrees.iter().sum::<u32>() as f32 / self.total_degrees.len() as f32
+            self.total_degrees as f32 / self.total_plys as f32
         };
 
         let player_a_win_rate = self.player_a_wins as f32 / self.num_games as f32;
@@ -265,6 +374,7 @@ impl StatsCollector {
             player_a_win_rate,
             player_b_win_rate,
             tie_rate,
+            end_reasons: self.end_reasons.clone(),
         }
     }
 }
@@ -22,10 +22,46 @@ pub enum GameOutcome {
     Tie,
 }
 
+/// Games that reach this global turn count without a winner end as a tie.
+pub const TURN_LIMIT: u8 = 100;
+
+/// Why a game ended. TCG Pocket has no deck-out loss (players just skip the draw),
+/// so running out of cards is not a reason on its own.
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GameEndReason {
+    /// A player reached 3 points
+    PointsReached,
+    /// The loser had no Pokémon left to promote to the Active Spot
+    NoPokemonInPlay,
+    /// Both players reached 3 points in the same knockout resolution
+    SimultaneousKnockoutTie,
+    /// `TURN_LIMIT` was reached without a winner
+    TurnLimit,
+}
+
+impl GameEndReason {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            GameEndReason::PointsReached => "points_reached",
+            GameEndReason::NoPokemonInPlay => "no_pokemon_in_play",
+            GameEndReason::SimultaneousKnockoutTie => "simultaneous_knockout_tie",
+            GameEndReason::TurnLimit => "turn_limit",
+        }
+    }
+}
+
+impl std::fmt::Display for GameEndReason {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
 #[derive(Debug, Clone, Hash, PartialEq, Eq, Default, Serialize, Deserialize)]
 pub struct State {
     // Turn State
     pub winner: Option<GameOutcome>,
+    pub end_reason: Option<GameEndReason>,
     pub points: [u8; 2],
     pub turn_count: u8, // Global turn count. Matches TCGPocket app.
     // Player that needs to select from playable actions. Might not be aligned
@@ -61,6 +97,7 @@ impl State {
     pub fn new(deck_a: &Deck, deck_b: &Deck) -> Self {
         Self {
             winner: None,
+            end_reason: None,
             points: [0, 0],
             turn_count: 0,
             current_player: 0,
@@ -181,7 +218,7 @@ impl State {
 
     pub(crate) fn discard_card_from_hand(&mut self, current_player: usize, card: &Card) {
         self.remove_card_from_hand(current_player, card);
-        self.discard_piles[current_player].push(card.clone());
+        self.discard_piles[current_player].push(*card);
     }
 
     /// Returns an iterator over supporter cards in a player's hand
@@ -191,7 +228,7 @@ impl State {
 
     pub(crate) fn maybe_draw_card(&mut self, player: usize) {
         if let Some((card, vis)) = self.decks[player].draw() {
-            self.hands[player].push(card.clone());
+            self.hands[player].push(card);
             self.hands_visibility[player].push(vis);
             debug!(
                 "Player {} drew: {:?}, now hand is: {:?} and deck has {} cards",
@@ -226,7 +263,7 @@ impl State {
         if !self.decks[player].visibility.is_empty() {
             self.decks[player].visibility.remove(pos);
         }
-        self.hands[player].push(card.clone());
+        self.hands[player].push(*card);
         // Transferring from deck to hand usually implies searching/revealing.
         // Prompt says: "When returning to hand... revealed". Safest to assume searches reveal.
         self.hands_visibility[player].push(true);
@@ -240,7 +277,7 @@ impl State {
             .expect("Card must exist in hand to transfer to deck");
         self.hands[player].remove(pos);
         self.hands_visibility[player].remove(pos);
-        self.decks[player].cards.push(card.clone());
+        self.decks[player].cards.push(*card);
         // Reset to non-visible when returning to deck
         self.decks[player].visibility.push(false);
     }
@@ -392,13 +429,23 @@ impl State {
         );
         self.current_player = (self.current_player + 1) % 2;
         self.turn_count += 1;
+        if self.turn_count >= TURN_LIMIT && self.winner.is_none() {
+            debug!("Turn limit reached without a winner");
+            self.end_reason = Some(GameEndReason::TurnLimit);
+        }
         self.end_turn_maintenance();
         self.maybe_draw_card(self.current_player);
         self.generate_energy(rng);
     }
 
     pub(crate) fn is_game_over(&self) -> bool {
-        self.winner.is_some() || self.turn_count >= 100
+        self.winner.is_some() || self.turn_count >= TURN_LIMIT
+    }
+
+    /// Ends the game, recording both the outcome and why it happened.
+    pub(crate) fn declare_outcome(&mut self, outcome: GameOutcome, reason: GameEndReason) {
+        self.winner = Some(outcome);
+        self.end_reason = Some(reason);
     }
 
     pub(crate) fn num_in_play_of_type(&self, player: usize, energy: EnergyType) -> usize {
@@ -419,7 +466,7 @@ impl State {
             .expect("There should be a Pokemon to discard");
         let mut cards_to_discard = ko_pokemon.cards_behind.clone();
         // TODO: Include attached Tools
-        cards_to_discard.push(ko_pokemon.card.clone());
+        cards_to_discard.push(ko_pokemon.card);
         debug!("Discarding: {cards_to_discard:?}");
         self.discard_piles[ko_receiver].extend(cards_to_discard);
         self.discard_energies[ko_receiver].extend(ko_pokemon.attached_energy.iter().cloned());
@@ -475,7 +522,7 @@ impl State {
         if enumerated_bench_pokemon.is_empty() {
             // If no bench pokemon, opponent wins
             let opponent = (player_with_empty_active + 1) % 2;
-            self.winner = Some(GameOutcome::Win(opponent));
+            self.declare_outcome(GameOutcome::Win(opponent), GameEndReason::NoPokemonInPlay);
             debug!("Player {player_with_empty_active} lost due to no bench pokemon");
         } else {
             // Queue up promotion actions
@@ -675,6 +722,35 @@ mod tests {
         assert!(state.next_energies[0].is_some());
     }
 
+    #[test]
+    fn test_losing_last_pokemon_records_end_reason() {
+        let (deck_a, deck_b) = load_test_decks();
+        let mut state = State::new(&deck_a, &deck_b);
+
+        state.trigger_promotion_or_declare_winner(1);
+
+        assert_eq!(state.winner, Some(GameOutcome::Win(0)));
+        assert_eq!(state.end_reason, Some(GameEndReason::NoPokemonInPlay));
+    }
+
+    #[test]
+    fn test_turn_limit_records_end_reason() {
+        use rand::SeedableRng;
+        let (deck_a, deck_b) = load_test_decks();
+        let mut state = State::new(&deck_a, &deck_b);
+        state.turn_count = TURN_LIMIT - 2;
+        let mut rng = StdRng::seed_from_u64(0);
+
+        state.advance_turn(&mut rng);
+        assert!(!state.is_game_over());
+        assert_eq!(state.end_reason, None);
+
+        state.advance_turn(&mut rng);
+        assert!(state.is_game_over());
+        assert_eq!(state.winner, None);
+        assert_eq!(state.end_reason, Some(GameEndReason::TurnLimit));
+    }
+
     #[test]
     #[should_panic(
         expected = "Card Pokemon(A1 001 Bulbasaur) must exist in deck to transfer to hand (Source: Test Source)"
@@ -78,7 +78,7 @@ impl PlayedCard {
                 }
             }
         };
-        Self::new(card.clone(), total_hp, total_hp, vec![], false, vec![])
+        Self::new(*card, total_hp, total_hp, vec![], false, vec![])
     }
 
     /// Create a fresh PlayedCard from a CardId at full HP with no energy, tools, or status.
@@ -237,15 +237,7 @@ impl PlayedCard {
     /// Apply a status condition to this Pokémon, respecting Arceus ex immunity
     pub(crate) fn apply_status_condition(&mut self, status: StatusCondition) {
         // Arceus Ex avoids status effects
-        let string_id = self.get_id();
-        let arceus_ids = [
-            CardId::A2a071ArceusEx,
-            CardId::A2a086ArceusEx,
-            CardId::A2a095ArceusEx,
-            CardId::A2a096ArceusEx,
-        ];
-        let card_id = CardId::from_card_id(&string_id).unwrap();
-        if arceus_ids.contains(&card_id) {
+        if self.card.get_canonical_card_id() == Some(CardId::A2a071ArceusEx) {
             debug!("Arceus Ex avoids status effect");
             return;
         }
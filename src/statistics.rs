@@ -0,0 +1,195 @@
+use serde::{Deserialize, Serialize};
+
+use crate::state::GameOutcome;
+
+/// z-score of a two-sided 95% confidence interval
+pub const Z_95: f64 = 1.96;
+
+/// Wins, losses and ties of one side of a matchup.
+///
+/// Win rates follow the rest of the crate: ties count as games that were not won.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MatchTally {
+    pub wins: u32,
+    pub losses: u32,
+    pub ties: u32,
+}
+
+impl MatchTally {
+    /// Records a finished game from the point of view of the player in `seat`.
+    /// Games without a winner (ties and turn limits) count as ties.
+    pub fn record(&mut self, outcome: Option<GameOutcome>, seat: usize) {
+        match outcome {
+            Some(GameOutcome::Win(winner)) if winner == seat => self.wins += 1,
+            Some(GameOutcome::Win(_)) => self.losses += 1,
+            Some(GameOutcome::Tie) | None => self.ties += 1,
+        }
+    }
+
+    pub fn merge(&mut self, other: &MatchTally) {
+        self.wins += other.wins;
+        self.losses += other.losses;
+        self.ties += other.ties;
+    }
+
+    pub fn games(&self) -> u32 {
+        self.wins + self.losses + self.ties
+    }
+
+    /// The same games seen from the opponent's side
+    pub fn flipped(&self) -> MatchTally {
+        MatchTally {
+            wins: self.losses,
+            losses: self.wins,
+            ties: self.ties,
+        }
+    }
+
+    pub fn win_rate(&self) -> f64 {
+        if self.games() == 0 {
+            0.0
+        } else {
+            self.wins as f64 / self.games() as f64
+        }
+    }
+
+    /// 95% Wilson score interval of the win rate
+    pub fn confidence_interval(&self) -> (f64, f64) {
+        wilson_interval(self.wins, self.games(), Z_95)
+    }
+}
+
+/// Wilson score interval for `successes` out of `trials` at the given z-score.
+/// Unlike the normal approximation it stays inside [0, 1] and is sensible for small samples.
+pub fn wilson_interval(successes: u32, trials: u32, z: f64) -> (f64, f64) {
+    if trials == 0 {
+        return (0.0, 1.0);
+    }
+    let n = trials as f64;
+    let p = successes as f64 / n;
+    let z2 = z * z;
+    let denominator = 1.0 + z2 / n;
+    let center = (p + z2 / (2.0 * n)) / denominator;
+    let margin = z * (p * (1.0 - p) / n + z2 / (4.0 * n * n)).sqrt() / denominator;
+    ((center - margin).max(0.0), (center + margin).min(1.0))
+}
+
+/// Weighted average of several win rates and its 95% confidence interval (normal approximation).
+///
+/// Each item is `(weight, tally)`; weights are normalized over the tallies that have games.
+pub fn weighted_win_rate(items: &[(f64, MatchTally)]) -> (f64, (f64, f64)) {
+    let Some((mean, variance)) = weighted_mean_and_variance(items) else {
+        return (0.0, (0.0, 1.0));
+    };
+    let margin = Z_95 * variance.sqrt();
+    (mean, ((mean - margin).max(0.0), (mean + margin).min(1.0)))
+}
+
+/// Difference between the weighted win rates of `variant` and `baseline` (see `weighted_win_rate`)
+/// and its 95% confidence interval, treating both samples as independent.
+pub fn win_rate_difference(
+    variant: &[(f64, MatchTally)],
+    baseline: &[(f64, MatchTally)],
+) -> (f64, (f64, f64)) {
+    match (
+        weighted_mean_and_variance(variant),
+        weighted_mean_and_variance(baseline),
+    ) {
+        (Some((variant_mean, variant_variance)), Some((baseline_mean, baseline_variance))) => {
+            let difference = variant_mean - baseline_mean;
+            let margin = Z_95 * (variant_variance + baseline_variance).sqrt();
+            (difference, (difference - margin, difference + margin))
+        }
+        _ => (0.0, (-1.0, 1.0)),
+    }
+}
+
+fn weighted_mean_and_variance(items: &[(f64, MatchTally)]) -> Option<(f64, f64)> {
+    let total_weight: f64 = items
+        .iter()
+        .filter(|(_, tally)| tally.games() > 0)
+        .map(|(weight, _)| weight)
+        .sum();
+    if total_weight <= 0.0 {
+        return None;
+    }
+
+    let mut mean = 0.0;
+    let mut variance = 0.0;
+    for (weight, tally) in items.iter().filter(|(_, tally)| tally.games() > 0) {
+        let share = weight / total_weight;
+        let p = tally.win_rate();
+        mean += share * p;
+        variance += share * share * p * (1.0 - p) / tally.games() as f64;
+    }
+    Some((mean, variance))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_record_from_both_seats() {
+        let mut tally = MatchTally::default();
+        tally.record(Some(GameOutcome::Win(0)), 0);
+        tally.record(Some(GameOutcome::Win(0)), 1);
+        tally.record(Some(GameOutcome::Tie), 0);
+        tally.record(None, 1);
+
+        assert_eq!(
+            tally,
+            MatchTally {
+                wins: 1,
+                losses: 1,
+                ties: 2
+            }
+        );
+        assert_eq!(tally.flipped().losses, 1);
+        assert_eq!(tally.win_rate(), 0.25);
+    }
+
+    #[test]
+    fn test_wilson_interval() {
+        let (low, high) = wilson_interval(50, 100, Z_95);
+        assert!((low - 0.4038).abs() < 1e-3);
+        assert!((high - 0.5962).abs() < 1e-3);
+
+        let (low, high) = wilson_interval(0, 10, Z_95);
+        assert_eq!(low, 0.0);
+        assert!(high > 0.2 && high < 0.35);
+    }
+
+    #[test]
+    fn test_weighted_win_rate_ignores_empty_matchups() {
+        let strong = MatchTally {
+            wins: 80,
+            losses: 20,
+            ties: 0,
+        };
+        let weak = MatchTally {
+            wins: 20,
+            losses: 80,
+            ties: 0,
+        };
+        let (mean, (low, high)) =
+            weighted_win_rate(&[(3.0, strong), (1.0, weak), (10.0, MatchTally::default())]);
+        assert!((mean - 0.65).abs() < 1e-9);
+        assert!(low < mean && mean < high);
+    }
+
+    #[test]
+    fn test_win_rate_difference() {
+        let tally = |wins, losses| MatchTally {
+            wins,
+            losses,
+            ties: 0,
+        };
+        let (difference, (low, high)) =
+            win_rate_difference(&[(1.0, tally(60, 40))], &[(1.0, tally(50, 50))]);
+        assert!((difference - 0.1).abs() < 1e-9);
+        // sqrt(0.24 / 100 + 0.25 / 100) = 0.07
+        assert!((high - difference - Z_95 * 0.07).abs() < 1e-9);
+        assert!(low < 0.0);
+    }
+}
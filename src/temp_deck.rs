@@ -1,20 +1,13 @@
 use std::collections::HashSet;
 
 use crate::card_ids::CardId;
-use crate::database::get_card_by_enum;
+use crate::database::{get_card_by_enum, get_card_ref};
 use crate::models::{Card, EnergyType, PokemonCard, TrainerType};
 use strum::IntoEnumIterator;
 
 /// Find a CardId by its ID string (e.g., "A1 003")
 pub fn find_card_id(id: &str) -> Option<CardId> {
-    for card_id in CardId::iter() {
-        let card = get_card_by_enum(card_id);
-        let card_id_str = card.get_id();
-        if card_id_str == id {
-            return Some(card_id);
-        }
-    }
-    None
+    CardId::from_card_id(id)
 }
 
 /// Generate a temporary deck for testing based on the card type.
@@ -52,7 +45,7 @@ fn get_evolution_line(card: &Card) -> (Option<Card>, Option<Card>, Option<Card>)
             } else {
                 None
             };
-            (Some(card.clone()), stage1, stage2)
+            (Some(*card), stage1, stage2)
         }
         1 => {
             // Stage 1 - find Basic and Stage 2
@@ -61,7 +54,7 @@ fn get_evolution_line(card: &Card) -> (Option<Card>, Option<Card>, Option<Card>)
                 .as_ref()
                 .and_then(|name| find_card_by_name(name));
             let stage2 = find_stage2_evolution(&pokemon.name);
-            (basic, Some(card.clone()), stage2)
+            (basic, Some(*card), stage2)
         }
         2 => {
             // Stage 2 - find Stage 1 and Basic
@@ -77,7 +70,7 @@ fn get_evolution_line(card: &Card) -> (Option<Card>, Option<Card>, Option<Card>)
             } else {
                 None
             };
-            (basic, stage1, Some(card.clone()))
+            (basic, stage1, Some(*card))
         }
         _ => (None, None, None),
     }
@@ -86,12 +79,12 @@ fn get_evolution_line(card: &Card) -> (Option<Card>, Option<Card>, Option<Card>)
 /// Find a Stage 1 evolution for a given Basic Pokemon name.
 fn find_stage1_evolution(basic_name: &str) -> Option<Card> {
     for id in CardId::iter() {
-        let card = get_card_by_enum(id);
-        if let Card::Pokemon(pokemon) = &card {
+        let card = get_card_ref(id);
+        if let Card::Pokemon(pokemon) = card {
             if pokemon.stage == 1 {
                 if let Some(ref evolves_from) = pokemon.evolves_from {
                     if evolves_from == basic_name {
-                        return Some(card);
+                        return Some(*card);
                     }
                 }
             }
@@ -103,12 +96,12 @@ fn find_stage1_evolution(basic_name: &str) -> Option<Card> {
 /// Find a Stage 2 evolution for a given Stage 1 Pokemon name.
 fn find_stage2_evolution(stage1_name: &str) -> Option<Card> {
     for id in CardId::iter() {
-        let card = get_card_by_enum(id);
-        if let Card::Pokemon(pokemon) = &card {
+        let card = get_card_ref(id);
+        if let Card::Pokemon(pokemon) = card {
             if pokemon.stage == 2 {
                 if let Some(ref evolves_from) = pokemon.evolves_from {
                     if evolves_from == stage1_name {
-                        return Some(card);
+                        return Some(*card);
                     }
                 }
             }
@@ -120,13 +113,13 @@ fn find_stage2_evolution(stage1_name: &str) -> Option<Card> {
 /// Find a card by exact name match.
 fn find_card_by_name(name: &str) -> Option<Card> {
     for id in CardId::iter() {
-        let card = get_card_by_enum(id);
-        let card_name = match &card {
+        let card = get_card_ref(id);
+        let card_name = match card {
             Card::Pokemon(pokemon) => &pokemon.name,
             Card::Trainer(trainer) => &trainer.name,
         };
         if card_name == name {
-            return Some(card);
+            return Some(*card);
         }
     }
     None
@@ -155,7 +148,7 @@ fn generate_fossil_deck(fossil_card: &Card) -> String {
                 let (_, stage1, stage2) = get_evolution_line(&pokemon_card);
 
                 // Build evolution line with fossil instead of basic
-                let evolution_line = vec![Some(fossil_card.clone()), stage1, stage2];
+                let evolution_line = vec![Some(*fossil_card), stage1, stage2];
                 let fallback = get_valid_fallback_energy(pokemon.energy_type);
                 generate_evolution_line_deck(&evolution_line, fallback)
             } else {
@@ -172,8 +165,8 @@ fn generate_fossil_deck(fossil_card: &Card) -> String {
 /// Find the highest evolution Pokemon that can evolve from a fossil.
 fn find_highest_fossil_evolution(fossil_name: &str) -> Option<Card> {
     // Find all Pokemon that directly evolve from this fossil
-    let direct_evolutions: Vec<Card> = CardId::iter()
-        .map(get_card_by_enum)
+    let direct_evolutions: Vec<&Card> = CardId::iter()
+        .map(get_card_ref)
         .filter(|card| {
             if let Card::Pokemon(pokemon) = card {
                 pokemon
@@ -188,7 +181,7 @@ fn find_highest_fossil_evolution(fossil_name: &str) -> Option<Card> {
 
     // Find the highest stage evolution from the direct evolutions
     direct_evolutions
-        .iter()
+        .into_iter()
         .filter_map(find_highest_evolution)
         .max_by_key(|card| {
             if let Card::Pokemon(pokemon) = card {
@@ -217,7 +210,7 @@ fn find_highest_evolution(card: &Card) -> Option<Card> {
     }
 
     // This is the highest evolution
-    Some(card.clone())
+    Some(*card)
 }
 
 /// Calculate energy types from all attacks in the Pokemon list.
@@ -254,7 +247,7 @@ fn calculate_energy_types(pokemon_list: &[&PokemonCard], fallback_type: EnergyTy
         .join(", ")
 }
 
-fn format_card_line(card: &Card, count: u8) -> String {
+pub(crate) fn format_card_line(card: &Card, count: u8) -> String {
     let id = card.get_id();
     let formatted_id = format_card_id(&id);
 
@@ -294,7 +287,7 @@ fn generate_evolution_line_deck(
         .iter()
         .filter_map(|c| {
             if let Card::Pokemon(p) = c {
-                Some(p)
+                Some(*p)
             } else {
                 None
             }
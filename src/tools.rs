@@ -2,7 +2,7 @@ use std::sync::LazyLock;
 
 use crate::{
     card_ids::CardId,
-    database::get_card_by_enum,
+    database::get_card_ref,
     models::{Card, EnergyType, PlayedCard, TrainerCard, TrainerType},
     State,
 };
@@ -24,29 +24,27 @@ pub(crate) fn ensure_tool_trainer(trainer_card: &TrainerCard) -> &TrainerCard {
     trainer_card
 }
 
-fn tool_effect_text_from_card_id(tool_card_id: CardId) -> String {
-    let card = get_card_by_enum(tool_card_id);
-    let trainer_card = ensure_tool_card(&card);
-    trainer_card.effect.clone()
+fn tool_effect_text_from_card_id(tool_card_id: CardId) -> &'static str {
+    ensure_tool_card(get_card_ref(tool_card_id)).effect.as_str()
 }
 
-static GIANT_CAPE_EFFECT: LazyLock<String> =
+static GIANT_CAPE_EFFECT: LazyLock<&'static str> =
     LazyLock::new(|| tool_effect_text_from_card_id(CardId::A2147GiantCape));
-static ROCKY_HELMET_EFFECT: LazyLock<String> =
+static ROCKY_HELMET_EFFECT: LazyLock<&'static str> =
     LazyLock::new(|| tool_effect_text_from_card_id(CardId::A2148RockyHelmet));
-static POISON_BARB_EFFECT: LazyLock<String> =
+static POISON_BARB_EFFECT: LazyLock<&'static str> =
     LazyLock::new(|| tool_effect_text_from_card_id(CardId::A3146PoisonBarb));
-static LEAF_CAPE_EFFECT: LazyLock<String> =
+static LEAF_CAPE_EFFECT: LazyLock<&'static str> =
     LazyLock::new(|| tool_effect_text_from_card_id(CardId::A3147LeafCape));
-static ELECTRICAL_CORD_EFFECT: LazyLock<String> =
+static ELECTRICAL_CORD_EFFECT: LazyLock<&'static str> =
     LazyLock::new(|| tool_effect_text_from_card_id(CardId::A3a065ElectricalCord));
-static INFLATABLE_BOAT_EFFECT: LazyLock<String> =
+static INFLATABLE_BOAT_EFFECT: LazyLock<&'static str> =
     LazyLock::new(|| tool_effect_text_from_card_id(CardId::A4a067InflatableBoat));
-static HEAVY_HELMET_EFFECT: LazyLock<String> =
+static HEAVY_HELMET_EFFECT: LazyLock<&'static str> =
     LazyLock::new(|| tool_effect_text_from_card_id(CardId::B1219HeavyHelmet));
-static PROTECTIVE_PONCHO_EFFECT: LazyLock<String> =
+static PROTECTIVE_PONCHO_EFFECT: LazyLock<&'static str> =
     LazyLock::new(|| tool_effect_text_from_card_id(CardId::B2147ProtectivePoncho));
-static METAL_CORE_BARRIER_EFFECT: LazyLock<String> =
+static METAL_CORE_BARRIER_EFFECT: LazyLock<&'static str> =
     LazyLock::new(|| tool_effect_text_from_card_id(CardId::B2148MetalCoreBarrier));
 
 pub fn tool_effects_equal(trainer_card: &TrainerCard, reference_tool_id: CardId) -> bool {
@@ -66,16 +64,16 @@ pub fn has_tool(played_card: &PlayedCard, reference_tool_id: CardId) -> bool {
 pub fn can_attach_tool_to(trainer_card: &TrainerCard, pokemon: &PlayedCard) -> bool {
     let trainer_card = ensure_tool_trainer(trainer_card);
     let effect = trainer_card.effect.as_str();
-    if effect == LEAF_CAPE_EFFECT.as_str() {
+    if effect == *LEAF_CAPE_EFFECT {
         return pokemon.card.get_type() == Some(EnergyType::Grass);
     }
-    if effect == ELECTRICAL_CORD_EFFECT.as_str() {
+    if effect == *ELECTRICAL_CORD_EFFECT {
         return pokemon.card.get_type() == Some(EnergyType::Lightning);
     }
-    if effect == INFLATABLE_BOAT_EFFECT.as_str() {
+    if effect == *INFLATABLE_BOAT_EFFECT {
         return pokemon.card.get_type() == Some(EnergyType::Water);
     }
-    if effect == METAL_CORE_BARRIER_EFFECT.as_str() {
+    if effect == *METAL_CORE_BARRIER_EFFECT {
         return pokemon.card.get_type() == Some(EnergyType::Metal);
     }
     true
@@ -99,14 +97,14 @@ pub fn is_tool_effect_implemented(trainer_card: &TrainerCard) -> bool {
     let effect = trainer_card.effect.as_str();
     matches!(
         effect,
-        e if e == GIANT_CAPE_EFFECT.as_str()
-            || e == ROCKY_HELMET_EFFECT.as_str()
-            || e == POISON_BARB_EFFECT.as_str()
-            || e == LEAF_CAPE_EFFECT.as_str()
-            || e == ELECTRICAL_CORD_EFFECT.as_str()
-            || e == INFLATABLE_BOAT_EFFECT.as_str()
-            || e == HEAVY_HELMET_EFFECT.as_str()
-            || e == PROTECTIVE_PONCHO_EFFECT.as_str()
-            || e == METAL_CORE_BARRIER_EFFECT.as_str()
+        e if e == *GIANT_CAPE_EFFECT
+            || e == *ROCKY_HELMET_EFFECT
+            || e == *POISON_BARB_EFFECT
+            || e == *LEAF_CAPE_EFFECT
+            || e == *ELECTRICAL_CORD_EFFECT
+            || e == *INFLATABLE_BOAT_EFFECT
+            || e == *HEAVY_HELMET_EFFECT
+            || e == *PROTECTIVE_PONCHO_EFFECT
+            || e == *METAL_CORE_BARRIER_EFFECT
     )
 }
@@ -46,6 +46,12 @@ impl App {
         // Load decks from files
         let deck_a = Deck::from_file(deck_a_path)?;
         let deck_b = Deck::from_file(deck_b_path)?;
+        deck_a
+            .check_valid()
+            .map_err(|err| format!("{deck_a_path} is not valid: {err}"))?;
+        deck_b
+            .check_valid()
+            .map_err(|err| format!("{deck_b_path} is not valid: {err}"))?;
 
         // Detect if any player is human
         let has_human = player_codes.contains(&PlayerCode::H);
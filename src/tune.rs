@@ -0,0 +1,433 @@
+use log::warn;
+use num_format::{Locale, ToFormattedString};
+use serde::Serialize;
+
+use crate::{
+    card_ids::CardId,
+    database::{get_card_by_enum, get_card_ref},
+    deck_builder::has_coherent_evolutions,
+    gauntlet::{run_gauntlet, GauntletReport, Meta},
+    inventory::Inventory,
+    optimize::{try_parse_card_id_string, ParallelConfig, SimulationConfig},
+    simulate::{create_progress_bar, deck_name_from_path},
+    statistics::{win_rate_difference, MatchTally},
+    Deck,
+};
+
+/// How a variant differs from the tuned deck
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum ChangeKind {
+    /// One copy of a deck card replaced by a candidate card
+    Swap,
+    /// One copy of a deck card moved to another card already in the deck (−1/+1 copy)
+    Copy,
+}
+
+/// A deck that differs from the tuned one by a single card
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct DeckChange {
+    pub kind: ChangeKind,
+    pub removed: CardId,
+    pub added: CardId,
+}
+
+impl DeckChange {
+    /// e.g. "-1 Potion (P-A 001) +1 Sabrina (A1 225)"
+    pub fn label(&self) -> String {
+        let describe = |id: CardId| {
+            let card = get_card_ref(id);
+            format!("{} ({})", card.get_name(), card.get_id())
+        };
+        format!("-1 {} +1 {}", describe(self.removed), describe(self.added))
+    }
+}
+
+/// Gauntlet results of one variant and how they compare to the tuned deck
+#[derive(Debug, Clone, Serialize)]
+pub struct VariantResult {
+    pub change: DeckChange,
+    pub report: GauntletReport,
+    /// Weighted win rate of the variant minus the one of the tuned deck
+    pub delta: f64,
+    /// 95% confidence interval of `delta`
+    pub delta_confidence_interval: (f64, f64),
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct TuneReport {
+    pub baseline: GauntletReport,
+    /// Variants from the biggest improvement to the biggest loss
+    pub variants: Vec<VariantResult>,
+}
+
+/// Every valid deck one card away from `deck`: each distinct card loses a copy in favor of
+/// a candidate (swaps) or of another card of the deck (−1/+1 copy variants).
+/// Variants breaking the 2-copy limit, the basic requirement or an evolution line are skipped,
+/// as are those the inventory (if any) can't provide. Decks with cards loaded at runtime can't
+/// be tuned, as changes are made of `CardId`s.
+pub fn deck_changes(
+    deck: &Deck,
+    candidates: &[CardId],
+    inventory: Option<&Inventory>,
+) -> Result<Vec<(DeckChange, Deck)>, String> {
+    let deck_ids = deck
+        .card_ids()
+        .map_err(|err| format!("Can't tune the deck: {err}"))?;
+    let mut in_deck: Vec<CardId> = Vec::new();
+    for &id in &deck_ids {
+        if !in_deck.contains(&id) {
+            in_deck.push(id);
+        }
+    }
+    let mut additions: Vec<(ChangeKind, CardId)> = Vec::new();
+    for &id in candidates {
+        if !in_deck.contains(&id) && !additions.contains(&(ChangeKind::Swap, id)) {
+            additions.push((ChangeKind::Swap, id));
+        }
+    }
+    additions.extend(in_deck.iter().map(|&id| (ChangeKind::Copy, id)));
+
+    let mut changes = Vec::new();
+    for &removed in &in_deck {
+        for &(kind, added) in &additions {
+            if added == removed {
+                continue;
+            }
+            let index = deck_ids
+                .iter()
+                .position(|&id| id == removed)
+                .expect("removed card comes from the deck");
+            let mut variant = deck.clone();
+            variant.cards[index] = get_card_by_enum(added);
+            let affordable = inventory.is_none_or(|inventory| {
+                let mut cards = deck_ids.clone();
+                cards[index] = added;
+                inventory.allows(&cards)
+            });
+            if variant.is_valid() && has_coherent_evolutions(&variant.cards) && affordable {
+                changes.push((
+                    DeckChange {
+                        kind,
+                        removed,
+                        added,
+                    },
+                    variant,
+                ));
+            }
+        }
+    }
+    Ok(changes)
+}
+
+/// Plays `deck` and every single-card variant of it (see `deck_changes`) against a meta.
+///
+/// All decks play the same seeds, so differences come from the cards rather than the draws.
+/// `on_game_complete` is called after every game, e.g. to advance a progress bar.
+pub fn run_tune<F>(
+    deck: &Deck,
+    candidates: &[CardId],
+    inventory: Option<&Inventory>,
+    meta: &Meta,
+    sim_config: &SimulationConfig,
+    parallel_config: &ParallelConfig,
+    on_game_complete: Option<F>,
+) -> Result<TuneReport, String>
+where
+    F: Fn() + Sync,
+{
+    let changes = deck_changes(deck, candidates, inventory)?;
+    let baseline = run_gauntlet(
+        deck,
+        meta,
+        sim_config,
+        parallel_config,
+        on_game_complete.as_ref(),
+    );
+    let baseline_items = weighted_tallies(&baseline);
+
+    let mut variants: Vec<VariantResult> = changes
+        .into_iter()
+        .map(|(change, variant)| {
+            let report = run_gauntlet(
+                &variant,
+                meta,
+                sim_config,
+                parallel_config,
+                on_game_complete.as_ref(),
+            );
+            let (delta, delta_confidence_interval) =
+                win_rate_difference(&weighted_tallies(&report), &baseline_items);
+            VariantResult {
+                change,
+                report,
+                delta,
+                delta_confidence_interval,
+            }
+        })
+        .collect();
+    variants.sort_by(|a, b| b.delta.total_cmp(&a.delta));
+    Ok(TuneReport { baseline, variants })
+}
+
+fn weighted_tallies(report: &GauntletReport) -> Vec<(f64, MatchTally)> {
+    report
+        .matchups
+        .iter()
+        .map(|matchup| (matchup.share, matchup.tally))
+        .collect()
+}
+
+/// Runs `run_tune` with a progress bar and prints the variants table, as the CLI does.
+/// `candidate_cards` is a comma-separated list of card ids.
+pub fn cli_tune(
+    deck_path: &str,
+    candidate_cards: &str,
+    inventory: Option<Inventory>,
+    meta: &Meta,
+    sim_config: SimulationConfig,
+    parallel_config: ParallelConfig,
+) -> Option<TuneReport> {
+    let deck = match Deck::from_file(deck_path) {
+        Ok(deck) => match deck.check_valid() {
+            Ok(()) => deck,
+            Err(err) => {
+                warn!("{deck_path} is not a complete, valid deck: {err}");
+                return None;
+            }
+        },
+        Err(err) => {
+            warn!("Failed to load deck {deck_path}: {err}");
+            return None;
+        }
+    };
+    let candidates: Vec<CardId> = match candidate_cards
+        .split(',')
+        .map(str::trim)
+        .filter(|id| !id.is_empty())
+        .map(try_parse_card_id_string)
+        .collect()
+    {
+        Ok(candidates) => candidates,
+        Err(err) => {
+            warn!("{err}");
+            return None;
+        }
+    };
+
+    let num_variants = match deck_changes(&deck, &candidates, inventory.as_ref()) {
+        Ok(changes) => changes.len(),
+        Err(err) => {
+            warn!("{err}");
+            return None;
+        }
+    };
+    warn!(
+        "Tuning {} with {} variants, {} games each against {} decks{}",
+        deck_name_from_path(deck_path),
+        num_variants,
+        sim_config.num_games.to_formatted_string(&Locale::en),
+        meta.entries.len(),
+        if parallel_config.enabled {
+            " (parallel)"
+        } else {
+            ""
+        }
+    );
+
+    let total_games = (num_variants as u64 + 1) * sim_config.num_games as u64;
+    let pb = create_progress_bar(total_games);
+    pb.tick(); // Ensure progress bar is drawn immediately
+    let report = run_tune(
+        &deck,
+        &candidates,
+        inventory.as_ref(),
+        meta,
+        &sim_config,
+        &parallel_config,
+        Some(|| pb.inc(1)),
+    );
+    pb.finish_with_message("Tuning complete!");
+    let report = match report {
+        Ok(report) => report,
+        Err(err) => {
+            warn!("{err}");
+            return None;
+        }
+    };
+
+    print_tune_report(&report);
+    Some(report)
+}
+
+/// Prints the tuned deck's win rate and every variant's change from it
+pub fn print_tune_report(report: &TuneReport) {
+    let (low, high) = report.baseline.weighted_confidence_interval;
+    warn!(
+        "Current list: {:.2}% weighted win rate (95% CI {:.2}%-{:.2}%) over {} games",
+        report.baseline.weighted_win_rate * 100.0,
+        low * 100.0,
+        high * 100.0,
+        report
+            .baseline
+            .total_games()
+            .to_formatted_string(&Locale::en)
+    );
+    let labels: Vec<String> = report
+        .variants
+        .iter()
+        .map(|variant| variant.change.label())
+        .collect();
+    let label_width = labels
+        .iter()
+        .map(|label| label.chars().count())
+        .max()
+        .unwrap_or(0)
+        .max("Change".len());
+    warn!(
+        "{:<label_width$}  {:>6}  {:>7}  {:>17}",
+        "Change", "Win%", "Delta", "95% CI of delta"
+    );
+    for (variant, label) in report.variants.iter().zip(&labels) {
+        let (low, high) = variant.delta_confidence_interval;
+        warn!(
+            "{:<label_width$}  {:>5.1}%  {:>+6.1}%  {:>17}",
+            label,
+            variant.report.weighted_win_rate * 100.0,
+            variant.delta * 100.0,
+            format!("{:+.1}% to {:+.1}%", low * 100.0, high * 100.0)
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        gauntlet::MetaEntry,
+        models::{Card, PokemonCard},
+        players::PlayerCode,
+    };
+
+    #[test]
+    fn test_deck_changes_respect_deck_rules() {
+        let deck = Deck::from_file("example_decks/venusaur-exeggutor.txt").unwrap();
+        // Giovanni is new, Sabrina is already in the deck and Charmeleon has no Charmander
+        let candidates = [
+            CardId::A1223Giovanni,
+            CardId::A1225Sabrina,
+            CardId::A1034Charmeleon,
+        ];
+        let changes = deck_changes(&deck, &candidates, None).unwrap();
+
+        assert!(!changes.is_empty());
+        for (change, variant) in &changes {
+            assert!(variant.is_valid(), "{}", change.label());
+            assert!(has_coherent_evolutions(&variant.cards));
+            assert_ne!(change.removed, change.added);
+        }
+        let has_change = |kind, removed, added| {
+            changes.iter().any(|(change, _)| {
+                change.kind == kind && change.removed == removed && change.added == added
+            })
+        };
+        assert!(has_change(
+            ChangeKind::Swap,
+            CardId::PA006RedCard,
+            CardId::A1223Giovanni
+        ));
+        // +1 Sabrina is a copy change, never a swap
+        assert!(has_change(
+            ChangeKind::Copy,
+            CardId::PA006RedCard,
+            CardId::A1225Sabrina
+        ));
+        assert!(!changes
+            .iter()
+            .any(|(change, _)| change.kind == ChangeKind::Swap
+                && change.added == CardId::A1225Sabrina));
+        assert!(!changes
+            .iter()
+            .any(|(change, _)| change.added == CardId::A1034Charmeleon));
+        // A third Erika would break the 2-copy limit
+        assert!(!changes
+            .iter()
+            .any(|(change, _)| change.added == CardId::A1219Erika));
+    }
+
+    #[test]
+    fn test_run_tune_reports_every_variant() {
+        let deck = Deck::from_file("example_decks/venusaur-exeggutor.txt").unwrap();
+        let meta = Meta {
+            entries: vec![MetaEntry {
+                name: "weezing-arbok".to_string(),
+                deck: Deck::from_file("example_decks/weezing-arbok.txt").unwrap(),
+                weight: 1.0,
+            }],
+        };
+        let sim_config = SimulationConfig {
+            num_games: 4,
+            players: Some(vec![PlayerCode::R, PlayerCode::R]),
+            seed: Some(7),
+        };
+        let candidates = [CardId::A1223Giovanni];
+
+        let report = run_tune(
+            &deck,
+            &candidates,
+            None,
+            &meta,
+            &sim_config,
+            &ParallelConfig::default(),
+            None::<fn()>,
+        )
+        .unwrap();
+        assert_eq!(report.baseline.total_games(), 4);
+        assert_eq!(
+            report.variants.len(),
+            deck_changes(&deck, &candidates, None).unwrap().len()
+        );
+        assert!(report
+            .variants
+            .windows(2)
+            .all(|pair| pair[0].delta >= pair[1].delta));
+        for variant in &report.variants {
+            let (low, high) = variant.delta_confidence_interval;
+            assert!(low <= variant.delta && variant.delta <= high);
+        }
+    }
+
+    #[test]
+    fn test_decks_with_runtime_cards_cant_be_tuned() {
+        let mut deck = Deck::from_file("example_decks/venusaur-exeggutor.txt").unwrap();
+        let Card::Pokemon(bulbasaur) = deck.cards[0] else {
+            panic!("the deck starts with Bulbasaur");
+        };
+        let runtime_bulbasaur = Card::from(PokemonCard {
+            id: "Z7 002".to_string(),
+            ..bulbasaur.clone()
+        });
+        deck.cards[0] = runtime_bulbasaur;
+        let err = deck_changes(&deck, &[CardId::A1223Giovanni], None).unwrap_err();
+        assert!(err.contains("Z7 002"), "{err}");
+    }
+
+    #[test]
+    fn test_deck_changes_limited_by_inventory() {
+        let deck = Deck::from_file("example_decks/venusaur-exeggutor.txt").unwrap();
+        let candidates = [CardId::A1223Giovanni];
+        let mut inventory = Inventory::from_file("example_decks/venusaur-exeggutor.txt").unwrap();
+
+        // Owning exactly the deck, every change needs a card that isn't owned: a Giovanni,
+        // a second Sabrina or a second Red Card
+        assert!(deck_changes(&deck, &candidates, Some(&inventory))
+            .unwrap()
+            .is_empty());
+
+        inventory.add(CardId::A1223Giovanni, 1);
+        let changes = deck_changes(&deck, &candidates, Some(&inventory)).unwrap();
+        assert!(!changes.is_empty());
+        assert!(changes
+            .iter()
+            .all(|(change, _)| change.added == CardId::A1223Giovanni));
+    }
+}
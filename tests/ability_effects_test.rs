@@ -69,3 +69,83 @@ fn test_serperior_jungle_totem_ability() {
         assert_eq!(index, 0, "Attack index should be 0 (Vine Whip)");
     }
 }
+
+#[test]
+fn test_victreebel_fragrance_trap_needs_benched_basic() {
+    let mut game = get_initialized_game(0);
+    let mut state = game.get_state_clone();
+    let test_player = state.current_player;
+    let opponent = (test_player + 1) % 2;
+
+    state.set_board(
+        test_player,
+        vec![PlayedCard::from_id(CardId::A1020Victreebel)],
+    );
+    // Only an evolved Pokémon on the opponent's bench: nothing to switch in
+    state.set_board(
+        opponent,
+        vec![
+            PlayedCard::from_id(CardId::A1001Bulbasaur),
+            PlayedCard::from_id(CardId::A1002Ivysaur),
+        ],
+    );
+    state.move_generation_stack.clear();
+    game.set_state(state.clone());
+
+    let (_, actions) = generate_possible_actions(&state);
+    assert!(
+        !actions
+            .iter()
+            .any(|action| matches!(action.action, SimpleAction::UseAbility { .. })),
+        "Fragrance Trap should not be usable without a Benched Basic Pokémon"
+    );
+
+    state.set_board(
+        opponent,
+        vec![
+            PlayedCard::from_id(CardId::A1002Ivysaur),
+            PlayedCard::from_id(CardId::A1001Bulbasaur),
+        ],
+    );
+    let (_, actions) = generate_possible_actions(&state);
+    assert!(actions
+        .iter()
+        .any(|action| matches!(action.action, SimpleAction::UseAbility { in_play_idx: 0 })));
+}
+
+#[test]
+fn test_espeon_ex_psychic_healing_needs_damaged_pokemon() {
+    let mut game = get_initialized_game(0);
+    let mut state = game.get_state_clone();
+    let test_player = state.current_player;
+
+    state.set_board(
+        test_player,
+        vec![
+            PlayedCard::from_id(CardId::A4083EspeonEx),
+            PlayedCard::from_id(CardId::A1001Bulbasaur),
+        ],
+    );
+    state.move_generation_stack.clear();
+    game.set_state(state.clone());
+
+    let (_, actions) = generate_possible_actions(&state);
+    assert!(
+        !actions
+            .iter()
+            .any(|action| matches!(action.action, SimpleAction::UseAbility { .. })),
+        "Psychic Healing should not be usable without a damaged Pokémon"
+    );
+
+    state.set_board(
+        test_player,
+        vec![
+            PlayedCard::from_id(CardId::A4083EspeonEx),
+            PlayedCard::from_id(CardId::A1001Bulbasaur).with_damage(30),
+        ],
+    );
+    let (_, actions) = generate_possible_actions(&state);
+    assert!(actions
+        .iter()
+        .any(|action| matches!(action.action, SimpleAction::UseAbility { in_play_idx: 0 })));
+}
@@ -81,9 +81,7 @@ fn test_cloned_cards_are_equal() {
         get_card_by_enum(CardId::A1177Weezing)
     );
     assert!(get_card_by_enum(CardId::A1177Weezing) == get_card_by_enum(CardId::A1177Weezing));
-    assert!(
-        get_card_by_enum(CardId::A1177Weezing).clone() == get_card_by_enum(CardId::A1177Weezing)
-    );
+    assert!(get_card_by_enum(CardId::A1177Weezing) == get_card_by_enum(CardId::A1177Weezing));
 }
 
 #[test]
@@ -124,9 +122,7 @@ fn test_play_pokeball_action() {
     let hand = state.hands[current_player].clone();
     let pokeball = &hand[2];
     if let Card::Trainer(trainer_card) = pokeball {
-        let action = SimpleAction::Play {
-            trainer_card: trainer_card.clone(),
-        };
+        let action = SimpleAction::Play { trainer_card };
 
         let deck_size = state.decks[state.current_player].cards.len();
         let action = Action {
@@ -154,7 +150,7 @@ fn test_place_action() {
     let current_player = state.current_player;
 
     let basic_pokemon = get_card_by_enum(CardId::A1001Bulbasaur);
-    state.hands[current_player].push(basic_pokemon.clone());
+    state.hands[current_player].push(basic_pokemon);
     state.hands_visibility[current_player].push(true);
     let action = SimpleAction::Place(basic_pokemon, 2);
 
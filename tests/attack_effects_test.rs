@@ -3,6 +3,7 @@ use deckgym::{
     actions::{Action, SimpleAction},
     card_ids::CardId,
     database::get_card_by_enum,
+    errata::Errata,
     models::{EnergyType, PlayedCard},
 };
 
@@ -20,7 +21,7 @@ fn test_weedle_multiply_attack() {
 
     // Set up player 0 with Weedle in active position
     let active_weedle = PlayedCard::new(
-        weedle_card.clone(),
+        weedle_card,
         50,                      // remaining_hp
         50,                      // total_hp
         vec![EnergyType::Grass], // Has 1 Grass energy to use Multiply
@@ -39,7 +40,7 @@ fn test_weedle_multiply_attack() {
     }
 
     // Add another Weedle to the deck
-    state.decks[0].cards.push(weedle_card.clone());
+    state.decks[0].cards.push(weedle_card);
 
     // Count bench pokemon before attack
     let bench_count_before = state.enumerate_bench_pokemon(0).count();
@@ -289,3 +290,67 @@ fn test_discard_hand_card_attack() {
         assert!(matches!(last.1[0], SimpleAction::EndTurn));
     }
 }
+
+#[test]
+fn test_vaporeon_hyper_whirlpool_discards_at_most_attached_energy() {
+    let mut game = get_initialized_game(0);
+    let mut state = game.get_state_clone();
+    state.current_player = 0;
+    state.set_board(
+        0,
+        vec![
+            PlayedCard::from_id(CardId::A3b016Vaporeon).with_energy(vec![
+                EnergyType::Water,
+                EnergyType::Water,
+                EnergyType::Water,
+            ]),
+        ],
+    );
+    state.set_board(
+        1,
+        vec![PlayedCard::from_id(CardId::A1002Ivysaur)
+            .with_energy(vec![EnergyType::Grass, EnergyType::Fire])],
+    );
+    game.set_state(state);
+
+    // Outcome 3 is three heads, more than the two energies attached
+    let attack_action = Action {
+        actor: 0,
+        action: SimpleAction::Attack(0),
+        is_stack: false,
+    };
+    game.apply_action_with_outcome(&attack_action, 3);
+
+    let state = game.get_state_clone();
+    assert!(state.get_active(1).attached_energy.is_empty());
+    assert_eq!(state.discard_energies[1].len(), 2);
+}
+
+#[test]
+fn test_errata_changes_attack_damage() {
+    let errata = Errata::from_string("Mewtwo ex: attack Psydrive damage -20").unwrap();
+    let mewtwo = errata.patch(get_card_by_enum(CardId::A1129MewtwoEx));
+
+    let mut game = get_initialized_game(0);
+    let mut state = game.get_state_clone();
+    state.current_player = 0;
+    state.set_board(
+        0,
+        vec![PlayedCard::from_card(&mewtwo).with_energy(vec![EnergyType::Psychic; 4])],
+    );
+    // Golem has no Psychic weakness
+    let golem = PlayedCard::from_id(CardId::A1149Golem);
+    let golem_hp = golem.total_hp;
+    state.set_board(1, vec![golem]);
+    game.set_state(state);
+
+    let attack_action = Action {
+        actor: 0,
+        action: SimpleAction::Attack(1),
+        is_stack: false,
+    };
+    game.apply_action_with_outcome(&attack_action, 0);
+
+    let state = game.get_state_clone();
+    assert_eq!(state.get_active(1).remaining_hp, golem_hp - 130);
+}
@@ -576,7 +576,7 @@ fn test_quick_grow_extract_evolves_from_deck() {
 
     // Put exactly ONE Ivysaur (evolution of Bulbasaur, Grass type) in the deck
     let ivysaur = get_card_by_enum(CardId::A1002Ivysaur);
-    state.decks[0].cards.push(ivysaur.clone());
+    state.decks[0].cards.push(ivysaur);
 
     // Add some other cards to the deck so it's not empty
     state.decks[0]
@@ -585,7 +585,7 @@ fn test_quick_grow_extract_evolves_from_deck() {
 
     // Put Quick-Grow Extract in hand
     let extract = get_card_by_enum(CardId::B1a067QuickGrowExtract);
-    state.hands[0].push(extract.clone());
+    state.hands[0].push(extract);
     state.hands_visibility[0].push(true);
 
     game.set_state(state);
@@ -630,7 +630,7 @@ fn test_charmeleon_ignition() {
         state.in_play_pokemon[0][0] = Some(PlayedCard::from_id(CardId::A1033Charmander));
 
         let charmeleon = get_card_by_enum(CardId::B1a012Charmeleon);
-        state.hands[0].push(charmeleon.clone());
+        state.hands[0].push(charmeleon);
         state.hands_visibility[0].push(true);
 
         game.set_state(state);
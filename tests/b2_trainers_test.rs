@@ -28,7 +28,7 @@ fn test_piers_requires_obstagoon_and_discards_energy() {
         .unwrap()
         .attached_energy
         .push(EnergyType::Grass);
-    state.hands[0].push(piers.clone());
+    state.hands[0].push(piers);
     state.hands_visibility[0].push(true);
 
     let mut rng = rand::rngs::StdRng::seed_from_u64(42);
@@ -65,14 +65,9 @@ fn test_sightseer_draws_stage1_pokemon() {
     let bulbasaur = get_card_by_enum(CardId::A1001Bulbasaur); // Stage 0
 
     state.turn_count = 1;
-    state.hands[0].push(sightseer.clone());
+    state.hands[0].push(sightseer);
     state.hands_visibility[0].push(true);
-    state.decks[0].cards = vec![
-        ivysaur.clone(),
-        bulbasaur.clone(),
-        bulbasaur.clone(),
-        bulbasaur.clone(),
-    ];
+    state.decks[0].cards = vec![ivysaur, bulbasaur, bulbasaur, bulbasaur];
 
     let mut rng = rand::rngs::StdRng::seed_from_u64(42);
     let (_, actions) = generate_possible_actions(&state);
@@ -106,7 +101,7 @@ fn test_juggler_requires_3_types_and_moves_energy() {
         .unwrap()
         .attached_energy
         .push(EnergyType::Water);
-    state.hands[0].push(juggler.clone());
+    state.hands[0].push(juggler);
     state.hands_visibility[0].push(true);
 
     let mut rng = rand::rngs::StdRng::seed_from_u64(42);
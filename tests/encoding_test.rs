@@ -11,7 +11,7 @@ fn test_known_cards_encoding() {
     // Player 0 observes Player 1
     // Mark one card in P1's deck as visible
     state.decks[1].visibility[0] = true;
-    let visible_deck_card = state.decks[1].cards[0].clone();
+    let visible_deck_card = state.decks[1].cards[0];
     let visible_deck_id =
         CardId::from_card_id(&visible_deck_card.get_id()).unwrap() as usize as f32;
 
@@ -27,7 +27,7 @@ fn test_known_cards_encoding() {
         state.hands_visibility[1].push(vis);
     }
     state.hands_visibility[1][0] = true; // Mark 1st as visible
-    let visible_hand_card = state.hands[1][0].clone();
+    let visible_hand_card = state.hands[1][0];
     let visible_hand_id =
         CardId::from_card_id(&visible_hand_card.get_id()).unwrap() as usize as f32;
 
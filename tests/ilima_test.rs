@@ -9,7 +9,7 @@ use deckgym::{
 
 mod common;
 
-fn make_ilima_trainer_card() -> TrainerCard {
+fn make_ilima_trainer_card() -> &'static TrainerCard {
     match get_card_by_enum(CardId::A3149Ilima) {
         Card::Trainer(tc) => tc,
         _ => panic!("Expected trainer card"),
@@ -32,7 +32,7 @@ fn test_ilima_last_pokemon_losses_game() {
     state.hands[current_player].clear();
 
     let trainer_card = make_ilima_trainer_card();
-    state.hands[current_player].push(Card::Trainer(trainer_card.clone()));
+    state.hands[current_player].push(Card::Trainer(trainer_card));
     game.set_state(state);
 
     let play_action = Action {
@@ -78,7 +78,7 @@ fn test_ilima_returns_active_and_triggers_promotion() {
 
     state.hands[current_player].clear();
     let trainer_card = make_ilima_trainer_card();
-    state.hands[current_player].push(Card::Trainer(trainer_card.clone()));
+    state.hands[current_player].push(Card::Trainer(trainer_card));
     game.set_state(state);
 
     let play_action = Action {
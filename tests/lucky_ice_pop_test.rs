@@ -22,7 +22,7 @@ mod tests {
         state.in_play_pokemon[0][0] = Some(played_mankey);
 
         // Hand: Lucky Ice Pop
-        state.hands[0].push(lucky_ice_pop.clone());
+        state.hands[0].push(lucky_ice_pop);
         state.hands_visibility[0].push(true);
 
         let trainer_card = match &lucky_ice_pop {
@@ -39,9 +39,7 @@ mod tests {
         // Test Logic Execution (Heal + Return on Heads)
         let action = Action {
             actor: 0,
-            action: SimpleAction::Play {
-                trainer_card: trainer_card.clone(),
-            },
+            action: SimpleAction::Play { trainer_card },
             is_stack: false,
         };
 
@@ -96,7 +94,7 @@ mod tests {
         state.in_play_pokemon[0][0] = Some(played_mankey);
 
         // Hand: Lucky Ice Pop
-        state.hands[0].push(lucky_ice_pop.clone());
+        state.hands[0].push(lucky_ice_pop);
         state.hands_visibility[0].push(true);
 
         let trainer_card = match &lucky_ice_pop {
@@ -22,10 +22,10 @@ fn test_may_generates_all_combinations() {
     let mut state = State::new(&Deck::default(), &Deck::default());
 
     // Setup player's hand with Pikachu
-    state.hands[0] = vec![pikachu.clone()];
+    state.hands[0] = vec![pikachu];
 
     // Setup player's deck with Charmander and Squirtle
-    state.decks[0].cards = vec![charmander.clone(), squirtle.clone()];
+    state.decks[0].cards = vec![charmander, squirtle];
     state.current_player = 0;
 
     // Call May effect
@@ -41,7 +41,7 @@ fn test_may_generates_all_combinations() {
     let action = Action {
         actor: 0,
         action: SimpleAction::Play {
-            trainer_card: get_card_by_enum(CardId::B1223May).as_trainer().clone(),
+            trainer_card: get_card_by_enum(CardId::B1223May).as_trainer(),
         },
         is_stack: false,
     };
@@ -108,12 +108,7 @@ fn test_may_probabilities_with_multiple_pokemon() {
     let mut state = State::new(&Deck::default(), &Deck::default());
 
     // Setup player's deck with 4 Pokemon
-    state.decks[0].cards = vec![
-        pikachu.clone(),
-        charmander.clone(),
-        squirtle.clone(),
-        bulbasaur.clone(),
-    ];
+    state.decks[0].cards = vec![pikachu, charmander, squirtle, bulbasaur];
     state.current_player = 0;
 
     // Call May effect
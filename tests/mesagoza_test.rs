@@ -47,12 +47,12 @@ fn test_mesagoza_reset_on_new_stadium() {
     let mut state = State::new(&Deck::default(), &Deck::default());
     let mesagoza = get_card_by_enum(CardId::B2a093Mesagoza);
 
-    state.set_stadium(mesagoza.clone(), 0);
+    state.set_stadium(mesagoza, 0);
     state.stadium_used_this_turn = true;
 
     // Played a new Mesagoza (replaces old)
     state.set_stadium(mesagoza, 0);
-    assert_eq!(state.stadium_used_this_turn, false);
+    assert!(!state.stadium_used_this_turn);
 }
 
 #[test]
@@ -62,7 +62,7 @@ fn test_mesagoza_effect_heads() {
     let pikachu = get_card_by_enum(CardId::A1094Pikachu);
 
     // Add Pikachu to deck
-    state.decks[0].cards.push(pikachu.clone());
+    state.decks[0].cards.push(pikachu);
     state.set_stadium(mesagoza, 0);
 
     let action = Action {
@@ -103,7 +103,7 @@ fn test_mesagoza_effect_tails() {
     let mesagoza = get_card_by_enum(CardId::B2a093Mesagoza);
     let pikachu = get_card_by_enum(CardId::A1094Pikachu);
 
-    state.decks[0].cards.push(pikachu.clone());
+    state.decks[0].cards.push(pikachu);
     state.set_stadium(mesagoza, 0);
 
     let action = Action {
@@ -149,8 +149,8 @@ fn test_mesagoza_comprehensive() {
     state.hands[0].clear();
     state.decks[0].cards.clear();
 
-    state.decks[0].cards.push(pikachu.clone());
-    state.decks[0].cards.push(bulbasaur.clone());
+    state.decks[0].cards.push(pikachu);
+    state.decks[0].cards.push(bulbasaur);
     state.set_stadium(mesagoza, 0);
 
     let action = Action {
@@ -199,7 +199,7 @@ fn test_mesagoza_comprehensive() {
 fn test_play_mesagoza_from_hand() {
     let mesagoza = get_card_by_enum(CardId::B2a093Mesagoza);
     let mut state = State::new(&Deck::default(), &Deck::default());
-    state.hands[0].push(mesagoza.clone());
+    state.hands[0].push(mesagoza);
     state.hands_visibility[0].push(true);
     state.turn_count = 1;
 
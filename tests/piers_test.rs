@@ -8,7 +8,7 @@ use deckgym::{
 
 mod common;
 
-fn make_piers_trainer_card() -> TrainerCard {
+fn make_piers_trainer_card() -> &'static TrainerCard {
     match get_card_by_enum(CardId::B2152Piers) {
         Card::Trainer(tc) => tc,
         _ => panic!("Expected trainer card"),
@@ -35,7 +35,7 @@ fn test_piers_discards_two_energies() {
     state.in_play_pokemon[opponent][0] = Some(bulbasaur);
 
     let trainer_card = make_piers_trainer_card();
-    state.hands[current_player].push(Card::Trainer(trainer_card.clone()));
+    state.hands[current_player].push(Card::Trainer(trainer_card));
     state.hands_visibility[current_player].push(true);
     game.set_state(state);
 
@@ -71,7 +71,7 @@ fn test_piers_discards_one_energy_if_only_one_available() {
     state.in_play_pokemon[opponent][0] = Some(bulbasaur);
 
     let trainer_card = make_piers_trainer_card();
-    state.hands[current_player].push(Card::Trainer(trainer_card.clone()));
+    state.hands[current_player].push(Card::Trainer(trainer_card));
     state.hands_visibility[current_player].push(true);
     game.set_state(state);
 
@@ -105,7 +105,7 @@ fn test_piers_is_not_playable_if_opponent_has_no_energy() {
     state.in_play_pokemon[opponent][0] = Some(PlayedCard::from_id(CardId::A1001Bulbasaur));
 
     let trainer_card = make_piers_trainer_card();
-    state.hands[current_player].push(Card::Trainer(trainer_card.clone()));
+    state.hands[current_player].push(Card::Trainer(trainer_card));
     state.hands_visibility[current_player].push(true);
 
     // Verify move generation doesn't include Piers
@@ -137,7 +137,7 @@ fn test_piers_is_not_playable_if_no_obstagoon() {
     state.in_play_pokemon[opponent][0] = Some(bulbasaur);
 
     let trainer_card = make_piers_trainer_card();
-    state.hands[current_player].push(Card::Trainer(trainer_card.clone()));
+    state.hands[current_player].push(Card::Trainer(trainer_card));
     state.hands_visibility[current_player].push(true);
 
     // Verify move generation doesn't include Piers
@@ -19,7 +19,7 @@ fn test_pokemon_center_lady_heals_30_damage() {
         Some(PlayedCard::from_id(CardId::A1001Bulbasaur).with_hp(20));
 
     // Add Pokemon Center Lady to hand
-    let pokemon_center_lady = Card::Trainer(TrainerCard {
+    let pokemon_center_lady = Card::from(TrainerCard {
         id: "A2b 070".to_string(),
         trainer_card_type: deckgym::models::TrainerType::Supporter,
         name: "Pokémon Center Lady".to_string(),
@@ -29,7 +29,7 @@ fn test_pokemon_center_lady_heals_30_damage() {
         rarity: "◊◊".to_string(),
         booster_pack: "Shining Revelry (A2b)".to_string(),
     });
-    state.hands[current_player].push(pokemon_center_lady.clone());
+    state.hands[current_player].push(pokemon_center_lady);
     state.hands_visibility[current_player].push(true);
     game.set_state(state);
 
@@ -88,7 +88,7 @@ fn test_pokemon_center_lady_cures_poisoned() {
         Some(PlayedCard::from_id(CardId::A1001Bulbasaur).with_status(StatusCondition::Poisoned));
 
     // Add Pokemon Center Lady to hand
-    let pokemon_center_lady = Card::Trainer(TrainerCard {
+    let pokemon_center_lady = Card::from(TrainerCard {
         id: "A2b 070".to_string(),
         trainer_card_type: deckgym::models::TrainerType::Supporter,
         name: "Pokémon Center Lady".to_string(),
@@ -98,7 +98,7 @@ fn test_pokemon_center_lady_cures_poisoned() {
         rarity: "◊◊".to_string(),
         booster_pack: "Shining Revelry (A2b)".to_string(),
     });
-    state.hands[current_player].push(pokemon_center_lady.clone());
+    state.hands[current_player].push(pokemon_center_lady);
     state.hands_visibility[current_player].push(true);
     game.set_state(state);
 
@@ -161,7 +161,7 @@ fn test_pokemon_center_lady_heals_and_cures_together() {
     );
 
     // Add Pokemon Center Lady to hand
-    let pokemon_center_lady = Card::Trainer(TrainerCard {
+    let pokemon_center_lady = Card::from(TrainerCard {
         id: "A2b 089".to_string(),
         trainer_card_type: deckgym::models::TrainerType::Supporter,
         name: "Pokémon Center Lady".to_string(),
@@ -171,7 +171,7 @@ fn test_pokemon_center_lady_heals_and_cures_together() {
         rarity: "☆☆".to_string(),
         booster_pack: "Shining Revelry (A2b)".to_string(),
     });
-    state.hands[current_player].push(pokemon_center_lady.clone());
+    state.hands[current_player].push(pokemon_center_lady);
     state.hands_visibility[current_player].push(true);
     game.set_state(state);
 
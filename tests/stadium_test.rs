@@ -9,7 +9,7 @@ fn test_stadium_can_be_set_and_retrieved() {
     assert!(state.get_stadium().is_none());
 
     // Set Stadium
-    state.set_stadium(training_area.clone(), 0);
+    state.set_stadium(training_area, 0);
 
     // Stadium should be set
     assert!(state.get_stadium().is_some());
@@ -23,11 +23,11 @@ fn test_stadium_replacement() {
     let starting_plains = get_card_by_enum(CardId::B2154StartingPlains);
 
     // Set first Stadium
-    state.set_stadium(training_area.clone(), 0);
+    state.set_stadium(training_area, 0);
     assert_eq!(state.get_stadium().unwrap().get_id(), "B2 153");
 
     // Replace with second Stadium
-    state.set_stadium(starting_plains.clone(), 1);
+    state.set_stadium(starting_plains, 1);
     assert_eq!(state.get_stadium().unwrap().get_id(), "B2 154");
 
     // Old Stadium should be in player 1's discard pile
@@ -9,7 +9,7 @@ use deckgym::{
 
 mod common;
 
-fn trainer_from_id(card_id: CardId) -> deckgym::models::TrainerCard {
+fn trainer_from_id(card_id: CardId) -> &'static deckgym::models::TrainerCard {
     match get_card_by_enum(card_id) {
         Card::Trainer(trainer_card) => trainer_card,
         _ => panic!("Expected trainer card"),